@@ -0,0 +1,116 @@
+//! `hm-lex bench`: measure lexer throughput on real files.
+//!
+//! Every file is read once up front, then lexed `--iterations` times. The
+//! report gives token and byte throughput over all iterations, the fastest
+//! single iteration, and heap usage per iteration so results can be compared
+//! across lexer versions and configurations.
+
+use std::time::{Duration, Instant};
+
+use hm_lexer::allocstats::{self, AllocCounters};
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::LexerConfig;
+use hm_lexer::lexer::Lexer;
+
+use crate::{emit, read_bytes};
+
+/// Iterations run when `--iterations` is not given.
+const DEFAULT_ITERATIONS: usize = 10;
+
+/// Run the `bench` subcommand.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut iterations = DEFAULT_ITERATIONS;
+    let mut config = LexerConfig::default();
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" | "-n" => {
+                iterations = match args.next().map(|n| n.parse()) {
+                    Some(Ok(n)) if n > 0 => n,
+                    Some(_) => return Err("--iterations must be a positive integer".to_string()),
+                    None => return Err("--iterations requires a value".to_string()),
+                }
+            }
+            "--recover" => config.error_recovery = true,
+            "--char-offsets" => config.char_offsets = true,
+            "--offsets-only" => config.offsets_only = true,
+            _ => paths.push(arg.as_str()),
+        }
+    }
+    if paths.is_empty() {
+        return Err("bench requires at least one FILE".to_string());
+    }
+
+    let sources = paths
+        .iter()
+        .map(|&path| read_bytes(Some(path)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let bytes: usize = sources.iter().map(Vec::len).sum();
+
+    let mut tokens = 0;
+    let mut total = Duration::ZERO;
+    let mut fastest = Duration::MAX;
+    let mut allocations = 0;
+    let mut peak_bytes = 0;
+    for _ in 0..iterations {
+        allocstats::reset_peak();
+        let before = AllocCounters::now();
+        let started = Instant::now();
+        tokens = 0;
+        for (source, path) in sources.iter().zip(&paths) {
+            tokens += lex_all(source, &config).map_err(|e| format!("{path}: {e}"))?;
+        }
+        let elapsed = started.elapsed();
+        total += elapsed;
+        fastest = fastest.min(elapsed);
+        let after = AllocCounters::now();
+        allocations += after.allocations - before.allocations;
+        peak_bytes = peak_bytes.max(after.peak_live_bytes - before.live_bytes);
+    }
+
+    let secs = total.as_secs_f64();
+    emit(|out| {
+        writeln!(out, "files:         {}", paths.len())?;
+        writeln!(out, "bytes:         {bytes}")?;
+        writeln!(out, "tokens:        {tokens}")?;
+        writeln!(out, "iterations:    {iterations}")?;
+        writeln!(
+            out,
+            "time:          {:.3} ms/iter (fastest {:.3} ms)",
+            secs * 1000.0 / iterations as f64,
+            fastest.as_secs_f64() * 1000.0
+        )?;
+        if secs > 0.0 {
+            let runs = iterations as f64;
+            writeln!(
+                out,
+                "throughput:    {:.0} tokens/s, {:.1} MB/s",
+                (tokens as f64 * runs) / secs,
+                (bytes as f64 * runs) / secs / 1_000_000.0
+            )?;
+        }
+        writeln!(
+            out,
+            "allocations:   {} per iteration",
+            allocations / iterations
+        )?;
+        writeln!(out, "peak heap:     {peak_bytes} bytes")?;
+        Ok(())
+    })
+}
+
+/// Lex `source` to the end, discarding the tokens.
+///
+/// # Returns
+///
+/// The number of tokens produced, excluding `Eof`.
+fn lex_all(source: &[u8], config: &LexerConfig) -> Result<usize, String> {
+    let stream = CharStream::from_bytes(source).map_err(|e| e.to_string())?;
+    let mut lexer = Lexer::with_config(stream, config.clone());
+    let mut count = 0;
+    while !lexer.next_token().map_err(|e| e.to_string())?.is_eof() {
+        count += 1;
+    }
+    Ok(count)
+}
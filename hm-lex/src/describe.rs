@@ -0,0 +1,65 @@
+//! `hm-lex describe`: print the lexical grammar supported by this build.
+//!
+//! `--format json` emits the machine-readable document from
+//! [`hm_lexer::grammar::describe_json`]; the default text format is meant
+//! for humans.
+
+use hm_lexer::grammar::{
+    self, BLOCK_COMMENT, ESCAPES, FLOAT_SUFFIXES, INT_SUFFIXES, LINE_COMMENT, LITERAL_FORMS,
+};
+
+use crate::emit;
+
+/// Run the `describe` subcommand.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut json = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => match args.next().map(String::as_str) {
+                Some("json") => json = true,
+                Some("text") => json = false,
+                Some(other) => return Err(format!("unknown format '{other}'")),
+                None => return Err("--format requires a value".to_string()),
+            },
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    if json {
+        let document = grammar::describe_json();
+        return emit(|out| out.write_all(document.as_bytes()));
+    }
+
+    emit(|out| {
+        let keywords: Vec<_> = grammar::keywords().into_iter().map(|(s, _)| s).collect();
+        writeln!(out, "keywords:    {}", keywords.join(" "))?;
+        let punctuators: Vec<_> = grammar::punctuators().into_iter().map(|(s, _)| s).collect();
+        writeln!(out, "punctuation: {}", punctuators.join(" "))?;
+        writeln!(out, "literals:")?;
+        for form in LITERAL_FORMS {
+            writeln!(
+                out,
+                "  {:<12} {:<28} e.g. {}",
+                form.name, form.pattern, form.example
+            )?;
+        }
+        let suffixes = |list: &[&str]| match list {
+            [] => "(none)".to_string(),
+            list => list.join(" "),
+        };
+        writeln!(
+            out,
+            "suffixes:    int: {}, float: {}",
+            suffixes(INT_SUFFIXES),
+            suffixes(FLOAT_SUFFIXES)
+        )?;
+        let escapes: Vec<_> = ESCAPES.iter().map(|(s, _)| *s).collect();
+        writeln!(out, "escapes:     {} \\u{{XXXX}}", escapes.join(" "))?;
+        writeln!(
+            out,
+            "comments:    {} ... | {} ... {}",
+            LINE_COMMENT, BLOCK_COMMENT.0, BLOCK_COMMENT.1
+        )
+    })
+}
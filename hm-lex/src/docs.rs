@@ -0,0 +1,48 @@
+//! `hm-lex docs`: check the Hummingbird code blocks in Markdown files.
+//!
+//! Every ```` ```hummingbird ```` (or ```` ```hm ````) fenced block is lexed
+//! and each failure is reported against the Markdown file's own lines, so
+//! documentation can be checked in CI alongside the code.
+
+use hm_lexer::markdown::lex_blocks;
+
+use crate::{emit, read_source};
+
+/// Run the `docs` subcommand.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let paths: Vec<Option<&str>> = if args.is_empty() {
+        vec![None]
+    } else {
+        args.iter().map(|p| Some(p.as_str())).collect()
+    };
+
+    let mut blocks = 0;
+    let mut failures = Vec::new();
+    for path in paths {
+        let markdown = read_source(path)?;
+        let name = path.unwrap_or("<stdin>");
+        for lexed in lex_blocks(&markdown) {
+            blocks += 1;
+            if let Err(err) = lexed.result {
+                failures.push(format!("{name}: {}: {err}", err.code().as_str()));
+            }
+        }
+    }
+
+    emit(|out| {
+        for failure in &failures {
+            writeln!(out, "{failure}")?;
+        }
+        writeln!(
+            out,
+            "{blocks} code blocks checked, {} with errors",
+            failures.len()
+        )
+    })?;
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("{} code blocks failed to lex", failures.len()))
+    }
+}
@@ -0,0 +1,13 @@
+//! `hm-lex graph`: dump the lexer's dispatch structure as Graphviz DOT.
+//!
+//! Pipe the output through `dot -Tsvg` to audit how start bytes map to
+//! tokens and which maximal-munch continuations exist.
+
+/// Run the `graph` subcommand.
+pub fn run(args: &[String]) -> Result<(), String> {
+    if let Some(extra) = args.first() {
+        return Err(format!("unexpected argument '{extra}'"));
+    }
+    let dot = hm_lexer::grammar::dispatch_dot();
+    crate::emit(|out| out.write_all(dot.as_bytes()))
+}
@@ -0,0 +1,103 @@
+//! `hm-lex`: command-line front end for the Hummingbird lexer.
+//!
+//! Run `hm-lex help` for the list of subcommands.
+
+mod bench;
+mod describe;
+mod docs;
+mod graph;
+mod minimize;
+mod stats;
+mod textmate;
+mod tokens;
+
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+use hm_lexer::allocstats::CountingAllocator;
+
+/// Counts heap usage for `bench` and `stats`.
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "\
+Usage: hm-lex <command> [args]
+
+Commands:
+  tokens [--format debug|table|snapshot] [FILE]
+                  Print the tokens of FILE (or stdin)
+  minimize [--code CODE | --panic] [FILE]
+                  Shrink FILE to a minimal input that still fails to lex
+  describe [--format text|json]
+                  Print every keyword, operator, and literal form
+  docs [FILE...]  Lex the Hummingbird code blocks in Markdown FILEs
+  graph           Print the lexer dispatch graph in Graphviz DOT format
+  stats [FILE...] Print token, comment, and identifier statistics
+  bench [--iterations N] [--recover] [--char-offsets] [--offsets-only] FILE...
+                  Lex FILEs repeatedly and report throughput and allocations
+  textmate        Print a .tmLanguage.json grammar for editor highlighting
+  help            Show this message";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let result = match command.as_str() {
+        "tokens" => tokens::run(rest),
+        "describe" => describe::run(rest),
+        "docs" => docs::run(rest),
+        "graph" => graph::run(rest),
+        "minimize" => minimize::run(rest),
+        "stats" => stats::run(rest),
+        "bench" => bench::run(rest),
+        "textmate" => textmate::run(rest),
+        "help" | "-h" | "--help" => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        other => Err(format!("unknown command '{other}'\n\n{USAGE}")),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("hm-lex: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Read source text from `path`, or from stdin when `path` is `None` or `-`.
+pub fn read_source(path: Option<&str>) -> Result<String, String> {
+    String::from_utf8(read_bytes(path)?).map_err(|_| "input is not valid UTF-8".to_string())
+}
+
+/// Read raw bytes from `path`, or from stdin when `path` is `None` or `-`.
+pub fn read_bytes(path: Option<&str>) -> Result<Vec<u8>, String> {
+    match path {
+        None | Some("-") => {
+            let mut bytes = Vec::new();
+            io::stdin()
+                .read_to_end(&mut bytes)
+                .map_err(|e| format!("failed to read stdin: {e}"))?;
+            Ok(bytes)
+        }
+        Some(path) => std::fs::read(path).map_err(|e| format!("failed to read '{path}': {e}")),
+    }
+}
+
+/// Write command output to stdout, treating a closed pipe (e.g. `| head`)
+/// as success rather than an error.
+pub fn emit(write: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> Result<(), String> {
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    match write(&mut out).and_then(|()| out.flush()) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+            Err(format!("failed to write output: {e}"))
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,75 @@
+//! `hm-lex minimize`: reduce a failing input to a minimal reproducer.
+//!
+//! By default an input is "interesting" if lexing it fails with any error.
+//! `--code L0003` narrows that to a specific error code, and `--panic` looks
+//! for lexer panics instead. The reduced input is written to stdout.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::LexResult;
+use hm_lexer::minimize::minimize;
+
+use crate::{emit, read_bytes};
+
+/// What makes a candidate input worth keeping.
+enum Target {
+    /// Lexing fails with any error.
+    AnyError,
+    /// Lexing fails with this error code (e.g. `L0003`).
+    Code(String),
+    /// The lexer panics.
+    Panic,
+}
+
+/// Run the `minimize` subcommand.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut target = Target::AnyError;
+    let mut path = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--code" => {
+                let code = args.next().ok_or("--code requires an error code")?;
+                target = Target::Code(code.clone());
+            }
+            "--panic" => target = Target::Panic,
+            _ if path.is_none() => path = Some(arg.as_str()),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    let input = read_bytes(path)?;
+    if let Target::Panic = target {
+        // Candidates panic constantly while reducing; keep stderr readable.
+        panic::set_hook(Box::new(|_| {}));
+    }
+
+    let interesting = |candidate: &[u8]| match &target {
+        Target::AnyError => {
+            matches!(lex(candidate), Ok(Err(_)))
+        }
+        Target::Code(code) => {
+            matches!(lex(candidate), Ok(Err(e)) if e.code().as_str() == code)
+        }
+        Target::Panic => lex(candidate).is_err(),
+    };
+    if !interesting(&input) {
+        return Err("input does not reproduce the requested failure".to_string());
+    }
+
+    let reduced = minimize(&input, interesting);
+    eprintln!("hm-lex: reduced {} bytes to {}", input.len(), reduced.len());
+    emit(|out| out.write_all(&reduced))
+}
+
+/// Lex `bytes` to the end, returning `Err` if the lexer panicked.
+fn lex(bytes: &[u8]) -> Result<LexResult<()>, ()> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut lexer = Lexer::new(CharStream::from_bytes(bytes)?);
+        while !lexer.next_token()?.is_eof() {}
+        Ok(())
+    }))
+    .map_err(|_| ())
+}
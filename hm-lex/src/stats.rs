@@ -0,0 +1,91 @@
+//! `hm-lex stats`: print a health snapshot of one or more source files.
+//!
+//! With several files the statistics are aggregated; with none, stdin is
+//! read.
+
+use hm_lexer::stats::LexStats;
+
+use crate::{emit, read_source};
+
+/// Number of identifiers listed in the frequency table.
+const TOP_IDENTIFIERS: usize = 10;
+
+/// Run the `stats` subcommand.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let paths: Vec<Option<&str>> = if args.is_empty() {
+        vec![None]
+    } else {
+        args.iter().map(|p| Some(p.as_str())).collect()
+    };
+
+    let mut total = LexStats::default();
+    let mut longest = (String::from("<stdin>"), 0, 0);
+    for path in paths {
+        let source = read_source(path)?;
+        let stats = LexStats::collect(&source);
+        if stats.longest_line.1 > longest.2 {
+            let name = path.unwrap_or("<stdin>").to_string();
+            longest = (name, stats.longest_line.0, stats.longest_line.1);
+        }
+        merge(&mut total, stats);
+    }
+
+    emit(|out| {
+        writeln!(out, "bytes:         {}", total.bytes)?;
+        writeln!(out, "lines:         {}", total.lines)?;
+        writeln!(out, "tokens:        {}", total.tokens)?;
+        for (category, count) in &total.by_category {
+            writeln!(out, "  {category:<12} {count}")?;
+        }
+        writeln!(
+            out,
+            "comments:      {} ({:.1}% of bytes)",
+            total.comments,
+            total.comment_density() * 100.0
+        )?;
+        writeln!(
+            out,
+            "longest line:  {} bytes ({}:{})",
+            longest.2, longest.0, longest.1
+        )?;
+        let secs = total.lex_time.as_secs_f64();
+        write!(out, "lex time:      {:.3} ms", secs * 1000.0)?;
+        if secs > 0.0 {
+            write!(
+                out,
+                " ({:.1} MB/s)",
+                total.bytes as f64 / secs / 1_000_000.0
+            )?;
+        }
+        writeln!(out)?;
+        writeln!(
+            out,
+            "allocations:   {} ({} bytes, peak {} bytes live)",
+            total.allocations, total.bytes_allocated, total.peak_live_bytes
+        )?;
+        writeln!(out, "top identifiers:")?;
+        for (name, count) in total.top_identifiers(TOP_IDENTIFIERS) {
+            writeln!(out, "  {count:>6}  {name}")?;
+        }
+        Ok(())
+    })
+}
+
+/// Fold the statistics of one file into the running total.
+fn merge(total: &mut LexStats, stats: LexStats) {
+    total.bytes += stats.bytes;
+    total.lines += stats.lines;
+    total.tokens += stats.tokens;
+    for (category, count) in stats.by_category {
+        *total.by_category.entry(category).or_default() += count;
+    }
+    total.comments += stats.comments;
+    total.comment_bytes += stats.comment_bytes;
+    for (name, count) in stats.identifiers {
+        *total.identifiers.entry(name).or_default() += count;
+    }
+    total.lex_time += stats.lex_time;
+    total.allocations += stats.allocations;
+    total.bytes_allocated += stats.bytes_allocated;
+    total.peak_live_bytes = total.peak_live_bytes.max(stats.peak_live_bytes);
+}
@@ -0,0 +1,13 @@
+//! `hm-lex textmate`: generate a TextMate grammar for editor highlighting.
+//!
+//! Redirect the output to `syntaxes/hummingbird.tmLanguage.json` in an
+//! editor extension to keep highlighting in sync with the lexer.
+
+/// Run the `textmate` subcommand.
+pub fn run(args: &[String]) -> Result<(), String> {
+    if let Some(extra) = args.first() {
+        return Err(format!("unexpected argument '{extra}'"));
+    }
+    let json = hm_lexer::textmate::tm_language_json();
+    crate::emit(|out| out.write_all(json.as_bytes()))
+}
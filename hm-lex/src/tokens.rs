@@ -0,0 +1,143 @@
+//! `hm-lex tokens`: print the token stream of a source file.
+//!
+//! The default `debug` format prints each token's `Debug` representation;
+//! `--format table` prints an aligned `KIND | LEXEME | LINE:COL | SPAN`
+//! listing that is easier to scan, and `--format snapshot` prints the
+//! canonical `.tokens` format used by golden tests.
+
+use hm_lexer::lexerror::render;
+use hm_lexer::token::Token;
+use hm_lexer::token::keywords::Keywords;
+use hm_lexer::token::literals::Literals;
+use hm_lexer::token::tokenkind::TokenKind;
+use hm_lexer::{snapshot, tokenize};
+
+use crate::{emit, read_source};
+
+/// Output format of the `tokens` subcommand.
+enum Format {
+    Debug,
+    Table,
+    Snapshot,
+}
+
+/// Run the `tokens` subcommand.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut format = Format::Debug;
+    let mut path = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => match args.next().map(String::as_str) {
+                Some("debug") => format = Format::Debug,
+                Some("table") => format = Format::Table,
+                Some("snapshot") => format = Format::Snapshot,
+                Some(other) => return Err(format!("unknown format '{other}'")),
+                None => return Err("--format requires a value".to_string()),
+            },
+            _ if path.is_none() => path = Some(arg.as_str()),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    let source = read_source(path)?;
+    let (tokens, names) =
+        tokenize(&source).map_err(|e| render(&e, &source).trim_end().to_string())?;
+    match format {
+        Format::Debug => emit(|out| {
+            for token in &tokens {
+                writeln!(out, "{token:?}")?;
+            }
+            Ok(())
+        }),
+        Format::Table => print_table(&tokens),
+        Format::Snapshot => {
+            emit(|out| out.write_all(snapshot::serialize(&tokens, &names).as_bytes()))
+        }
+    }
+}
+
+/// Print tokens as a column-aligned table.
+fn print_table(tokens: &[Token]) -> Result<(), String> {
+    let rows: Vec<[String; 4]> = tokens
+        .iter()
+        .map(|token| {
+            let span = &token.span;
+            [
+                kind_name(&token.kind),
+                printable(&token.lexeme),
+                format!("{}:{}", span.line_start, span.column_start),
+                format!("{}..{}", span.start, span.end),
+            ]
+        })
+        .collect();
+
+    let header = ["KIND", "LEXEME", "LINE:COL", "SPAN"].map(String::from);
+    let mut widths = header.each_ref().map(|h| h.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    emit(|out| {
+        for row in std::iter::once(&header).chain(&rows) {
+            writeln!(
+                out,
+                "{:<w0$} | {:<w1$} | {:<w2$} | {}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// Escape control characters so every lexeme fits on one table row.
+fn printable(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_control() {
+                c.escape_default().to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Short human-readable name of a token kind.
+fn kind_name(kind: &TokenKind) -> String {
+    let name = match kind {
+        TokenKind::Keyword(Keywords::Type(_)) => "Type",
+        TokenKind::Keyword(_) => "Keyword",
+        TokenKind::Identifier(_) => "Identifier",
+        TokenKind::Literal(literal) => match literal {
+            Literals::StringLiteral(_) => "String",
+            Literals::InterpolatedString(_) => "InterpolatedString",
+            Literals::MultilineStringLiteral { .. } => "MultilineString",
+            Literals::CharacterLiteral(_) => "Char",
+            Literals::ByteLiteral(_) => "Byte",
+            Literals::IntLiteral(_) => "Int",
+            Literals::UnsignedIntLiteral(_) => "Unsigned",
+            Literals::FloatLiteral(_) => "Float",
+            Literals::NumericLiteral { .. } => "Numeric",
+            Literals::BoolLiteral(_) => "Bool",
+        },
+        TokenKind::Delimiter(_) => "Delimiter",
+        TokenKind::ArithmeticOperator(_) => "Arithmetic",
+        TokenKind::RelationalOperator(_) => "Relational",
+        TokenKind::LogicalOperator(_) => "Logical",
+        TokenKind::AssignmentOperator(_) => "Assignment",
+        TokenKind::BitwiseOperator(_) => "Bitwise",
+        TokenKind::SpecialOperator(_) => "Special",
+        TokenKind::Error { message_code } => return format!("Error({})", message_code.as_str()),
+        TokenKind::Eof => "Eof",
+    };
+    name.to_string()
+}
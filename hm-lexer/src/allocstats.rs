@@ -0,0 +1,154 @@
+//! Heap allocation counters for measuring the lexer.
+//!
+//! A library cannot observe allocations on its own, so this module provides
+//! [`CountingAllocator`], a [`GlobalAlloc`] wrapper around [`System`] that a
+//! binary or test installs with `#[global_allocator]`. Once installed,
+//! [`AllocCounters::now`] reads the running totals and
+//! [`LexStats`](crate::stats::LexStats) reports what lexing allocated. Without
+//! it every counter stays at zero.
+//!
+//! Only available with the `alloc-stats` feature.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator that forwards to [`System`] while counting
+/// allocations, bytes allocated, and live bytes.
+///
+/// Counting costs a few relaxed atomic updates per allocation.
+///
+/// # Example
+///
+/// ```
+/// use hm_lexer::allocstats::{AllocCounters, CountingAllocator};
+///
+/// #[global_allocator]
+/// static GLOBAL: CountingAllocator = CountingAllocator;
+///
+/// let before = AllocCounters::now();
+/// let (tokens, _) = hm_lexer::tokenize("var name = \"text\";").unwrap();
+/// assert!(AllocCounters::now().allocations > before.allocations);
+/// # drop(tokens);
+/// ```
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Count one allocation of `size` bytes and raise the peak if needed.
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES_ALLOCATED.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+/// A reading of the [`CountingAllocator`] counters.
+///
+/// `allocations` and `bytes_allocated` only ever grow, so the difference
+/// between two readings is what was allocated in between.
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct AllocCounters {
+    /// Allocations (including reallocations) made so far
+    pub allocations: usize,
+    /// Total bytes requested by those allocations
+    pub bytes_allocated: usize,
+    /// Bytes currently allocated and not yet freed
+    pub live_bytes: usize,
+    /// Highest value of `live_bytes` since the last [`reset_peak`]
+    pub peak_live_bytes: usize,
+}
+
+impl AllocCounters {
+    /// Read the current counters.
+    pub fn now() -> Self {
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed),
+            live_bytes: LIVE_BYTES.load(Ordering::Relaxed),
+            peak_live_bytes: PEAK_LIVE_BYTES.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Lower the recorded peak to the bytes live right now, so the next reading
+/// reports the peak of what follows.
+pub fn reset_peak() {
+    PEAK_LIVE_BYTES.store(LIVE_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+}
+
+/// Accumulates what the lexer allocates while statistics are gathered
+/// around it, excluding the statistics' own allocations.
+pub(crate) struct LexTally {
+    /// Live bytes before lexing started
+    base: usize,
+    /// Net live bytes added by bookkeeping between lexer calls
+    outside: isize,
+    /// Counters when the current lexer call began, or the previous one ended
+    mark: AllocCounters,
+    pub(crate) allocations: usize,
+    pub(crate) bytes_allocated: usize,
+    pub(crate) peak_live_bytes: usize,
+}
+
+impl LexTally {
+    /// Start tallying; everything from here until [`LexTally::pause`] is
+    /// attributed to the lexer.
+    pub(crate) fn start() -> Self {
+        reset_peak();
+        let mark = AllocCounters::now();
+        Self {
+            base: mark.live_bytes,
+            outside: 0,
+            mark,
+            allocations: 0,
+            bytes_allocated: 0,
+            peak_live_bytes: 0,
+        }
+    }
+
+    /// Stop attributing allocations to the lexer.
+    pub(crate) fn pause(&mut self) {
+        let now = AllocCounters::now();
+        self.allocations += now.allocations - self.mark.allocations;
+        self.bytes_allocated += now.bytes_allocated - self.mark.bytes_allocated;
+        let peak = now.peak_live_bytes as isize - self.base as isize - self.outside;
+        self.peak_live_bytes = self.peak_live_bytes.max(peak.max(0) as usize);
+        self.mark = now;
+    }
+
+    /// Resume attributing allocations to the lexer.
+    pub(crate) fn resume(&mut self) {
+        let now = AllocCounters::now();
+        self.outside += now.live_bytes as isize - self.mark.live_bytes as isize;
+        reset_peak();
+        self.mark = AllocCounters::now();
+    }
+}
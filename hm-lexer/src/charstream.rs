@@ -1,6 +1,19 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::ops::Deref;
+use std::path::Path;
 use std::str::FromStr;
 
-use crate::lexerror::LexError;
+use crate::config::{is_continuation, ColumnCounter, ColumnUnit, LineEndings};
+use crate::lexerror::{LexError, LexResult};
+use crate::token::span::Span;
+
+/// Largest input, in bytes, that [`CharStream::from_file`] and
+/// `CharStream::from_mmap` accept.
+///
+/// Larger files are rejected with [`LexError::InputTooLarge`] before they
+/// are read into memory.
+pub const MAX_INPUT_LEN: usize = u32::MAX as usize;
 
 /// An ASCII-only cursor over an in-memory byte buffer for lexer frontends.
 ///
@@ -11,9 +24,9 @@ use crate::lexerror::LexError;
 /// source and never advances past `input.len()`, making it deterministic and
 /// replayable for tokenization.
 pub struct CharStream {
-    /// Owned input buffer containing the raw bytes to be consumed by the lexer.
+    /// Input buffer containing the raw bytes to be consumed by the lexer.
     /// No UTF-8 assumptions are made; bytes are treated as ASCII code units.
-    input: Vec<u8>,
+    input: Input,
 
     /// Current byte index (0-based) into the input buffer.
     /// This always points to the next byte to be read.
@@ -24,33 +37,193 @@ pub struct CharStream {
     line: usize,
 
     /// Current column number (1-based) corresponding to the cursor position.
-    /// Reset to 1 after a newline, incremented for any other consumed byte.
+    /// Reset to 1 after a newline, advanced by the byte's width in
+    /// `column_unit` for any other consumed byte.
     column: usize,
+
+    /// How consumed bytes advance `column`.
+    columns: ColumnCounter,
+
+    /// Number of characters (Unicode scalar values) before the cursor, or
+    /// `None` when character tracking is disabled.
+    char_index: Option<usize>,
+
+    /// Whether `line` and `column` are maintained as bytes are consumed.
+    track_lines: bool,
 }
 
 impl CharStream {
     /// Create a new stream from an existing byte buffer.
     ///
     /// The cursor starts at index 0 with line 1 and column 1. No UTF-8 decoding
-    /// occurs; bytes are treated as ASCII code units.
-    pub fn new(input: Vec<u8>) -> Result<Self, LexError> {
-        if input.is_empty() {
-            return Err(LexError::EmptyInput);
-        }
-        Ok(Self {
+    /// occurs; bytes are treated as ASCII code units. Empty input is valid:
+    /// a lexer over it returns `Eof` at line 1, column 1 straight away.
+    ///
+    /// This never fails; it returns a `Result` for compatibility with code
+    /// written when empty input was rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexer::Lexer;
+    /// let mut lexer = Lexer::new(CharStream::new(Vec::new()).unwrap());
+    /// let eof = lexer.next_token().unwrap();
+    /// assert!(eof.is_eof());
+    /// assert_eq!((eof.span.line_start, eof.span.column_start), (1, 1));
+    /// ```
+    pub fn new(input: Vec<u8>) -> LexResult<Self> {
+        Ok(Self::from_input(Input::Owned(input)))
+    }
+
+    /// Create a stream over `input`.
+    fn from_input(input: Input) -> Self {
+        Self {
             input,
             index: 0,
             line: 1,
             column: 1,
-        })
+            columns: ColumnCounter::default(),
+            char_index: None,
+            track_lines: true,
+        }
     }
 
     /// Create a stream by copying a byte slice into an owned buffer.
     #[inline]
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LexError> {
+    pub fn from_bytes(bytes: &[u8]) -> LexResult<Self> {
         Self::new(bytes.to_vec())
     }
 
+    /// Create a stream from everything `reader` produces, e.g. standard
+    /// input or a socket.
+    ///
+    /// The reader is drained into the stream's buffer before lexing starts,
+    /// so it does not need to be wrapped in a [`BufReader`](std::io::BufReader).
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The source of the input bytes, read to the end
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(CharStream)` positioned at the start of the input
+    /// - `Err(io::Error)` if reading fails
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexer::Lexer;
+    /// let piped: &[u8] = b"var x = 1;";
+    /// let stream = CharStream::from_reader(piped).unwrap();
+    /// let tokens: Vec<_> = Lexer::new(stream).map(Result::unwrap).collect();
+    /// assert_eq!(tokens[1].lexeme, "x");
+    /// ```
+    pub fn from_reader(mut reader: impl Read) -> io::Result<Self> {
+        let mut input = Vec::new();
+        reader.read_to_end(&mut input)?;
+        Ok(Self::from_input(Input::Owned(input)))
+    }
+
+    /// Create a stream from the contents of the file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to read
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(CharStream)` positioned at the start of the file
+    /// - `Err(LexError::Io)` if the file cannot be opened or read
+    /// - `Err(LexError::InputTooLarge)` if the file is larger than
+    ///   [`MAX_INPUT_LEN`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexerror::LexError;
+    /// let err = CharStream::from_file("does/not/exist.hm").err().unwrap();
+    /// assert!(matches!(err, LexError::Io { .. }));
+    /// assert!(err.to_string().starts_with("Failed to read 'does/not/exist.hm'"));
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> LexResult<Self> {
+        let path = path.as_ref();
+        let io_error = |err: io::Error| LexError::Io {
+            path: path.display().to_string(),
+            message: err.to_string(),
+            context: None,
+        };
+        let too_large = |size: usize| LexError::InputTooLarge {
+            size,
+            context: Some(
+                format!(
+                    "'{}' exceeds the limit of {MAX_INPUT_LEN} bytes",
+                    path.display()
+                )
+                .into(),
+            ),
+        };
+
+        let file = File::open(path).map_err(io_error)?;
+        let len = file.metadata().map_err(io_error)?.len();
+        let len = usize::try_from(len).unwrap_or(usize::MAX);
+        if len > MAX_INPUT_LEN {
+            return Err(too_large(len));
+        }
+
+        // Read one byte past the limit to notice a file that grew since
+        // its metadata was read.
+        let mut input = Vec::with_capacity(len);
+        file.take(MAX_INPUT_LEN as u64 + 1)
+            .read_to_end(&mut input)
+            .map_err(io_error)?;
+        if input.len() > MAX_INPUT_LEN {
+            return Err(too_large(input.len()));
+        }
+        Ok(Self::from_input(Input::Owned(input)))
+    }
+
+    /// Create a stream that reads a memory-mapped file in place.
+    ///
+    /// Unlike [`from_file`](Self::from_file), the file's contents are never
+    /// copied: the stream keeps the mapping alive and pages are loaded as
+    /// the lexer reaches them. Lexemes are still copied out of the mapping
+    /// as tokens are produced.
+    ///
+    /// Mapping a file is `unsafe` because another process may modify or
+    /// truncate it while it is mapped; the caller takes on that obligation
+    /// when creating `map`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(CharStream)` positioned at the start of the file
+    /// - `Err(LexError::InputTooLarge)` if the file is larger than
+    ///   [`MAX_INPUT_LEN`]
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexer::Lexer;
+    /// let file = File::open("generated.hm").unwrap();
+    /// // SAFETY: nothing else writes to the file while it is lexed.
+    /// let map = unsafe { memmap2::Mmap::map(&file) }.unwrap();
+    /// let lexer = Lexer::new(CharStream::from_mmap(map).unwrap());
+    /// ```
+    #[cfg(feature = "mmap")]
+    pub fn from_mmap(map: memmap2::Mmap) -> LexResult<Self> {
+        if map.len() > MAX_INPUT_LEN {
+            return Err(LexError::InputTooLarge {
+                size: map.len(),
+                context: None,
+            });
+        }
+        Ok(Self::from_input(Input::Mapped(map)))
+    }
+
     /// Returns true when the cursor is at or beyond the end of the buffer.
     pub fn is_eof(&self) -> bool {
         self.index >= self.input.len()
@@ -71,6 +244,109 @@ impl CharStream {
         self.column
     }
 
+    /// The unit in which columns are counted.
+    pub fn column_unit(&self) -> ColumnUnit {
+        self.columns.unit
+    }
+
+    /// Change the unit in which columns are counted from now on.
+    ///
+    /// Intended to be called before any bytes are consumed; columns already
+    /// reached are not recomputed.
+    pub fn set_column_unit(&mut self, unit: ColumnUnit) {
+        self.columns.unit = unit;
+    }
+
+    /// How `\r\n` line endings are counted.
+    pub fn line_endings(&self) -> LineEndings {
+        self.columns.line_endings
+    }
+
+    /// Change how `\r\n` line endings are counted from now on; see
+    /// [`LineEndings`].
+    ///
+    /// Intended to be called before any bytes are consumed; columns already
+    /// reached are not recomputed.
+    pub fn set_line_endings(&mut self, line_endings: LineEndings) {
+        self.columns.line_endings = line_endings;
+    }
+
+    /// Distance between tab stops; 0 and 1 count a tab as one column.
+    pub fn tab_width(&self) -> usize {
+        self.columns.tab_width
+    }
+
+    /// Change the distance between tab stops from now on: a tab moves the
+    /// column to the next multiple of `width` plus one.
+    ///
+    /// Intended to be called before any bytes are consumed; columns already
+    /// reached are not recomputed.
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// let mut stream = CharStream::from_bytes(b"ab\tc").unwrap();
+    /// stream.set_tab_width(4);
+    /// stream.advance_n(3);
+    /// assert_eq!(stream.column(), 5);
+    /// ```
+    pub fn set_tab_width(&mut self, width: usize) {
+        self.columns.tab_width = width;
+    }
+
+    /// Number of characters (Unicode scalar values) before the cursor, if
+    /// character tracking is enabled.
+    pub fn char_index(&self) -> Option<usize> {
+        self.char_index
+    }
+
+    /// Enable or disable counting characters as bytes are consumed.
+    ///
+    /// Enabling tracking counts the characters already consumed, so it may
+    /// be turned on at any point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// let mut stream = CharStream::from_bytes("é🙂x".as_bytes()).unwrap();
+    /// stream.advance_n(2);
+    /// stream.set_char_tracking(true);
+    /// stream.advance_n(5);
+    /// assert_eq!(stream.char_index(), Some(3));
+    /// ```
+    pub fn set_char_tracking(&mut self, enabled: bool) {
+        self.char_index = enabled.then(|| self.count_chars(0, self.index));
+    }
+
+    /// Enable or disable line and column bookkeeping.
+    ///
+    /// With tracking disabled, [`line`](Self::line) and
+    /// [`column`](Self::column) report 0 and consuming a byte only moves
+    /// the index. Intended to be called before any bytes are consumed;
+    /// re-enabling tracking restarts counting at line 1, column 1.
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// let mut stream = CharStream::from_bytes(b"a\nb").unwrap();
+    /// stream.set_line_tracking(false);
+    /// stream.advance_n(2);
+    /// assert_eq!(stream.current_position(), (2, 0, 0));
+    /// ```
+    pub fn set_line_tracking(&mut self, enabled: bool) {
+        self.track_lines = enabled;
+        let start = usize::from(enabled);
+        self.line = start;
+        self.column = start;
+    }
+
+    /// Number of characters starting in `input[start..end]`.
+    fn count_chars(&self, start: usize, end: usize) -> usize {
+        self.input[start..end]
+            .iter()
+            .filter(|&&b| !is_continuation(b))
+            .count()
+    }
+
     /// Current (line, column) tuple.
     pub fn line_column(&self) -> (usize, usize) {
         (self.line, self.column)
@@ -96,25 +372,129 @@ impl CharStream {
     /// Consume and return the current byte, updating line and column counters.
     ///
     /// Newlines (`b'\n'`) increment the line and reset the column to 1. Any
-    /// other byte increments the column. Returns `None` if already at EOF.
+    /// other byte advances the column by its width in the stream's
+    /// [`ColumnUnit`], except that a tab moves to the next tab stop (see
+    /// [`set_tab_width`](Self::set_tab_width)) and the `\r` of a `\r\n`
+    /// occupies no column under [`LineEndings::Crlf`]. Returns `None` if
+    /// already at EOF.
     pub fn advance(&mut self) -> Option<u8> {
         if self.is_eof() {
             return None;
         }
 
         let b = self.input[self.index];
-        self.index += 1;
 
-        if b == b'\n' {
+        if !self.track_lines {
+            // Offsets only; see `set_line_tracking`.
+        } else if b == b'\n' {
             self.line += 1;
             self.column = 1;
         } else {
-            self.column += 1;
+            self.column = self.columns.advance(self.column, &self.input, self.index);
+        }
+        self.index += 1;
+        if let Some(chars) = &mut self.char_index {
+            *chars += usize::from(!is_continuation(b));
         }
 
         Some(b)
     }
 
+    /// Decode the UTF-8 character at the cursor without advancing.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some((char, len)))` with the character and its length in bytes
+    /// - `Ok(None)` at end of input
+    /// - `Err(LexError::InvalidUtf8)` spanning the invalid bytes at the
+    ///   cursor, or the truncated sequence at the end of the input
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexerror::LexError;
+    /// let stream = CharStream::from_bytes("é!".as_bytes()).unwrap();
+    /// assert_eq!(stream.peek_char().unwrap(), Some(('é', 2)));
+    ///
+    /// let stream = CharStream::from_bytes(b"\xE2\x80x").unwrap();
+    /// let Err(LexError::InvalidUtf8 { span, .. }) = stream.peek_char() else {
+    ///     panic!("expected invalid UTF-8");
+    /// };
+    /// assert_eq!((span.start, span.end), (0, 2));
+    /// ```
+    pub fn peek_char(&self) -> LexResult<Option<(char, usize)>> {
+        match self.peek() {
+            None => return Ok(None),
+            Some(b) if b.is_ascii() => return Ok(Some((b as char, 1))),
+            Some(_) => {}
+        }
+        let end = self.input.len().min(self.index + 4);
+        let bytes = &self.input[self.index..end];
+        let valid = match std::str::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(err) if err.valid_up_to() > 0 => {
+                std::str::from_utf8(&bytes[..err.valid_up_to()]).expect("checked valid prefix")
+            }
+            Err(err) => {
+                let len = err.error_len().unwrap_or(bytes.len());
+                return Err(LexError::InvalidUtf8 {
+                    span: self.span_ahead(len),
+                    context: None,
+                });
+            }
+        };
+        Ok(valid.chars().next().map(|ch| (ch, ch.len_utf8())))
+    }
+
+    /// Consume and return the UTF-8 character at the cursor.
+    ///
+    /// Behaves like [`peek_char`](Self::peek_char); on invalid UTF-8 nothing
+    /// is consumed, so the caller decides how far to skip.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// let mut stream = CharStream::from_bytes(b"\xCF\x80\n\xFFz").unwrap();
+    /// assert_eq!(stream.advance_char().unwrap(), Some('π'));
+    /// assert_eq!(stream.advance_char().unwrap(), Some('\n'));
+    ///
+    /// let err = stream.advance_char().unwrap_err();
+    /// assert_eq!((err.line(), err.column()), (Some(2), Some(1)));
+    /// stream.advance();
+    /// assert_eq!(stream.advance_char().unwrap(), Some('z'));
+    /// assert_eq!(stream.advance_char().unwrap(), None);
+    /// ```
+    pub fn advance_char(&mut self) -> LexResult<Option<char>> {
+        let Some((ch, len)) = self.peek_char()? else {
+            return Ok(None);
+        };
+        self.advance_n(len);
+        Ok(Some(ch))
+    }
+
+    /// The span of the `len` bytes at the cursor, which lie on one line.
+    fn span_ahead(&self, len: usize) -> Span {
+        let end = self.index + len;
+        let column_end = if self.track_lines {
+            (self.index..end).fold(self.column, |column, i| {
+                self.columns.advance(column, &self.input, i)
+            })
+        } else {
+            0
+        };
+        Span {
+            start: self.index,
+            end,
+            line_start: self.line,
+            column_start: self.column,
+            line_end: self.line,
+            column_end,
+            char_offsets: None,
+        }
+    }
+
     /// Advances the stream by `n` bytes.
     ///
     /// This function will call the `advance` method `n` times, consuming
@@ -142,6 +522,47 @@ impl CharStream {
         }
     }
 
+    /// Advance the cursor to byte offset `end`, or to the end of the input
+    /// if `end` is past it.
+    ///
+    /// Equivalent to [`advance_n`](Self::advance_n) over the distance, but
+    /// line, column, and character counters are fixed up from the skipped
+    /// slice in bulk: newlines are found with `memchr` and only the bytes
+    /// after the last one are measured for the column. Use it to skip long
+    /// runs such as comment bodies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// let mut stream = CharStream::from_bytes(b"/* a\nbb\n ccc */ x").unwrap();
+    /// stream.advance_to(15);
+    /// assert_eq!(stream.peek(), Some(b' '));
+    /// assert_eq!(stream.line_column(), (3, 8));
+    /// ```
+    pub fn advance_to(&mut self, end: usize) {
+        let end = end.min(self.input.len());
+        if end <= self.index {
+            return;
+        }
+        if let Some(chars) = self.char_index {
+            self.char_index = Some(chars + self.count_chars(self.index, end));
+        }
+        if self.track_lines {
+            let skipped = &self.input[self.index..end];
+            let mut line_start = self.index;
+            if let Some(last) = memchr::memrchr(b'\n', skipped) {
+                self.line += memchr::memchr_iter(b'\n', skipped).count();
+                self.column = 1;
+                line_start += last + 1;
+            }
+            self.column = self
+                .columns
+                .advance_over(self.column, &self.input, line_start, end);
+        }
+        self.index = end;
+    }
+
     /// Advance if the next byte matches `expected`.
     ///
     /// Returns `true` when a match occurs and consumes the byte, `false`
@@ -155,6 +576,11 @@ impl CharStream {
         }
     }
 
+    /// Borrow the whole underlying buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.input
+    }
+
     /// Borrow a slice of the underlying buffer for lexeme extraction.
     ///
     /// Panics if the range is out of bounds, matching normal slice behavior.
@@ -193,6 +619,106 @@ impl CharStream {
     pub fn current_position(&self) -> (usize, usize, usize) {
         (self.index, self.line, self.column)
     }
+
+    /// Move the cursor back to a position captured by [`current_position`].
+    ///
+    /// [`current_position`]: Self::current_position
+    pub(crate) fn back_up(&mut self, (index, line, column): (usize, usize, usize)) {
+        if self.char_index.is_some() {
+            let rewound = self.count_chars(index, self.index);
+            self.char_index = self.char_index.map(|chars| chars - rewound);
+        }
+        self.index = index;
+        self.line = line;
+        self.column = column;
+    }
+
+    /// Capture the cursor position so [`rewind`](Self::rewind) can return
+    /// to it.
+    ///
+    /// Checkpoints are O(1) to take and restore; speculative lexing can
+    /// take as many as it likes.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            position: self.current_position(),
+            chars: self.char_index,
+        }
+    }
+
+    /// Move the cursor to a position captured by
+    /// [`checkpoint`](Self::checkpoint), restoring its index, line, column,
+    /// and character count exactly.
+    ///
+    /// The checkpoint may lie before or after the cursor, but must come
+    /// from this stream.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// let mut stream = CharStream::from_bytes(b"a\nb>>c").unwrap();
+    /// stream.advance_n(3);
+    /// let before = stream.checkpoint();
+    ///
+    /// // Speculatively consume `>>`, then back out of it.
+    /// stream.advance_n(2);
+    /// assert_eq!(stream.current_position(), (5, 2, 4));
+    /// stream.rewind(before);
+    /// assert_eq!(stream.current_position(), (3, 2, 2));
+    /// assert_eq!(stream.peek(), Some(b'>'));
+    /// ```
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        let (index, line, column) = checkpoint.position;
+        self.index = index;
+        self.line = line;
+        self.column = column;
+        if self.char_index.is_some() {
+            // Tracking may have been enabled after the checkpoint was taken.
+            self.char_index = Some(match checkpoint.chars {
+                Some(chars) => chars,
+                None => self.count_chars(0, index),
+            });
+        }
+    }
+}
+
+/// A saved [`CharStream`] position, returned by
+/// [`CharStream::checkpoint`] and accepted by [`CharStream::rewind`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Checkpoint {
+    /// Stream index, line, and column
+    position: (usize, usize, usize),
+    /// Characters before `position`, when character tracking was enabled
+    chars: Option<usize>,
+}
+
+impl Checkpoint {
+    /// Byte offset of the saved position.
+    pub fn index(&self) -> usize {
+        self.position.0
+    }
+}
+
+/// The bytes a [`CharStream`] reads from.
+enum Input {
+    /// A buffer owned by the stream.
+    Owned(Vec<u8>),
+    /// A memory-mapped file, read in place.
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Deref for Input {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Input::Owned(bytes) => bytes,
+            #[cfg(feature = "mmap")]
+            Input::Mapped(map) => map,
+        }
+    }
 }
 
 impl FromStr for CharStream {
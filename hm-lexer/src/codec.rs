@@ -0,0 +1,1316 @@
+//! Compact binary encoding for token streams.
+//!
+//! On-disk lexer caches for large projects should stay small, so tokens are
+//! not stored as fixed-width records. Instead the encoder uses:
+//!
+//! - **Varints** (LEB128) for every integer, with zigzag encoding for signed values
+//! - **Kind ids**: a single byte identifying the [`TokenKind`], followed by a
+//!   payload only for identifiers and literals. The payload of an
+//!   interpolated string nests the token list of each `${...}` expression
+//! - **Span deltas**: each span is stored relative to the end of the previous
+//!   token; a span on the same line as its predecessor costs only its gap and length
+//! - **Interned text**: lexemes, identifier names and string literal contents are
+//!   stored once in a string table and referenced by index. Identifier symbols are
+//!   resolved through the tokens' [`Interner`] when encoding, and [`decode`]
+//!   returns a fresh interner for the decoded symbols. Lexemes that can be derived
+//!   from the kind (keyword and operator spellings, identifier names, plain
+//!   integers, booleans) are not stored at all, and neither is their length
+//!
+//! # Layout
+//!
+//! ```text
+//! magic           b"HMTK"
+//! format_version  varint (FORMAT_VERSION)
+//! lexer_version   len varint, utf-8 bytes (crate::lexer_version)
+//! string_count  varint
+//! strings       (len varint, utf-8 bytes) * string_count
+//! char_offsets  varint (1 if any span carries character offsets, else 0)
+//! token_count   varint
+//! tokens        (kind, payload?, header, span_rest, chars?, lexeme_ref?) * token_count
+//! ```
+//!
+//! # Versioning
+//!
+//! Every stream starts with a header recording the encoding's
+//! [`FORMAT_VERSION`] and the [`lexer_version`](crate::lexer_version) that
+//! produced the tokens. [`decode`] rejects streams from a different lexer
+//! version, since the same source may tokenize differently, and streams in a
+//! format it cannot read. The lexer version covers the kind ids below, so a
+//! stream written before a kind was added or renumbered is rejected rather
+//! than decoded into the wrong kinds. Caches should treat either error as a
+//! miss and re-lex.
+//!
+//! With the `serde` feature, `VersionedTokens` carries the same versions
+//! for token lists written with serde, e.g. as JSON, and fails to
+//! deserialize on a mismatch.
+//!
+//! # Example
+//!
+//! ```
+//! # use hm_lexer::{codec, tokenize};
+//! let (tokens, names) = tokenize("var x = 42;").unwrap();
+//! let bytes = codec::encode(&tokens, &names);
+//! let (decoded, _) = codec::decode(&bytes).unwrap();
+//! assert_eq!(decoded.len(), tokens.len());
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use thiserror::Error;
+
+use crate::intern::Interner;
+use crate::lexerror::ErrorCode;
+use crate::token::delimiters::Delimiters;
+use crate::token::keywords::{Keywords, TypeKind};
+use crate::token::literals::{Literals, NumericSuffix, NumericValue, StringPart};
+use crate::token::operators::arithmetic::ArithmeticOps;
+use crate::token::operators::assignment::AssignmentOps;
+use crate::token::operators::bitwise::BitwiseOps;
+use crate::token::operators::logical::LogicalOps;
+use crate::token::operators::relational::RelationalOps;
+use crate::token::operators::SpecialOps;
+use crate::token::span::Span;
+use crate::token::tokenkind::TokenKind;
+use crate::token::{owned_lexeme, static_lexeme, Token};
+
+/// Errors that can occur while decoding an encoded token stream.
+#[derive(Debug, Error)]
+pub enum DecodeError {
+    /// The input ended in the middle of a value.
+    #[error("Unexpected end of encoded token stream at byte {offset}")]
+    UnexpectedEnd {
+        /// Byte offset at which more input was expected
+        offset: usize,
+    },
+
+    /// A varint did not fit in 64 bits or a value did not fit its target type.
+    #[error("Numeric overflow in encoded token stream at byte {offset}")]
+    Overflow {
+        /// Byte offset of the offending value
+        offset: usize,
+    },
+
+    /// A kind tag or sub-kind id does not correspond to any token kind.
+    #[error("Invalid token kind id {id} at byte {offset}")]
+    InvalidKind {
+        /// The unrecognized id
+        id: u64,
+        /// Byte offset of the offending id
+        offset: usize,
+    },
+
+    /// A string reference points past the end of the string table.
+    #[error("String reference {index} is out of bounds")]
+    InvalidStringRef {
+        /// The out-of-bounds string table index
+        index: u64,
+    },
+
+    /// The input does not start with the token-stream magic bytes.
+    #[error("Not an encoded token stream (bad magic bytes)")]
+    BadMagic,
+
+    /// The stream uses an encoding format this build cannot read.
+    #[error("Unsupported token stream format version {found} (expected {expected})")]
+    UnsupportedVersion {
+        /// Format version recorded in the stream
+        found: u64,
+        /// Format version written by this build
+        expected: u32,
+    },
+
+    /// The stream was produced by a different version of the lexer.
+    #[error("Token stream was produced by lexer {found}, but this is lexer {expected}")]
+    LexerVersionMismatch {
+        /// Lexer version recorded in the stream
+        found: String,
+        /// Version of this lexer
+        expected: &'static str,
+    },
+
+    /// A string table entry is not valid UTF-8.
+    #[error("Invalid UTF-8 in string table entry {index}")]
+    InvalidUtf8 {
+        /// Index of the offending string table entry
+        index: usize,
+    },
+}
+
+/// Version of the binary layout written by [`encode`]. Bump it whenever the
+/// layout changes; kind id changes are caught by the lexer version.
+pub const FORMAT_VERSION: u32 = 4;
+
+/// Bytes that start every encoded token stream.
+const MAGIC: &[u8; 4] = b"HMTK";
+
+// Kind ids. These values are part of the encoded format and must not change;
+// each operator family reserves room for new variants.
+const KEYWORD_BASE: u8 = 0;
+const TYPE_BASE: u8 = 32;
+const DELIMITER_BASE: u8 = 64;
+const ARITHMETIC_BASE: u8 = 96;
+const RELATIONAL_BASE: u8 = 104;
+const LOGICAL_BASE: u8 = 112;
+const ASSIGNMENT_BASE: u8 = 120;
+const BITWISE_BASE: u8 = 128;
+const SPECIAL_BASE: u8 = 136;
+const KIND_EOF: u8 = 160;
+const KIND_IDENTIFIER: u8 = 161;
+const KIND_STRING: u8 = 162;
+const KIND_CHAR: u8 = 163;
+const KIND_INT: u8 = 164;
+const KIND_UNSIGNED: u8 = 165;
+const KIND_FLOAT: u8 = 166;
+const KIND_ERROR: u8 = 167;
+const KIND_MULTILINE_STRING: u8 = 168;
+const KIND_BYTE: u8 = 169;
+const KIND_NUMERIC: u8 = 170;
+const KIND_INTERPOLATED_STRING: u8 = 171;
+const KIND_BOOL: u8 = 172;
+const KIND_MAGNITUDE: u8 = 173;
+
+// Tags of the parts of an interpolated string.
+const PART_TEXT: u8 = 0;
+const PART_EXPR: u8 = 1;
+
+// Low bits of each token header: a derived-lexeme flag and a span shape.
+const FLAG_DERIVED_LEXEME: u64 = 0b001;
+const SHAPE_MASK: u64 = 0b110;
+const SHAPE_EXPLICIT: u64 = 0b000;
+const SHAPE_SAME_LINE: u64 = 0b010;
+const SHAPE_NEW_LINE: u64 = 0b100;
+const HEADER_BITS: u32 = 3;
+
+/// Encode a token stream into the compact binary format.
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens to encode, usually ending with `Eof`
+/// * `names` - The interner the tokens' identifier symbols come from
+///
+/// # Returns
+///
+/// The encoded bytes, which can be restored with [`decode`]
+pub fn encode(tokens: &[Token], names: &Interner) -> Vec<u8> {
+    let mut strings = StringTable::default();
+    let mut body = Vec::with_capacity(tokens.len() * 6);
+
+    let chars = tokens.iter().any(|t| t.span.char_offsets.is_some());
+    write_varint(&mut body, u64::from(chars));
+    encode_tokens(&mut body, &mut strings, names, chars, tokens);
+
+    let mut out = Vec::with_capacity(body.len() + strings.byte_len() + 16);
+    out.extend_from_slice(MAGIC);
+    write_varint(&mut out, u64::from(FORMAT_VERSION));
+    let lexer_version = crate::lexer_version();
+    write_varint(&mut out, lexer_version.len() as u64);
+    out.extend_from_slice(lexer_version.as_bytes());
+    write_varint(&mut out, strings.entries.len() as u64);
+    for s in &strings.entries {
+        write_varint(&mut out, s.len() as u64);
+        out.extend_from_slice(s.as_bytes());
+    }
+    out.extend_from_slice(&body);
+    out
+}
+
+/// Decode a token stream produced by [`encode`].
+///
+/// # Arguments
+///
+/// * `bytes` - The encoded bytes
+///
+/// # Returns
+///
+/// - `Ok((Vec<Token>, Interner))` with the original tokens, and an interner
+///   holding the names of their identifiers
+/// - `Err(DecodeError)` if the input is truncated, malformed, or was written
+///   by another format or lexer version
+pub fn decode(bytes: &[u8]) -> Result<(Vec<Token>, Interner), DecodeError> {
+    let mut reader = Reader { bytes, pos: 0 };
+
+    if reader.read_bytes(MAGIC.len())? != MAGIC {
+        return Err(DecodeError::BadMagic);
+    }
+    let version = reader.read_varint()?;
+    if version != u64::from(FORMAT_VERSION) {
+        // Older layouts would be migrated here; none exist yet.
+        return Err(DecodeError::UnsupportedVersion {
+            found: version,
+            expected: FORMAT_VERSION,
+        });
+    }
+    let len = reader.read_len()?;
+    let lexer_version = reader.read_bytes(len)?;
+    if lexer_version != crate::lexer_version().as_bytes() {
+        return Err(DecodeError::LexerVersionMismatch {
+            found: String::from_utf8_lossy(lexer_version).into_owned(),
+            expected: crate::lexer_version(),
+        });
+    }
+
+    let string_count = reader.read_len()?;
+    let mut strings = Vec::with_capacity(string_count.min(bytes.len()));
+    for index in 0..string_count {
+        let len = reader.read_len()?;
+        let raw = reader.read_bytes(len)?;
+        let s = std::str::from_utf8(raw).map_err(|_| DecodeError::InvalidUtf8 { index })?;
+        strings.push(s);
+    }
+
+    let chars = reader.read_varint()? != 0;
+    let mut names = Interner::new();
+    let tokens = decode_tokens(&mut reader, &strings, &mut names, chars)?;
+    Ok((tokens, names))
+}
+
+/// A token list with its interned names and the versions that wrote it,
+/// for caching tokens with serde (feature `serde`).
+///
+/// Serializing records [`SERDE_FORMAT_VERSION`] and the
+/// [`lexer_version`](crate::lexer_version) next to the tokens;
+/// deserializing fails with the same errors as [`decode`] if either differs
+/// from this build's.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::codec::VersionedTokens;
+/// let (tokens, names) = hm_lexer::tokenize("var x = 42;").unwrap();
+/// let json = serde_json::to_string(&VersionedTokens::new(tokens.clone(), names)).unwrap();
+/// let cached: VersionedTokens = serde_json::from_str(&json).unwrap();
+/// let (cached_tokens, cached_names) = cached.into_parts();
+/// assert_eq!(cached_tokens, tokens);
+/// assert_eq!(cached_names.get("x").map(|x| x.index()), Some(0));
+///
+/// let stale = json.replace(hm_lexer::lexer_version(), "0.0.1");
+/// assert!(serde_json::from_str::<VersionedTokens>(&stale).is_err());
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "UncheckedTokens")]
+pub struct VersionedTokens {
+    format_version: u32,
+    lexer_version: Cow<'static, str>,
+    names: Interner,
+    tokens: Vec<Token>,
+}
+
+/// Version of the serde layout of [`VersionedTokens`]. Bump it whenever
+/// the serde representation of tokens changes shape.
+#[cfg(feature = "serde")]
+pub const SERDE_FORMAT_VERSION: u32 = 2;
+
+#[cfg(feature = "serde")]
+impl VersionedTokens {
+    /// Wrap `tokens`, and the interner holding their identifier names, with
+    /// the versions of this build.
+    pub fn new(tokens: Vec<Token>, names: Interner) -> Self {
+        Self {
+            format_version: SERDE_FORMAT_VERSION,
+            lexer_version: Cow::Borrowed(crate::lexer_version()),
+            names,
+            tokens,
+        }
+    }
+
+    /// The wrapped tokens.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// The names the tokens' identifier symbols stand for.
+    pub fn names(&self) -> &Interner {
+        &self.names
+    }
+
+    /// Unwrap the tokens and their interner.
+    pub fn into_parts(self) -> (Vec<Token>, Interner) {
+        (self.tokens, self.names)
+    }
+}
+
+/// [`VersionedTokens`] as read, before its versions are checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedTokens {
+    format_version: u64,
+    lexer_version: String,
+    names: Interner,
+    tokens: Vec<Token>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedTokens> for VersionedTokens {
+    type Error = DecodeError;
+
+    fn try_from(unchecked: UncheckedTokens) -> Result<Self, DecodeError> {
+        if unchecked.format_version != u64::from(SERDE_FORMAT_VERSION) {
+            return Err(DecodeError::UnsupportedVersion {
+                found: unchecked.format_version,
+                expected: SERDE_FORMAT_VERSION,
+            });
+        }
+        if unchecked.lexer_version != crate::lexer_version() {
+            return Err(DecodeError::LexerVersionMismatch {
+                found: unchecked.lexer_version,
+                expected: crate::lexer_version(),
+            });
+        }
+        check_symbols(&unchecked.tokens, &unchecked.names)?;
+        Ok(Self::new(unchecked.tokens, unchecked.names))
+    }
+}
+
+/// Reject identifier symbols, including those of interpolations, that
+/// `names` has no name for.
+#[cfg(feature = "serde")]
+fn check_symbols(tokens: &[Token], names: &Interner) -> Result<(), DecodeError> {
+    for token in tokens {
+        match &token.kind {
+            TokenKind::Identifier(symbol) if symbol.index() >= names.len() => {
+                return Err(DecodeError::InvalidStringRef {
+                    index: symbol.index() as u64,
+                });
+            }
+            TokenKind::Literal(Literals::InterpolatedString(parts)) => {
+                for part in parts {
+                    if let StringPart::Expr(expr) = part {
+                        check_symbols(expr, names)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Write a token count followed by `tokens`, with spans delta-encoded from
+/// the start of the source.
+///
+/// Also used for the expression tokens of an interpolated string, which
+/// restart the span deltas.
+fn encode_tokens(
+    out: &mut Vec<u8>,
+    strings: &mut StringTable,
+    names: &Interner,
+    chars: bool,
+    tokens: &[Token],
+) {
+    write_varint(out, tokens.len() as u64);
+    let mut prev = SpanCursor::default();
+    for token in tokens {
+        encode_kind(out, strings, names, chars, &token.kind);
+        let derived = derived_lexeme(&token.kind, names).is_some_and(|text| {
+            text == *token.lexeme && text.len() == token.span.end - token.span.start
+        });
+        prev.encode(out, &token.span, derived);
+        if chars {
+            prev.encode_chars(out, token.span.char_offsets);
+        }
+        if !derived {
+            write_varint(out, strings.intern(&token.lexeme));
+        }
+    }
+}
+
+/// Read tokens written by [`encode_tokens`].
+fn decode_tokens(
+    reader: &mut Reader,
+    strings: &[&str],
+    names: &mut Interner,
+    chars: bool,
+) -> Result<Vec<Token>, DecodeError> {
+    let token_count = reader.read_len()?;
+    let mut tokens = Vec::with_capacity(token_count.min(reader.bytes.len()));
+    let mut prev = SpanCursor::default();
+    for _ in 0..token_count {
+        let kind = decode_kind(reader, strings, names, chars)?;
+        let derived = derived_lexeme(&kind, names);
+        let (mut span, used_derived) = prev.decode(reader, derived.as_ref().map(|t| t.len()))?;
+        if chars {
+            span.char_offsets = prev.decode_chars(reader)?;
+        }
+        let lexeme = match (kind.fixed_spelling(), derived) {
+            (Some(text), _) if used_derived => static_lexeme(text),
+            (None, Some(text)) if used_derived => owned_lexeme(&text),
+            _ => owned_lexeme(lookup(strings, reader.read_varint()?)?),
+        };
+        tokens.push(Token { kind, span, lexeme });
+    }
+    Ok(tokens)
+}
+
+/// Deduplicating table of strings referenced by index.
+#[derive(Default)]
+struct StringTable {
+    entries: Vec<String>,
+    index: HashMap<String, u64>,
+}
+
+impl StringTable {
+    /// Return the index of `s`, adding it to the table if not yet present.
+    fn intern(&mut self, s: &str) -> u64 {
+        if let Some(&i) = self.index.get(s) {
+            return i;
+        }
+        let i = self.entries.len() as u64;
+        self.entries.push(s.to_owned());
+        self.index.insert(s.to_owned(), i);
+        i
+    }
+
+    /// Total number of string bytes stored in the table.
+    fn byte_len(&self) -> usize {
+        self.entries.iter().map(|s| s.len() + 1).sum()
+    }
+}
+
+/// Tracks the previous token's end position so spans can be delta-encoded.
+///
+/// Each token starts with a header varint holding the zigzag-encoded gap from
+/// the previous token's end above [`HEADER_BITS`] bits of flags. The length
+/// follows unless the lexeme is derived from the kind. The span shape then
+/// decides how much line/column data is stored:
+///
+/// - same line: nothing; columns advance exactly with bytes from the previous token
+/// - new line: the line delta and starting column; the token itself is on one line
+/// - explicit: full line and column deltas for anything else
+///
+/// Character offsets, when the stream has them, follow as the character
+/// length plus one (zero meaning absent) and then the zigzag-encoded gap from
+/// the previous token's character end.
+#[derive(Default)]
+struct SpanCursor {
+    end: usize,
+    line: usize,
+    column: usize,
+    char_end: usize,
+}
+
+impl SpanCursor {
+    fn encode(&mut self, out: &mut Vec<u8>, span: &Span, derived_lexeme: bool) {
+        let gap = span.start as i64 - self.end as i64;
+        let len = span.end - span.start;
+        let single_line =
+            span.line_end == span.line_start && span.column_end - span.column_start == len;
+        let shape = if single_line
+            && span.line_start == self.line
+            && span.column_start as i64 - self.column as i64 == gap
+        {
+            SHAPE_SAME_LINE
+        } else if single_line && span.line_start > self.line {
+            SHAPE_NEW_LINE
+        } else {
+            SHAPE_EXPLICIT
+        };
+
+        let mut header = zigzag(gap) << HEADER_BITS | shape;
+        if derived_lexeme {
+            header |= FLAG_DERIVED_LEXEME;
+        }
+        write_varint(out, header);
+        if !derived_lexeme {
+            write_varint(out, len as u64);
+        }
+
+        match shape {
+            SHAPE_SAME_LINE => {}
+            SHAPE_NEW_LINE => {
+                write_varint(out, (span.line_start - self.line) as u64);
+                write_varint(out, span.column_start as u64);
+            }
+            _ => {
+                write_signed(out, span.line_start as i64 - self.line as i64);
+                write_varint(out, span.column_start as u64);
+                write_varint(out, (span.line_end - span.line_start) as u64);
+                write_varint(out, span.column_end as u64);
+            }
+        }
+
+        self.end = span.end;
+        self.line = span.line_end;
+        self.column = span.column_end;
+    }
+
+    fn encode_chars(&mut self, out: &mut Vec<u8>, char_offsets: Option<(usize, usize)>) {
+        let Some((start, end)) = char_offsets else {
+            write_varint(out, 0);
+            return;
+        };
+        write_varint(out, (end - start) as u64 + 1);
+        write_signed(out, start as i64 - self.char_end as i64);
+        self.char_end = end;
+    }
+
+    fn decode_chars(&mut self, reader: &mut Reader) -> Result<Option<(usize, usize)>, DecodeError> {
+        let len = match reader.read_len()? {
+            0 => return Ok(None),
+            n => n - 1,
+        };
+        let gap = reader.read_signed()?;
+        let start = reader.offset(self.char_end, gap)?;
+        let end = reader.offset(start, len as i64)?;
+        self.char_end = end;
+        Ok(Some((start, end)))
+    }
+
+    /// Decode a span. `derived_len` is the length of the lexeme implied by the
+    /// token kind, used when the header says the lexeme was not stored.
+    ///
+    /// Returns the span and whether the derived lexeme applies.
+    fn decode(
+        &mut self,
+        reader: &mut Reader,
+        derived_len: Option<usize>,
+    ) -> Result<(Span, bool), DecodeError> {
+        let header_offset = reader.pos;
+        let header = reader.read_varint()?;
+        let gap = unzigzag(header >> HEADER_BITS);
+        let start = reader.offset(self.end, gap)?;
+
+        let used_derived = header & FLAG_DERIVED_LEXEME != 0;
+        let len = match (used_derived, derived_len) {
+            (true, Some(len)) => len,
+            (true, None) => {
+                return Err(DecodeError::InvalidKind {
+                    id: header,
+                    offset: header_offset,
+                });
+            }
+            (false, _) => reader.read_len()?,
+        };
+        let end = reader.offset(start, len as i64)?;
+
+        let (line_start, column_start, line_end, column_end) = match header & SHAPE_MASK {
+            SHAPE_SAME_LINE => {
+                let column_start = reader.offset(self.column, gap)?;
+                let column_end = reader.offset(column_start, len as i64)?;
+                (self.line, column_start, self.line, column_end)
+            }
+            SHAPE_NEW_LINE => {
+                let line_delta = reader.read_len()?;
+                let line = reader.offset(self.line, line_delta as i64)?;
+                let column_start = reader.read_len()?;
+                let column_end = reader.offset(column_start, len as i64)?;
+                (line, column_start, line, column_end)
+            }
+            _ => {
+                let line_delta = reader.read_signed()?;
+                let line_start = reader.offset(self.line, line_delta)?;
+                let column_start = reader.read_len()?;
+                let line_span = reader.read_len()?;
+                let line_end = reader.offset(line_start, line_span as i64)?;
+                let column_end = reader.read_len()?;
+                (line_start, column_start, line_end, column_end)
+            }
+        };
+
+        self.end = end;
+        self.line = line_end;
+        self.column = column_end;
+
+        let span = Span {
+            start,
+            end,
+            line_start,
+            column_start,
+            line_end,
+            column_end,
+            char_offsets: None,
+        };
+        Ok((span, used_derived))
+    }
+}
+
+/// Cursor over encoded input.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read_byte(&mut self) -> Result<u8, DecodeError> {
+        let b = *self
+            .bytes
+            .get(self.pos)
+            .ok_or(DecodeError::UnexpectedEnd { offset: self.pos })?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(DecodeError::UnexpectedEnd {
+                offset: self.bytes.len(),
+            })?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, DecodeError> {
+        let start = self.pos;
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let b = self.read_byte()?;
+            if shift >= 64 || (shift == 63 && b > 1) {
+                return Err(DecodeError::Overflow { offset: start });
+            }
+            value |= u64::from(b & 0x7f) << shift;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    fn read_signed(&mut self) -> Result<i64, DecodeError> {
+        Ok(unzigzag(self.read_varint()?))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, DecodeError> {
+        let raw: [u8; 8] = self.read_bytes(8)?.try_into().expect("slice has length 8");
+        Ok(f64::from_bits(u64::from_le_bytes(raw)))
+    }
+
+    fn read_len(&mut self) -> Result<usize, DecodeError> {
+        let offset = self.pos;
+        usize::try_from(self.read_varint()?).map_err(|_| DecodeError::Overflow { offset })
+    }
+
+    /// Apply a signed delta to `base`, rejecting results below zero.
+    fn offset(&self, base: usize, delta: i64) -> Result<usize, DecodeError> {
+        base.checked_add_signed(delta as isize)
+            .ok_or(DecodeError::Overflow { offset: self.pos })
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_signed(out: &mut Vec<u8>, value: i64) {
+    write_varint(out, zigzag(value));
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// The lexeme implied by a token kind, if any. Tokens whose lexeme matches
+/// it store neither the lexeme nor its length.
+fn derived_lexeme<'a>(kind: &TokenKind, names: &'a Interner) -> Option<Cow<'a, str>> {
+    if let Some(text) = kind.fixed_spelling() {
+        return Some(Cow::Borrowed(text));
+    }
+    match kind {
+        TokenKind::Identifier(symbol) => Some(Cow::Borrowed(names.resolve(*symbol))),
+        TokenKind::Literal(Literals::IntLiteral(v)) => Some(Cow::Owned(v.to_string())),
+        TokenKind::Literal(Literals::UnsignedIntLiteral(v)) => Some(Cow::Owned(format!("{v}u"))),
+        TokenKind::Literal(Literals::NumericLiteral { value, suffix }) => match value {
+            NumericValue::Int(v) => Some(Cow::Owned(format!("{v}{}", suffix.as_str()))),
+            NumericValue::Unsigned(v) | NumericValue::Magnitude(v) => {
+                Some(Cow::Owned(format!("{v}{}", suffix.as_str())))
+            }
+            NumericValue::Float(_) => None,
+        },
+        TokenKind::Literal(Literals::BoolLiteral(b)) => {
+            Some(Cow::Borrowed(if *b { "true" } else { "false" }))
+        }
+        _ => None,
+    }
+}
+
+/// Read the type id of a numeric literal's suffix, rejecting suffixes for
+/// which `allowed` is false.
+fn read_suffix(
+    reader: &mut Reader,
+    allowed: impl Fn(NumericSuffix) -> bool,
+) -> Result<NumericSuffix, DecodeError> {
+    let offset = reader.pos;
+    let id = reader.read_byte()?;
+    NumericSuffix::ALL
+        .into_iter()
+        .find(|&suffix| type_id(&suffix.type_kind()) == id && allowed(suffix))
+        .ok_or(DecodeError::InvalidKind {
+            id: u64::from(id),
+            offset,
+        })
+}
+
+fn lookup<'a>(strings: &[&'a str], index: u64) -> Result<&'a str, DecodeError> {
+    usize::try_from(index)
+        .ok()
+        .and_then(|i| strings.get(i).copied())
+        .ok_or(DecodeError::InvalidStringRef { index })
+}
+
+fn encode_kind(
+    out: &mut Vec<u8>,
+    strings: &mut StringTable,
+    names: &Interner,
+    chars: bool,
+    kind: &TokenKind,
+) {
+    match kind {
+        TokenKind::Keyword(Keywords::Type(ty)) => out.push(TYPE_BASE + type_id(ty)),
+        TokenKind::Keyword(kw) => out.push(KEYWORD_BASE + keyword_id(kw)),
+        TokenKind::Delimiter(d) => out.push(DELIMITER_BASE + delimiter_id(d)),
+        TokenKind::ArithmeticOperator(op) => out.push(ARITHMETIC_BASE + arithmetic_id(op)),
+        TokenKind::RelationalOperator(op) => out.push(RELATIONAL_BASE + relational_id(op)),
+        TokenKind::LogicalOperator(op) => out.push(LOGICAL_BASE + logical_id(op)),
+        TokenKind::AssignmentOperator(op) => out.push(ASSIGNMENT_BASE + assignment_id(op)),
+        TokenKind::BitwiseOperator(op) => out.push(BITWISE_BASE + bitwise_id(op)),
+        TokenKind::SpecialOperator(op) => out.push(SPECIAL_BASE + special_id(op)),
+        TokenKind::Eof => out.push(KIND_EOF),
+        TokenKind::Error { message_code } => {
+            out.push(KIND_ERROR);
+            out.push(error_code_id(message_code));
+        }
+        TokenKind::Identifier(symbol) => {
+            out.push(KIND_IDENTIFIER);
+            write_varint(out, strings.intern(names.resolve(*symbol)));
+        }
+        TokenKind::Literal(Literals::StringLiteral(s)) => {
+            out.push(KIND_STRING);
+            write_varint(out, strings.intern(s));
+        }
+        TokenKind::Literal(Literals::MultilineStringLiteral { raw, value }) => {
+            out.push(KIND_MULTILINE_STRING);
+            write_varint(out, strings.intern(raw));
+            write_varint(out, strings.intern(value));
+        }
+        TokenKind::Literal(Literals::CharacterLiteral(c)) => {
+            out.push(KIND_CHAR);
+            write_varint(out, u64::from(*c));
+        }
+        TokenKind::Literal(Literals::ByteLiteral(b)) => {
+            out.push(KIND_BYTE);
+            out.push(*b);
+        }
+        TokenKind::Literal(Literals::IntLiteral(v)) => {
+            out.push(KIND_INT);
+            write_signed(out, *v);
+        }
+        TokenKind::Literal(Literals::UnsignedIntLiteral(v)) => {
+            out.push(KIND_UNSIGNED);
+            write_varint(out, *v);
+        }
+        TokenKind::Literal(Literals::FloatLiteral(v)) => {
+            out.push(KIND_FLOAT);
+            out.extend_from_slice(&v.to_bits().to_le_bytes());
+        }
+        TokenKind::Literal(Literals::InterpolatedString(parts)) => {
+            out.push(KIND_INTERPOLATED_STRING);
+            write_varint(out, parts.len() as u64);
+            for part in parts {
+                match part {
+                    StringPart::Text(text) => {
+                        out.push(PART_TEXT);
+                        write_varint(out, strings.intern(text));
+                    }
+                    StringPart::Expr(tokens) => {
+                        out.push(PART_EXPR);
+                        encode_tokens(out, strings, names, chars, tokens);
+                    }
+                }
+            }
+        }
+        TokenKind::Literal(Literals::NumericLiteral { value, suffix }) => {
+            out.push(match value {
+                NumericValue::Magnitude(_) => KIND_MAGNITUDE,
+                _ => KIND_NUMERIC,
+            });
+            out.push(type_id(&suffix.type_kind()));
+            match value {
+                NumericValue::Int(v) => write_signed(out, *v),
+                NumericValue::Unsigned(v) | NumericValue::Magnitude(v) => write_varint(out, *v),
+                NumericValue::Float(v) => out.extend_from_slice(&v.to_bits().to_le_bytes()),
+            }
+        }
+        TokenKind::Literal(Literals::BoolLiteral(b)) => {
+            out.push(KIND_BOOL);
+            out.push(u8::from(*b));
+        }
+    }
+}
+
+fn decode_kind(
+    reader: &mut Reader,
+    strings: &[&str],
+    names: &mut Interner,
+    chars: bool,
+) -> Result<TokenKind, DecodeError> {
+    let offset = reader.pos;
+    let id = reader.read_byte()?;
+
+    let kind = match id {
+        KIND_EOF => Some(TokenKind::Eof),
+        KIND_IDENTIFIER => Some(TokenKind::Identifier(
+            names.intern(lookup(strings, reader.read_varint()?)?),
+        )),
+        KIND_STRING => Some(TokenKind::Literal(Literals::StringLiteral(
+            lookup(strings, reader.read_varint()?)?.to_owned(),
+        ))),
+        KIND_MULTILINE_STRING => {
+            let raw = lookup(strings, reader.read_varint()?)?.to_owned();
+            let value = lookup(strings, reader.read_varint()?)?.to_owned();
+            Some(TokenKind::Literal(Literals::MultilineStringLiteral {
+                raw,
+                value,
+            }))
+        }
+        KIND_CHAR => {
+            let offset = reader.pos;
+            let c = u32::try_from(reader.read_varint()?)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or(DecodeError::Overflow { offset })?;
+            Some(TokenKind::Literal(Literals::CharacterLiteral(c)))
+        }
+        KIND_BYTE => Some(TokenKind::Literal(Literals::ByteLiteral(
+            reader.read_byte()?,
+        ))),
+        KIND_INT => Some(TokenKind::Literal(Literals::IntLiteral(
+            reader.read_signed()?,
+        ))),
+        KIND_UNSIGNED => Some(TokenKind::Literal(Literals::UnsignedIntLiteral(
+            reader.read_varint()?,
+        ))),
+        KIND_FLOAT => Some(TokenKind::Literal(Literals::FloatLiteral(
+            reader.read_f64()?,
+        ))),
+        KIND_NUMERIC => {
+            let suffix = read_suffix(reader, |_| true)?;
+            let value = if suffix.is_float() {
+                NumericValue::Float(reader.read_f64()?)
+            } else if suffix.is_unsigned() {
+                NumericValue::Unsigned(reader.read_varint()?)
+            } else {
+                NumericValue::Int(reader.read_signed()?)
+            };
+            Some(TokenKind::Literal(Literals::NumericLiteral {
+                value,
+                suffix,
+            }))
+        }
+        KIND_MAGNITUDE => {
+            let suffix = read_suffix(reader, |s| !s.is_float() && !s.is_unsigned())?;
+            Some(TokenKind::Literal(Literals::NumericLiteral {
+                value: NumericValue::Magnitude(reader.read_varint()?),
+                suffix,
+            }))
+        }
+        KIND_BOOL => {
+            let offset = reader.pos;
+            let b = match reader.read_byte()? {
+                0 => false,
+                1 => true,
+                id => {
+                    return Err(DecodeError::InvalidKind {
+                        id: u64::from(id),
+                        offset,
+                    })
+                }
+            };
+            Some(TokenKind::Literal(Literals::BoolLiteral(b)))
+        }
+        KIND_INTERPOLATED_STRING => {
+            let count = reader.read_len()?;
+            let mut parts = Vec::with_capacity(count.min(reader.bytes.len()));
+            for _ in 0..count {
+                let offset = reader.pos;
+                let part = match reader.read_byte()? {
+                    PART_TEXT => {
+                        StringPart::Text(lookup(strings, reader.read_varint()?)?.to_owned())
+                    }
+                    PART_EXPR => StringPart::Expr(decode_tokens(reader, strings, names, chars)?),
+                    id => {
+                        return Err(DecodeError::InvalidKind {
+                            id: u64::from(id),
+                            offset,
+                        })
+                    }
+                };
+                parts.push(part);
+            }
+            Some(TokenKind::Literal(Literals::InterpolatedString(parts)))
+        }
+        KIND_ERROR => {
+            let offset = reader.pos;
+            let id = reader.read_byte()?;
+            let message_code = error_code_from_id(id).ok_or(DecodeError::InvalidKind {
+                id: u64::from(id),
+                offset,
+            })?;
+            Some(TokenKind::Error { message_code })
+        }
+        _ => fixed_kind_from_id(id),
+    };
+
+    kind.ok_or(DecodeError::InvalidKind {
+        id: u64::from(id),
+        offset,
+    })
+}
+
+/// The keyword, delimiter, or operator kind with the given id.
+fn fixed_kind_from_id(id: u8) -> Option<TokenKind> {
+    match id {
+        KIND_EOF.. => None,
+        SPECIAL_BASE.. => special_from_id(id - SPECIAL_BASE).map(TokenKind::SpecialOperator),
+        BITWISE_BASE.. => bitwise_from_id(id - BITWISE_BASE).map(TokenKind::BitwiseOperator),
+        ASSIGNMENT_BASE.. => {
+            assignment_from_id(id - ASSIGNMENT_BASE).map(TokenKind::AssignmentOperator)
+        }
+        LOGICAL_BASE.. => logical_from_id(id - LOGICAL_BASE).map(TokenKind::LogicalOperator),
+        RELATIONAL_BASE.. => {
+            relational_from_id(id - RELATIONAL_BASE).map(TokenKind::RelationalOperator)
+        }
+        ARITHMETIC_BASE.. => {
+            arithmetic_from_id(id - ARITHMETIC_BASE).map(TokenKind::ArithmeticOperator)
+        }
+        DELIMITER_BASE.. => delimiter_from_id(id - DELIMITER_BASE).map(TokenKind::Delimiter),
+        TYPE_BASE.. => {
+            type_from_id(id - TYPE_BASE).map(|ty| TokenKind::Keyword(Keywords::Type(ty)))
+        }
+        KEYWORD_BASE.. => keyword_from_id(id - KEYWORD_BASE).map(TokenKind::Keyword),
+    }
+}
+
+/// Kind ids of tokens that carry a payload, by name.
+const PAYLOAD_KINDS: [(u8, &str); 14] = [
+    (KIND_EOF, "eof"),
+    (KIND_IDENTIFIER, "identifier"),
+    (KIND_STRING, "string"),
+    (KIND_CHAR, "char"),
+    (KIND_INT, "int"),
+    (KIND_UNSIGNED, "unsigned"),
+    (KIND_FLOAT, "float"),
+    (KIND_ERROR, "error"),
+    (KIND_MULTILINE_STRING, "multiline"),
+    (KIND_BYTE, "byte"),
+    (KIND_NUMERIC, "numeric"),
+    (KIND_INTERPOLATED_STRING, "interpolated"),
+    (KIND_BOOL, "bool"),
+    (KIND_MAGNITUDE, "magnitude"),
+];
+
+/// Every kind id and error code id with what it stands for, one per line,
+/// for [`grammar::fingerprint`](crate::grammar::fingerprint).
+pub(crate) fn kind_table() -> String {
+    let mut table = String::new();
+    for id in 0..=u8::MAX {
+        if let Some(spelling) = fixed_kind_from_id(id).and_then(|kind| kind.fixed_spelling()) {
+            let _ = writeln!(table, "{id} {spelling}");
+        }
+    }
+    for (id, name) in PAYLOAD_KINDS {
+        let _ = writeln!(table, "{id} {name}");
+    }
+    for code in ErrorCode::ALL {
+        let _ = writeln!(table, "error {} {}", error_code_id(&code), code.as_str());
+    }
+    table
+}
+
+fn keyword_id(kw: &Keywords) -> u8 {
+    match kw {
+        Keywords::Func => 0,
+        Keywords::Return => 1,
+        Keywords::If => 2,
+        Keywords::Else => 3,
+        Keywords::Elif => 4,
+        Keywords::Loop => 5,
+        Keywords::Switch => 6,
+        Keywords::Case => 7,
+        Keywords::Var => 8,
+        Keywords::Const => 9,
+        Keywords::Final => 10,
+        Keywords::Import => 11,
+        Keywords::Null => 12,
+        Keywords::Type(_) => unreachable!("type keywords are encoded with TYPE_BASE"),
+    }
+}
+
+fn keyword_from_id(id: u8) -> Option<Keywords> {
+    Some(match id {
+        0 => Keywords::Func,
+        1 => Keywords::Return,
+        2 => Keywords::If,
+        3 => Keywords::Else,
+        4 => Keywords::Elif,
+        5 => Keywords::Loop,
+        6 => Keywords::Switch,
+        7 => Keywords::Case,
+        8 => Keywords::Var,
+        9 => Keywords::Const,
+        10 => Keywords::Final,
+        11 => Keywords::Import,
+        12 => Keywords::Null,
+        _ => return None,
+    })
+}
+
+fn type_id(ty: &TypeKind) -> u8 {
+    match ty {
+        TypeKind::Int8 => 0,
+        TypeKind::Int16 => 1,
+        TypeKind::Int32 => 2,
+        TypeKind::Int64 => 3,
+        TypeKind::Unsigned8 => 4,
+        TypeKind::Unsigned16 => 5,
+        TypeKind::Unsigned32 => 6,
+        TypeKind::Unsigned64 => 7,
+        TypeKind::Float32 => 8,
+        TypeKind::Float64 => 9,
+        TypeKind::String => 10,
+        TypeKind::Char => 11,
+        TypeKind::Struct => 12,
+        TypeKind::Bool => 13,
+    }
+}
+
+fn type_from_id(id: u8) -> Option<TypeKind> {
+    Some(match id {
+        0 => TypeKind::Int8,
+        1 => TypeKind::Int16,
+        2 => TypeKind::Int32,
+        3 => TypeKind::Int64,
+        4 => TypeKind::Unsigned8,
+        5 => TypeKind::Unsigned16,
+        6 => TypeKind::Unsigned32,
+        7 => TypeKind::Unsigned64,
+        8 => TypeKind::Float32,
+        9 => TypeKind::Float64,
+        10 => TypeKind::String,
+        11 => TypeKind::Char,
+        12 => TypeKind::Struct,
+        13 => TypeKind::Bool,
+        _ => return None,
+    })
+}
+
+fn delimiter_id(d: &Delimiters) -> u8 {
+    match d {
+        Delimiters::LeftParen => 0,
+        Delimiters::RightParen => 1,
+        Delimiters::LeftBrace => 2,
+        Delimiters::RightBrace => 3,
+        Delimiters::LeftBracket => 4,
+        Delimiters::RightBracket => 5,
+        Delimiters::Colon => 6,
+        Delimiters::Semicolon => 7,
+        Delimiters::Comma => 8,
+        Delimiters::Dot => 9,
+        Delimiters::QuestionMark => 10,
+    }
+}
+
+fn delimiter_from_id(id: u8) -> Option<Delimiters> {
+    Some(match id {
+        0 => Delimiters::LeftParen,
+        1 => Delimiters::RightParen,
+        2 => Delimiters::LeftBrace,
+        3 => Delimiters::RightBrace,
+        4 => Delimiters::LeftBracket,
+        5 => Delimiters::RightBracket,
+        6 => Delimiters::Colon,
+        7 => Delimiters::Semicolon,
+        8 => Delimiters::Comma,
+        9 => Delimiters::Dot,
+        10 => Delimiters::QuestionMark,
+        _ => return None,
+    })
+}
+
+fn arithmetic_id(op: &ArithmeticOps) -> u8 {
+    match op {
+        ArithmeticOps::Plus => 0,
+        ArithmeticOps::Minus => 1,
+        ArithmeticOps::Asterisk => 2,
+        ArithmeticOps::Slash => 3,
+        ArithmeticOps::Modulo => 4,
+        #[cfg(feature = "experimental-ops")]
+        ArithmeticOps::Exponent => 5,
+    }
+}
+
+fn arithmetic_from_id(id: u8) -> Option<ArithmeticOps> {
+    Some(match id {
+        0 => ArithmeticOps::Plus,
+        1 => ArithmeticOps::Minus,
+        2 => ArithmeticOps::Asterisk,
+        3 => ArithmeticOps::Slash,
+        4 => ArithmeticOps::Modulo,
+        #[cfg(feature = "experimental-ops")]
+        5 => ArithmeticOps::Exponent,
+        _ => return None,
+    })
+}
+
+fn relational_id(op: &RelationalOps) -> u8 {
+    match op {
+        RelationalOps::LessThan => 0,
+        RelationalOps::GreaterThan => 1,
+        RelationalOps::LessThanOrEqual => 2,
+        RelationalOps::GreaterThanOrEqual => 3,
+        RelationalOps::Equal => 4,
+        RelationalOps::NotEqual => 5,
+    }
+}
+
+fn relational_from_id(id: u8) -> Option<RelationalOps> {
+    Some(match id {
+        0 => RelationalOps::LessThan,
+        1 => RelationalOps::GreaterThan,
+        2 => RelationalOps::LessThanOrEqual,
+        3 => RelationalOps::GreaterThanOrEqual,
+        4 => RelationalOps::Equal,
+        5 => RelationalOps::NotEqual,
+        _ => return None,
+    })
+}
+
+fn logical_id(op: &LogicalOps) -> u8 {
+    match op {
+        LogicalOps::And => 0,
+        LogicalOps::Or => 1,
+        LogicalOps::Not => 2,
+    }
+}
+
+fn logical_from_id(id: u8) -> Option<LogicalOps> {
+    Some(match id {
+        0 => LogicalOps::And,
+        1 => LogicalOps::Or,
+        2 => LogicalOps::Not,
+        _ => return None,
+    })
+}
+
+fn assignment_id(op: &AssignmentOps) -> u8 {
+    match op {
+        AssignmentOps::Assign => 0,
+        AssignmentOps::AddAssign => 1,
+        AssignmentOps::SubtractAssign => 2,
+        AssignmentOps::MultiplyAssign => 3,
+        AssignmentOps::DivideAssign => 4,
+        AssignmentOps::ModuloAssign => 5,
+        #[cfg(feature = "experimental-ops")]
+        AssignmentOps::ExponentAssign => 6,
+        AssignmentOps::NullCoalescingAssign => 7,
+    }
+}
+
+fn assignment_from_id(id: u8) -> Option<AssignmentOps> {
+    Some(match id {
+        0 => AssignmentOps::Assign,
+        1 => AssignmentOps::AddAssign,
+        2 => AssignmentOps::SubtractAssign,
+        3 => AssignmentOps::MultiplyAssign,
+        4 => AssignmentOps::DivideAssign,
+        5 => AssignmentOps::ModuloAssign,
+        #[cfg(feature = "experimental-ops")]
+        6 => AssignmentOps::ExponentAssign,
+        7 => AssignmentOps::NullCoalescingAssign,
+        _ => return None,
+    })
+}
+
+fn bitwise_id(op: &BitwiseOps) -> u8 {
+    match op {
+        BitwiseOps::And => 0,
+        BitwiseOps::Or => 1,
+        BitwiseOps::Xor => 2,
+        BitwiseOps::Not => 3,
+        BitwiseOps::LeftShift => 4,
+        BitwiseOps::RightShift => 5,
+    }
+}
+
+fn bitwise_from_id(id: u8) -> Option<BitwiseOps> {
+    Some(match id {
+        0 => BitwiseOps::And,
+        1 => BitwiseOps::Or,
+        2 => BitwiseOps::Xor,
+        3 => BitwiseOps::Not,
+        4 => BitwiseOps::LeftShift,
+        5 => BitwiseOps::RightShift,
+        _ => return None,
+    })
+}
+
+fn special_id(op: &SpecialOps) -> u8 {
+    match op {
+        SpecialOps::PointerAccess => 0,
+        SpecialOps::ScopingOperator => 1,
+        SpecialOps::Ellipsis => 2,
+        SpecialOps::NullCoalescing => 3,
+    }
+}
+
+fn special_from_id(id: u8) -> Option<SpecialOps> {
+    Some(match id {
+        0 => SpecialOps::PointerAccess,
+        1 => SpecialOps::ScopingOperator,
+        2 => SpecialOps::Ellipsis,
+        3 => SpecialOps::NullCoalescing,
+        _ => return None,
+    })
+}
+
+fn error_code_id(code: &ErrorCode) -> u8 {
+    match code {
+        ErrorCode::UnexpectedCharacter => 0,
+        ErrorCode::UnterminatedString => 1,
+        ErrorCode::InvalidEscape => 2,
+        ErrorCode::InvalidNumber => 3,
+        ErrorCode::UnexpectedEof => 4,
+        ErrorCode::InvalidUtf8 => 5,
+        ErrorCode::EmptyInput => 6,
+        ErrorCode::InputTooLarge => 7,
+        ErrorCode::InvalidCharLiteral => 8,
+        ErrorCode::InvalidNumberSuffix => 9,
+        ErrorCode::UnterminatedBlockComment => 10,
+        ErrorCode::LookalikeCharacter => 11,
+        ErrorCode::Io => 12,
+        ErrorCode::RequiresEdition => 13,
+    }
+}
+
+fn error_code_from_id(id: u8) -> Option<ErrorCode> {
+    Some(match id {
+        0 => ErrorCode::UnexpectedCharacter,
+        1 => ErrorCode::UnterminatedString,
+        2 => ErrorCode::InvalidEscape,
+        3 => ErrorCode::InvalidNumber,
+        4 => ErrorCode::UnexpectedEof,
+        5 => ErrorCode::InvalidUtf8,
+        6 => ErrorCode::EmptyInput,
+        7 => ErrorCode::InputTooLarge,
+        8 => ErrorCode::InvalidCharLiteral,
+        9 => ErrorCode::InvalidNumberSuffix,
+        10 => ErrorCode::UnterminatedBlockComment,
+        11 => ErrorCode::LookalikeCharacter,
+        12 => ErrorCode::Io,
+        13 => ErrorCode::RequiresEdition,
+        _ => return None,
+    })
+}
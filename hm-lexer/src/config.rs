@@ -0,0 +1,581 @@
+//! Lexer configuration.
+//!
+//! [`LexerConfig`] collects the options that change how a
+//! [`Lexer`](crate::lexer::Lexer) behaves. `Lexer::new` uses the defaults;
+//! `Lexer::with_config` accepts a customized configuration, which
+//! [`LexerBuilder`] assembles one option at a time.
+
+use std::fmt;
+
+use crate::charstream::CharStream;
+use crate::deprecation::DeprecationTable;
+use crate::keywordtable::KeywordTable;
+use crate::lexer::Lexer;
+
+/// How the lexer resynchronizes after an error in recovery mode.
+///
+/// Different consumers want different trade-offs: a formatter prefers to
+/// lose as little text as possible, while a compiler prefers to resume at a
+/// boundary the parser can recover from.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub enum SyncStrategy {
+    /// Skip to the next whitespace byte.
+    #[default]
+    Whitespace,
+    /// Skip to the next statement terminator `;`, which is left to be lexed.
+    StatementTerminator,
+    /// Skip past the end of the current line.
+    NextLine,
+}
+
+/// A version of the language's syntax.
+///
+/// Each edition accepts everything the previous one did plus newer token
+/// forms. Lexing with an older edition rejects the newer forms with
+/// [`LexError::RequiresEdition`](crate::lexerror::LexError::RequiresEdition)
+/// rather than silently accepting source its compiler would not.
+///
+/// | Edition | Adds |
+/// |---------|------|
+/// | 1 | The original syntax |
+/// | 2 | `0b` binary and `0o` octal literals, `b'x'` byte literals |
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::charstream::CharStream;
+/// # use hm_lexer::config::Edition;
+/// # use hm_lexer::lexer::Lexer;
+/// let stream = CharStream::from_bytes(b"var mask = 0b1010;").unwrap();
+/// let mut lexer = Lexer::builder().edition(Edition::V1).build(stream);
+/// let err = lexer.find_map(Result::err).unwrap();
+/// assert_eq!(
+///     err.to_string(),
+///     "Binary literals require edition 2 at line 1, column 12"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edition {
+    /// The original syntax.
+    V1,
+    /// Adds radix-prefixed integer literals and byte literals.
+    #[default]
+    V2,
+}
+
+impl Edition {
+    /// The newest edition, which accepts every token form.
+    pub const LATEST: Edition = Edition::V2;
+
+    /// The edition's number, as written in messages and manifests.
+    pub fn number(self) -> u32 {
+        match self {
+            Edition::V1 => 1,
+            Edition::V2 => 2,
+        }
+    }
+
+    /// Look up an edition by its number, the inverse of [`Self::number`].
+    pub fn from_number(number: u32) -> Option<Self> {
+        match number {
+            1 => Some(Edition::V1),
+            2 => Some(Edition::V2),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// The unit in which [`CharStream`](crate::charstream::CharStream) counts
+/// columns.
+///
+/// Consumers disagree on what a column is: plain logs and byte-oriented tools
+/// count bytes, terminals and humans count characters, and the Language
+/// Server Protocol counts UTF-16 code units by default. Lines are unaffected.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::charstream::CharStream;
+/// # use hm_lexer::config::ColumnUnit;
+/// let mut stream = CharStream::from_bytes("é🙂x".as_bytes()).unwrap();
+/// stream.set_column_unit(ColumnUnit::Utf16);
+/// stream.advance_n(6); // 2-byte `é` and 4-byte `🙂`
+/// assert_eq!(stream.column(), 4);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub enum ColumnUnit {
+    /// One column per byte.
+    #[default]
+    Bytes,
+    /// One column per Unicode scalar value (`char`).
+    Chars,
+    /// One column per UTF-16 code unit, so characters outside the Basic
+    /// Multilingual Plane count as two.
+    Utf16,
+}
+
+impl ColumnUnit {
+    /// Number of columns contributed by a (non-newline) byte of UTF-8 input.
+    ///
+    /// Multi-byte characters are counted at their lead byte; continuation
+    /// bytes contribute nothing in the character-based units.
+    #[inline]
+    pub fn width_of(self, byte: u8) -> usize {
+        match self {
+            ColumnUnit::Bytes => 1,
+            ColumnUnit::Chars => usize::from(!is_continuation(byte)),
+            ColumnUnit::Utf16 => match byte {
+                0x80..=0xBF => 0,
+                0xF0..=0xFF => 2,
+                _ => 1,
+            },
+        }
+    }
+}
+
+/// How `\r\n` (CRLF) line endings are treated.
+///
+/// By default only `\n` ends a line and a `\r` before it is an ordinary
+/// byte, so a position at the end of a line on a Windows-authored file is
+/// one column further right than an editor shows it.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::charstream::CharStream;
+/// # use hm_lexer::config::LineEndings;
+/// let mut stream = CharStream::from_bytes(b"ab\r\ncd").unwrap();
+/// stream.set_line_endings(LineEndings::Crlf);
+/// stream.advance_n(3);
+/// assert_eq!(stream.line_column(), (1, 3)); // at the `\n`, right after `b`
+/// stream.advance();
+/// assert_eq!(stream.line_column(), (2, 1));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub enum LineEndings {
+    /// Only `\n` ends a line; a `\r` before it occupies a column.
+    #[default]
+    Lf,
+    /// `\r\n` ends a line as a single terminator: the `\r` occupies no
+    /// column. A lone `\r` is still an ordinary byte.
+    Crlf,
+    /// Like [`Crlf`](Self::Crlf), and `\r\n` written inside a triple-quoted
+    /// string is also normalized to `\n` in the literal's value. Escaped
+    /// `\r\n` sequences are kept.
+    Normalize,
+}
+
+impl LineEndings {
+    /// Whether `\r\n` is tracked as a single line terminator.
+    pub fn is_crlf(self) -> bool {
+        !matches!(self, LineEndings::Lf)
+    }
+}
+
+/// The settings that decide how far each byte of a line moves the column,
+/// shared by [`CharStream`](crate::charstream::CharStream), the
+/// [`LineIndex`](crate::lineindex::LineIndex), and stream validation so
+/// they always agree.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub(crate) struct ColumnCounter {
+    /// The unit in which columns are counted
+    pub unit: ColumnUnit,
+    /// Whether the `\r` of a `\r\n` occupies a column
+    pub line_endings: LineEndings,
+    /// Distance between tab stops; 0 and 1 count a tab as one column
+    pub tab_width: usize,
+}
+
+impl ColumnCounter {
+    /// The column after the byte at `index` of `bytes`, which is not a
+    /// newline and starts at `column`.
+    ///
+    /// A tab moves to the next tab stop, the `\r` of a `\r\n` occupies no
+    /// column when CRLF endings are tracked, and any other byte advances by
+    /// its width in `unit`.
+    #[inline]
+    pub fn advance(&self, column: usize, bytes: &[u8], index: usize) -> usize {
+        match bytes[index] {
+            b'\t' if self.tab_width > 1 => {
+                (column - 1) / self.tab_width * self.tab_width + self.tab_width + 1
+            }
+            b'\r' if self.line_endings.is_crlf() && bytes.get(index + 1) == Some(&b'\n') => column,
+            b => column + self.unit.width_of(b),
+        }
+    }
+
+    /// The column after `bytes[start..end]`, which holds no newline and
+    /// starts at `column`; [`advance`](Self::advance) applied to each byte.
+    #[inline]
+    pub fn advance_over(&self, column: usize, bytes: &[u8], start: usize, end: usize) -> usize {
+        if self.unit == ColumnUnit::Bytes && self.tab_width <= 1 && !self.line_endings.is_crlf() {
+            return column + (end - start);
+        }
+        (start..end).fold(column, |column, index| self.advance(column, bytes, index))
+    }
+}
+
+/// Whether `byte` is a UTF-8 continuation byte (`0b10xx_xxxx`).
+#[inline]
+pub(crate) fn is_continuation(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
+
+/// Options controlling lexer behavior.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::config::{LexerConfig, SyncStrategy};
+/// let config = LexerConfig {
+///     error_recovery: true,
+///     sync_strategy: SyncStrategy::StatementTerminator,
+///     ..LexerConfig::default()
+/// };
+/// # let _ = config;
+/// ```
+#[derive(Clone, Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct LexerConfig {
+    /// Resynchronize after an error so that lexing can continue with the
+    /// next call to `next_token`. When disabled, the stream is left wherever
+    /// the error was detected.
+    pub error_recovery: bool,
+
+    /// Where to resume after an error when `error_recovery` is enabled.
+    pub sync_strategy: SyncStrategy,
+
+    /// The unit in which span and error columns are counted.
+    pub column_unit: ColumnUnit,
+
+    /// Distance between tab stops when counting columns: a tab moves the
+    /// column to the next multiple of `tab_width` plus one, the way editors
+    /// display it. The default, 0, and 1 both count a tab as one column.
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::config::LexerConfig;
+    /// # use hm_lexer::lexer::Lexer;
+    /// let config = LexerConfig {
+    ///     tab_width: 4,
+    ///     ..LexerConfig::default()
+    /// };
+    /// let mut lexer = Lexer::with_config(CharStream::from_bytes(b"\tx\ty").unwrap(), config);
+    /// assert_eq!(lexer.next_token().unwrap().span.column_start, 5);
+    /// assert_eq!(lexer.next_token().unwrap().span.column_start, 9);
+    /// ```
+    pub tab_width: usize,
+
+    /// Skip line and column bookkeeping: spans carry byte offsets only,
+    /// with every line and column field set to 0. A
+    /// [`LineIndex`](crate::lineindex::LineIndex) over the same source fills
+    /// them in on demand, typically just for spans that end up in a
+    /// diagnostic. Errors returned by the lexer are still fully located.
+    pub offsets_only: bool,
+
+    /// Never copy lexeme text out of the source: identifiers, literals,
+    /// and error tokens get an empty `lexeme`, so the token is just its
+    /// kind and span. Keywords and punctuation keep their static spelling,
+    /// which costs nothing. Callers that need the text of a few tokens get
+    /// it with [`Token::text`](crate::token::Token::text) from the source
+    /// they lexed. Identifier names are still interned in the lexer's
+    /// [`Interner`](crate::intern::Interner).
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexer::Lexer;
+    /// let source = "total = 42;";
+    /// let mut lexer = Lexer::builder()
+    ///     .lazy_lexemes(true)
+    ///     .build(CharStream::from_bytes(source.as_bytes()).unwrap());
+    /// let tokens: Vec<_> = lexer.by_ref().take(3).map(Result::unwrap).collect();
+    /// assert!(tokens[2].lexeme.is_empty());
+    /// assert_eq!(tokens[2].text(source), "42");
+    /// assert_eq!(tokens[1].lexeme, "=");
+    /// ```
+    pub lazy_lexemes: bool,
+
+    /// Record each token's start and end as character (Unicode scalar)
+    /// offsets in [`Span::char_offsets`](crate::token::span::Span::char_offsets),
+    /// for editor APIs that address text by character. The count is
+    /// maintained incrementally by the stream, so this costs a branch per
+    /// byte rather than a rescan per token.
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::config::LexerConfig;
+    /// # use hm_lexer::lexer::Lexer;
+    /// let stream = CharStream::from_bytes("\"héllo\" x".as_bytes()).unwrap();
+    /// let config = LexerConfig {
+    ///     char_offsets: true,
+    ///     ..LexerConfig::default()
+    /// };
+    /// let mut lexer = Lexer::with_config(stream, config);
+    /// lexer.next_token().unwrap();
+    /// let x = lexer.next_token().unwrap();
+    /// assert_eq!((x.span.start, x.span.end), (9, 10));
+    /// assert_eq!(x.span.char_offsets, Some((8, 9)));
+    /// ```
+    pub char_offsets: bool,
+
+    /// Strip the common leading indentation from triple-quoted strings, like
+    /// Java text blocks or Kotlin's `trimIndent`. A blank first and last line
+    /// (the lines holding the delimiters) are removed as well. The unprocessed
+    /// text remains available as the literal's `raw` field.
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::config::LexerConfig;
+    /// # use hm_lexer::lexer::Lexer;
+    /// # use hm_lexer::token::literals::Literals;
+    /// # use hm_lexer::token::tokenkind::TokenKind;
+    /// let source = "\"\"\"\n    fn main:\n        run\n    \"\"\"";
+    /// let stream = CharStream::from_bytes(source.as_bytes()).unwrap();
+    /// let config = LexerConfig {
+    ///     trim_indent: true,
+    ///     ..LexerConfig::default()
+    /// };
+    /// let token = Lexer::with_config(stream, config).next_token().unwrap();
+    /// let TokenKind::Literal(Literals::MultilineStringLiteral { value, .. }) = token.kind else {
+    ///     panic!("expected a multiline string");
+    /// };
+    /// assert_eq!(value, "fn main:\n    run");
+    /// ```
+    pub trim_indent: bool,
+
+    /// How `\r\n` line endings are counted, and whether they are normalized
+    /// in triple-quoted string values; see [`LineEndings`].
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::config::{LexerConfig, LineEndings};
+    /// # use hm_lexer::lexer::Lexer;
+    /// # use hm_lexer::token::literals::Literals;
+    /// # use hm_lexer::token::tokenkind::TokenKind;
+    /// let source = "x\r\n\"\"\"a\r\nb\"\"\"";
+    /// let config = LexerConfig {
+    ///     line_endings: LineEndings::Normalize,
+    ///     ..LexerConfig::default()
+    /// };
+    /// let mut lexer = Lexer::with_config(CharStream::from_bytes(source.as_bytes()).unwrap(), config);
+    /// let x = lexer.next_token().unwrap();
+    /// assert_eq!((x.span.line_end, x.span.column_end), (1, 2));
+    /// let TokenKind::Literal(Literals::MultilineStringLiteral { raw, value }) = lexer.next_token().unwrap().kind else {
+    ///     panic!("expected a multiline string");
+    /// };
+    /// assert_eq!((raw.as_str(), value.as_str()), ("a\r\nb", "a\nb"));
+    /// ```
+    pub line_endings: LineEndings,
+
+    /// Lex consecutive string literals separated only by whitespace and
+    /// comments as a single `StringLiteral`, as in C. The token's span and
+    /// lexeme cover everything from the first opening quote to the last
+    /// closing quote. Triple-quoted strings are never joined.
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::config::LexerConfig;
+    /// # use hm_lexer::lexer::Lexer;
+    /// # use hm_lexer::token::literals::Literals;
+    /// # use hm_lexer::token::tokenkind::TokenKind;
+    /// let source = "\"long \"\n    // split here\n    \"message\";";
+    /// let stream = CharStream::from_bytes(source.as_bytes()).unwrap();
+    /// let config = LexerConfig {
+    ///     concat_adjacent_strings: true,
+    ///     ..LexerConfig::default()
+    /// };
+    /// let mut lexer = Lexer::with_config(stream, config);
+    /// let token = lexer.next_token().unwrap();
+    /// assert!(matches!(
+    ///     token.kind,
+    ///     TokenKind::Literal(Literals::StringLiteral(ref s)) if s == "long message"
+    /// ));
+    /// assert_eq!((token.span.start, token.span.end), (0, 39));
+    /// assert_eq!(lexer.next_token().unwrap().lexeme, ";");
+    /// ```
+    pub concat_adjacent_strings: bool,
+
+    /// Leave escape sequences in string literals undecoded, so the value of
+    /// `"a\tb"` is the four characters `a\tb` rather than `a`, tab, `b`.
+    /// Escapes are not validated either; a backslash only protects the
+    /// character after it from ending the literal. Later phases, such as a
+    /// formatter that must reproduce the source or a compile-time string
+    /// processor with its own escapes, can then interpret them as they see
+    /// fit. Applies to plain and triple-quoted strings, not to character
+    /// literals.
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::config::LexerConfig;
+    /// # use hm_lexer::lexer::Lexer;
+    /// # use hm_lexer::token::literals::Literals;
+    /// # use hm_lexer::token::tokenkind::TokenKind;
+    /// let stream = CharStream::from_bytes(br#""tab\there \q""#).unwrap();
+    /// let config = LexerConfig {
+    ///     keep_escapes: true,
+    ///     ..LexerConfig::default()
+    /// };
+    /// let token = Lexer::with_config(stream, config).next_token().unwrap();
+    /// assert!(matches!(
+    ///     token.kind,
+    ///     TokenKind::Literal(Literals::StringLiteral(ref s)) if s == r"tab\there \q"
+    /// ));
+    /// ```
+    pub keep_escapes: bool,
+
+    /// Deprecated keyword and operator spellings to warn about. Deprecated
+    /// spellings still lex normally; see [`Lexer::warnings`](crate::lexer::Lexer::warnings).
+    pub deprecations: DeprecationTable,
+
+    /// Spellings lexed as keywords. Defaults to the built-in keywords; see
+    /// [`KeywordTable`] for adding, aliasing, or removing them.
+    pub keywords: KeywordTable,
+
+    /// The language edition to accept. Defaults to [`Edition::LATEST`];
+    /// older editions reject token forms introduced after them.
+    pub edition: Edition,
+}
+
+/// Builder for a [`Lexer`] with non-default options.
+///
+/// Created with [`Lexer::builder`]; each method sets the [`LexerConfig`]
+/// field of the same name, and unset options keep their defaults.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::charstream::CharStream;
+/// # use hm_lexer::config::SyncStrategy;
+/// # use hm_lexer::lexer::Lexer;
+/// let stream = CharStream::from_bytes(b"var x = @;\n\tvar y = 2;").unwrap();
+/// let mut lexer = Lexer::builder()
+///     .error_recovery(true)
+///     .sync_strategy(SyncStrategy::StatementTerminator)
+///     .tab_width(4)
+///     .build(stream);
+///
+/// let tokens: Vec<_> = lexer.by_ref().map(Result::unwrap).collect();
+/// assert_eq!(lexer.errors().len(), 1);
+/// let y = tokens.iter().find(|t| t.lexeme == "y").unwrap();
+/// assert_eq!(y.span.column_start, 9);
+/// ```
+#[derive(Clone, Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct LexerBuilder {
+    config: LexerConfig,
+}
+
+impl LexerBuilder {
+    /// See [`LexerConfig::error_recovery`].
+    pub fn error_recovery(mut self, enabled: bool) -> Self {
+        self.config.error_recovery = enabled;
+        self
+    }
+
+    /// See [`LexerConfig::sync_strategy`].
+    pub fn sync_strategy(mut self, strategy: SyncStrategy) -> Self {
+        self.config.sync_strategy = strategy;
+        self
+    }
+
+    /// See [`LexerConfig::column_unit`].
+    pub fn column_unit(mut self, unit: ColumnUnit) -> Self {
+        self.config.column_unit = unit;
+        self
+    }
+
+    /// See [`LexerConfig::tab_width`].
+    pub fn tab_width(mut self, width: usize) -> Self {
+        self.config.tab_width = width;
+        self
+    }
+
+    /// See [`LexerConfig::offsets_only`].
+    pub fn offsets_only(mut self, enabled: bool) -> Self {
+        self.config.offsets_only = enabled;
+        self
+    }
+
+    /// See [`LexerConfig::lazy_lexemes`].
+    pub fn lazy_lexemes(mut self, enabled: bool) -> Self {
+        self.config.lazy_lexemes = enabled;
+        self
+    }
+
+    /// See [`LexerConfig::char_offsets`].
+    pub fn char_offsets(mut self, enabled: bool) -> Self {
+        self.config.char_offsets = enabled;
+        self
+    }
+
+    /// See [`LexerConfig::trim_indent`].
+    pub fn trim_indent(mut self, enabled: bool) -> Self {
+        self.config.trim_indent = enabled;
+        self
+    }
+
+    /// See [`LexerConfig::line_endings`].
+    pub fn line_endings(mut self, line_endings: LineEndings) -> Self {
+        self.config.line_endings = line_endings;
+        self
+    }
+
+    /// See [`LexerConfig::concat_adjacent_strings`].
+    pub fn concat_adjacent_strings(mut self, enabled: bool) -> Self {
+        self.config.concat_adjacent_strings = enabled;
+        self
+    }
+
+    /// See [`LexerConfig::keep_escapes`].
+    pub fn keep_escapes(mut self, enabled: bool) -> Self {
+        self.config.keep_escapes = enabled;
+        self
+    }
+
+    /// See [`LexerConfig::deprecations`].
+    pub fn deprecations(mut self, table: DeprecationTable) -> Self {
+        self.config.deprecations = table;
+        self
+    }
+
+    /// See [`LexerConfig::keywords`].
+    pub fn keywords(mut self, table: KeywordTable) -> Self {
+        self.config.keywords = table;
+        self
+    }
+
+    /// See [`LexerConfig::edition`].
+    pub fn edition(mut self, edition: Edition) -> Self {
+        self.config.edition = edition;
+        self
+    }
+
+    /// The configuration built so far, e.g. to create several lexers with
+    /// [`Lexer::with_config`].
+    pub fn config(&self) -> &LexerConfig {
+        &self.config
+    }
+
+    /// Finish building and create a lexer over `stream`.
+    pub fn build(self, stream: CharStream) -> Lexer {
+        Lexer::with_config(stream, self.config)
+    }
+}
+
+impl From<LexerConfig> for LexerBuilder {
+    /// Start from an existing configuration instead of the defaults.
+    fn from(config: LexerConfig) -> Self {
+        Self { config }
+    }
+}
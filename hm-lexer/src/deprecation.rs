@@ -0,0 +1,125 @@
+//! Lexer-level deprecation warnings.
+//!
+//! A [`DeprecationTable`] maps old spellings of keywords, operators, and
+//! punctuation to their replacements. When a table is set in
+//! [`LexerConfig::deprecations`](crate::config::LexerConfig::deprecations),
+//! the lexer still produces the usual token for a deprecated spelling but
+//! also records a [`DeprecationWarning`], which callers collect with
+//! [`Lexer::take_warnings`](crate::lexer::Lexer::take_warnings). Toolchains
+//! keep one table per language edition and pick the one matching the code
+//! being compiled, so old syntax can be phased out gradually.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::token::span::Span;
+
+/// The replacement and explanation for one deprecated spelling.
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Deprecation {
+    /// The spelling to use instead
+    pub replacement: String,
+    /// Why the spelling is deprecated, or when it will be removed
+    pub message: String,
+}
+
+/// Deprecated spellings and their replacements.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::charstream::CharStream;
+/// # use hm_lexer::config::LexerConfig;
+/// # use hm_lexer::deprecation::DeprecationTable;
+/// # use hm_lexer::lexer::Lexer;
+/// let config = LexerConfig {
+///     deprecations: DeprecationTable::new().deprecate(
+///         "elif",
+///         "else if",
+///         "`elif` is removed in the next edition",
+///     ),
+///     ..LexerConfig::default()
+/// };
+/// let stream = CharStream::from_bytes(b"if a {} elif b {}").unwrap();
+/// let mut lexer = Lexer::with_config(stream, config);
+/// while !lexer.next_token().unwrap().is_eof() {}
+///
+/// let warnings = lexer.take_warnings();
+/// assert_eq!(warnings.len(), 1);
+/// assert_eq!(warnings[0].replacement, "else if");
+/// assert_eq!(warnings[0].span.start, 8);
+/// ```
+#[derive(Clone, Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct DeprecationTable {
+    entries: HashMap<String, Deprecation>,
+}
+
+impl DeprecationTable {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a deprecated spelling, replacing any earlier entry for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `spelling` - The source text of the deprecated keyword or operator
+    /// * `replacement` - The spelling to suggest instead
+    /// * `message` - Explanation shown with the warning
+    pub fn deprecate(
+        mut self,
+        spelling: impl Into<String>,
+        replacement: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.entries.insert(
+            spelling.into(),
+            Deprecation {
+                replacement: replacement.into(),
+                message: message.into(),
+            },
+        );
+        self
+    }
+
+    /// Look up a spelling.
+    pub fn get(&self, spelling: &str) -> Option<&Deprecation> {
+        self.entries.get(spelling)
+    }
+
+    /// Whether the table has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// A deprecated spelling found in the source.
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct DeprecationWarning {
+    /// The deprecated spelling as written
+    pub spelling: String,
+    /// The spelling to use instead
+    pub replacement: String,
+    /// Explanation from the [`DeprecationTable`]
+    pub message: String,
+    /// Location of the deprecated token
+    pub span: Span,
+}
+
+impl fmt::Display for DeprecationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is deprecated at line {}, column {}: {} (use '{}' instead)",
+            self.spelling,
+            self.span.line_start,
+            self.span.column_start,
+            self.message,
+            self.replacement
+        )
+    }
+}
@@ -0,0 +1,101 @@
+//! [`miette`] diagnostics for [`LexError`].
+//!
+//! With the `miette` feature enabled, every [`LexError`] is a
+//! [`miette::Diagnostic`]: its [`ErrorCode`](crate::lexerror::ErrorCode) is
+//! the diagnostic code, its span becomes a labeled source span, and most
+//! variants suggest a fix as help text. Attach the source the error came
+//! from to get an annotated snippet:
+//!
+//! ```
+//! use miette::{Diagnostic, NamedSource, Report};
+//!
+//! let source = "var s = \"open";
+//! let err = hm_lexer::tokenize(source).unwrap_err();
+//! // The inherent `LexError::code` shadows the trait method.
+//! assert_eq!(Diagnostic::code(&err).unwrap().to_string(), "L0002");
+//! let label = err.labels().unwrap().next().unwrap();
+//! assert_eq!((label.offset(), label.len()), (8, 5));
+//!
+//! let report = Report::new(err).with_source_code(NamedSource::new("main.hm", source));
+//! assert!(report.help().is_some());
+//! ```
+//!
+//! Errors lexed from a slice of a larger text, such as a Markdown block, are
+//! already shifted to offsets in the whole text, so attach that text instead.
+
+use std::fmt::Display;
+
+use miette::{Diagnostic, LabeledSpan};
+
+use crate::grammar::ESCAPES;
+use crate::lexerror::{expected_suffixes, CharLiteralReason, LexError};
+
+impl Diagnostic for LexError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(LexError::code(self).as_str()))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let help = match self {
+            LexError::LookalikeCharacter { suggestion, .. } => {
+                format!("replace it with `{suggestion}`")
+            }
+            LexError::UnterminatedString { .. } => {
+                "add a closing `\"`, or open the string with `\"\"\"` to continue it over several lines"
+                    .to_string()
+            }
+            LexError::UnterminatedBlockComment { .. } => {
+                "add a closing `*/`; block comments nest, so each `/*` inside needs its own `*/`"
+                    .to_string()
+            }
+            LexError::InvalidEscape { .. } => {
+                let escapes: Vec<&str> = ESCAPES.iter().map(|(escape, _)| *escape).collect();
+                format!(
+                    "valid escapes are {}, and `\\u{{...}}` with one to six hex digits",
+                    escapes.join(" ")
+                )
+            }
+            LexError::InvalidCharLiteral { reason, .. } => match reason {
+                CharLiteralReason::TooManyCharacters => {
+                    "a character literal holds one character; use `\"...\"` for a string"
+                }
+                CharLiteralReason::Empty => "write the character between the quotes, e.g. `' '`",
+                CharLiteralReason::MissingClosingQuote => "add a closing `'`",
+                CharLiteralReason::NonAscii => "use a character literal `'...'` instead",
+            }
+            .to_string(),
+            LexError::InvalidNumberSuffix { allowed, .. } => expected_suffixes(allowed.suffixes()),
+            LexError::InvalidUtf8 { .. } => "save the source file as UTF-8".to_string(),
+            LexError::InputTooLarge { .. } => "split the input into smaller files".to_string(),
+            LexError::Io { .. } => "check that the file exists and is readable".to_string(),
+            LexError::RequiresEdition { required, .. } => {
+                format!("set the edition to {required} or later")
+            }
+            _ => return None,
+        };
+        Some(Box::new(help))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let text = match self {
+            LexError::UnexpectedCharacter { .. } => "not valid here".to_string(),
+            LexError::LookalikeCharacter { name, .. } => format!("this is a {name}"),
+            LexError::UnterminatedString { .. } => "string starts here".to_string(),
+            LexError::UnterminatedBlockComment { .. } => "comment starts here".to_string(),
+            LexError::InvalidEscape { sequence, .. } => {
+                format!("`{sequence}` in this literal")
+            }
+            LexError::InvalidCharLiteral { reason, .. } => reason.to_string(),
+            LexError::InvalidNumber { .. } => "malformed number".to_string(),
+            LexError::InvalidNumberSuffix { .. } => "unknown suffix".to_string(),
+            LexError::UnexpectedEof { .. } => "input ends here".to_string(),
+            LexError::InvalidUtf8 { .. } => "invalid UTF-8".to_string(),
+            LexError::RequiresEdition { required, .. } => format!("requires edition {required}"),
+            LexError::EmptyInput | LexError::InputTooLarge { .. } | LexError::Io { .. } => {
+                return None
+            }
+        };
+        let label = LabeledSpan::new_primary_with_span(Some(text), self.highlighted()?);
+        Some(Box::new(std::iter::once(label)))
+    }
+}
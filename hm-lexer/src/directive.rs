@@ -0,0 +1,199 @@
+//! Magic comments that configure tools.
+//!
+//! Comments of the form `// hm: no-format` or `// hm-lint: disable unused`
+//! carry instructions for the formatter, linter, and other tools. The lexer
+//! skips them like any other comment; [`directives`] reads them back from
+//! the trivia between tokens and parses them into [`Directive`] values, so
+//! every tool shares one syntax instead of matching comments with its own
+//! regular expressions.
+//!
+//! A directive comment starts with a namespace and a colon, followed by a
+//! command name and optional arguments separated by whitespace or commas:
+//!
+//! ```text
+//! // hm-lint: disable unused, shadowing
+//! /* hm: no-format */
+//! ```
+
+use std::fmt;
+
+use crate::highlight::comment_ranges;
+use crate::token::span::Span;
+use crate::token::Token;
+
+/// The tool family a directive is addressed to.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub enum Namespace {
+    /// `hm:`, for the compiler and formatter
+    Hm,
+    /// `hm-lint:`, for the linter
+    Lint,
+}
+
+impl Namespace {
+    /// The prefix that introduces this namespace, without the colon.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Namespace::Hm => "hm",
+            Namespace::Lint => "hm-lint",
+        }
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A parsed directive comment.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Directive {
+    /// Which tool the directive is for
+    pub namespace: Namespace,
+    /// The command, e.g. `disable` or `no-format`
+    pub name: String,
+    /// Arguments following the command, e.g. the lint names to disable
+    pub args: Vec<String>,
+    /// The whole comment, markers included
+    pub span: Span,
+}
+
+impl Directive {
+    /// Parse the text of a single comment, markers included.
+    ///
+    /// # Arguments
+    ///
+    /// * `comment` - A `//` or `/* */` comment
+    /// * `span` - Where the comment is in its source
+    ///
+    /// # Returns
+    ///
+    /// `Some(Directive)` if the comment starts with a known namespace
+    /// followed by a command name, otherwise `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::directive::{Directive, Namespace};
+    /// # use hm_lexer::tokenize;
+    /// let span = tokenize("x").unwrap().0[0].span;
+    /// let directive = Directive::parse("// hm-lint: disable unused,shadowing", span).unwrap();
+    /// assert!(directive.namespace == Namespace::Lint);
+    /// assert_eq!(directive.name, "disable");
+    /// assert_eq!(directive.args, ["unused", "shadowing"]);
+    ///
+    /// assert!(Directive::parse("// hm is short for hummingbird", span).is_none());
+    /// ```
+    pub fn parse(comment: &str, span: Span) -> Option<Directive> {
+        let body = if let Some(rest) = comment.strip_prefix("//") {
+            rest
+        } else {
+            let rest = comment.strip_prefix("/*")?;
+            rest.strip_suffix("*/").unwrap_or(rest)
+        };
+
+        let (prefix, rest) = body.trim_start().split_once(':')?;
+        let namespace = match prefix {
+            "hm" => Namespace::Hm,
+            "hm-lint" => Namespace::Lint,
+            _ => return None,
+        };
+
+        let mut words = rest
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|word| !word.is_empty())
+            .map(str::to_string);
+        let name = words.next()?;
+        Some(Directive {
+            namespace,
+            name,
+            args: words.collect(),
+            span,
+        })
+    }
+}
+
+/// Collect every directive comment in `source`, in source order.
+///
+/// Comments are found in the gaps between consecutive token spans, so
+/// directive-like text inside string literals is never picked up. Columns
+/// in the returned spans are counted in bytes.
+///
+/// # Arguments
+///
+/// * `source` - The source text the tokens came from
+/// * `tokens` - Tokens in source order, as produced by the lexer
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::directive::{directives, Namespace};
+/// let source = "// hm: no-format\nvar s = \"// hm: ignored\"; // hm-lint: disable unused\n";
+/// let (tokens, _) = hm_lexer::tokenize(source).unwrap();
+/// let found = directives(source, &tokens);
+/// assert_eq!(found.len(), 2);
+/// assert_eq!(found[0].name, "no-format");
+/// assert!(found[1].namespace == Namespace::Lint);
+/// assert_eq!((found[1].span.line_start, found[1].span.column_start), (2, 27));
+/// ```
+pub fn directives(source: &str, tokens: &[Token]) -> Vec<Directive> {
+    let bytes = source.as_bytes();
+    let mut out = Vec::new();
+    let mut cursor = Cursor {
+        offset: 0,
+        line: 1,
+        column: 1,
+    };
+
+    for token in tokens {
+        let gap = cursor.offset..token.span.start.min(bytes.len());
+        for comment in comment_ranges(bytes, gap) {
+            let start = cursor.advance_to(bytes, comment.start);
+            let end = cursor.advance_to(bytes, comment.end);
+            let span = Span {
+                start: comment.start,
+                end: comment.end,
+                line_start: start.0,
+                column_start: start.1,
+                line_end: end.0,
+                column_end: end.1,
+                char_offsets: None,
+            };
+            if let Some(directive) = Directive::parse(&source[comment], span) {
+                out.push(directive);
+            }
+        }
+        cursor = Cursor {
+            offset: token.span.end,
+            line: token.span.line_end,
+            column: token.span.column_end,
+        };
+    }
+    out
+}
+
+/// A byte offset with its line and byte column.
+struct Cursor {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Cursor {
+    /// Move forward to `offset` and return the line and column there.
+    fn advance_to(&mut self, bytes: &[u8], offset: usize) -> (usize, usize) {
+        for &byte in &bytes[self.offset..offset] {
+            if byte == b'\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.offset = offset;
+        (self.line, self.column)
+    }
+}
@@ -0,0 +1,59 @@
+//! Display columns for human-facing diagnostics.
+//!
+//! Span columns count bytes, characters, or UTF-16 units (see
+//! [`ColumnUnit`](crate::config::ColumnUnit)), none of which match what a
+//! reader sees when a line contains emoji or combining characters: `é` written
+//! as `e` + U+0301 is two characters but one visible glyph. The functions here
+//! count extended grapheme clusters instead, so carets under a diagnostic
+//! line up with the text above them.
+//!
+//! Requires the `graphemes` feature.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::token::span::Span;
+
+/// Compute the 1-based grapheme column of byte `offset` within its line.
+///
+/// Offsets past the end of `source` are clamped to its length, and offsets
+/// inside a multi-byte character are rounded down to the character's start.
+///
+/// # Arguments
+///
+/// * `source` - The full source text
+/// * `offset` - A byte offset into `source`, e.g. `span.start`
+///
+/// # Returns
+///
+/// The number of grapheme clusters between the start of the line and
+/// `offset`, plus one
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::display::grapheme_column;
+/// let source = "var s = \"e\u{301}👍🏽\"; @";
+/// let at = source.find('@').unwrap();
+/// // `e` + combining accent and the toned thumbs-up are one glyph each.
+/// assert_eq!(grapheme_column(source, at), 15);
+/// ```
+pub fn grapheme_column(source: &str, offset: usize) -> usize {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    source[line_start..offset].graphemes(true).count() + 1
+}
+
+/// Compute the grapheme columns of the start and end of `span`.
+///
+/// # Returns
+///
+/// `(column_start, column_end)` measured like [`grapheme_column`]
+pub fn grapheme_columns(source: &str, span: &Span) -> (usize, usize) {
+    (
+        grapheme_column(source, span.start),
+        grapheme_column(source, span.end),
+    )
+}
@@ -0,0 +1,95 @@
+//! Cheap heuristics for sizing token buffers ahead of lexing.
+//!
+//! Growing a `Vec<Token>` one reallocation at a time is measurable on large
+//! inputs. [`estimate_token_count`] guesses how many tokens a source will
+//! produce without running the lexer, so callers (including
+//! [`crate::tokenize`]) can reserve capacity up front.
+
+/// Average token width (in bytes, including surrounding trivia) assumed when
+/// the input is too small to sample meaningfully.
+const DEFAULT_AVERAGE_WIDTH: usize = 4;
+
+/// Number of evenly spaced windows sampled from the input.
+const SAMPLE_WINDOWS: usize = 4;
+
+/// Size of each sampled window in bytes.
+const SAMPLE_WINDOW_LEN: usize = 256;
+
+/// Estimate the number of tokens (including the trailing `Eof`) that lexing
+/// `source` will produce.
+///
+/// The estimate is `source.len() / average_width`, where the average token
+/// width is measured by counting token starts in a few evenly spaced sample
+/// windows. Inputs smaller than the sample budget are scanned in full. The
+/// result is a capacity hint, not an exact count: strings and comments
+/// containing many words are over-counted, and multi-character operators are
+/// counted once per byte.
+///
+/// # Arguments
+///
+/// * `source` - The raw source bytes that will be lexed
+///
+/// # Returns
+///
+/// An approximate token count, always at least 1 (for the `Eof` token)
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::estimate_token_count;
+/// let estimate = estimate_token_count(b"var x = 42;");
+/// assert!(estimate >= 5);
+/// assert_eq!(estimate_token_count(b""), 1);
+/// ```
+pub fn estimate_token_count(source: &[u8]) -> usize {
+    if source.is_empty() {
+        return 1;
+    }
+
+    let sample_budget = SAMPLE_WINDOWS * SAMPLE_WINDOW_LEN;
+    if source.len() <= sample_budget {
+        return count_token_starts(source) + 1;
+    }
+
+    // Sample evenly spaced windows and extrapolate from their density.
+    let stride = source.len() / SAMPLE_WINDOWS;
+    let mut sampled_bytes = 0;
+    let mut sampled_tokens = 0;
+    for i in 0..SAMPLE_WINDOWS {
+        let start = i * stride;
+        let end = (start + SAMPLE_WINDOW_LEN).min(source.len());
+        sampled_bytes += end - start;
+        sampled_tokens += count_token_starts(&source[start..end]);
+    }
+
+    let estimate = if sampled_tokens == 0 {
+        source.len() / DEFAULT_AVERAGE_WIDTH
+    } else {
+        source.len() * sampled_tokens / sampled_bytes
+    };
+
+    estimate + 1
+}
+
+/// Count the positions in `bytes` that look like the start of a token.
+///
+/// A run of identifier/number characters counts as one token; every other
+/// non-whitespace byte counts as its own token.
+fn count_token_starts(bytes: &[u8]) -> usize {
+    let mut count = 0;
+    let mut in_word = false;
+    for &b in bytes {
+        if b.is_ascii_alphanumeric() || b == b'_' {
+            if !in_word {
+                count += 1;
+                in_word = true;
+            }
+        } else {
+            in_word = false;
+            if !b.is_ascii_whitespace() {
+                count += 1;
+            }
+        }
+    }
+    count
+}
@@ -0,0 +1,536 @@
+//! Introspection of the lexical grammar supported by this build.
+//!
+//! The lists here are generated from the token enums and the lexer's
+//! byte-class table rather than maintained by hand, so tooling built on them
+//! stays in sync with the lexer automatically.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::lexer::byteclass::{self, ByteClass};
+use crate::token::delimiters::Delimiters;
+use crate::token::keywords::Keywords;
+use crate::token::operators::arithmetic::ArithmeticOps;
+use crate::token::operators::assignment::AssignmentOps;
+use crate::token::operators::bitwise::BitwiseOps;
+use crate::token::operators::logical::LogicalOps;
+use crate::token::operators::relational::RelationalOps;
+use crate::token::operators::SpecialOps;
+use crate::token::tokenkind::TokenKind;
+
+/// Every keyword spelling paired with the token kind it produces.
+pub fn keywords() -> Vec<(&'static str, TokenKind)> {
+    Keywords::ALL
+        .into_iter()
+        .map(|kw| (kw.as_str(), TokenKind::Keyword(kw)))
+        .collect()
+}
+
+/// Every fixed-spelling punctuation token (delimiters and operators) paired
+/// with the token kind it produces.
+pub fn punctuators() -> Vec<(&'static str, TokenKind)> {
+    let mut out = Vec::new();
+    for d in Delimiters::ALL {
+        out.push((d.as_str(), TokenKind::Delimiter(d)));
+    }
+    for op in ArithmeticOps::ALL {
+        out.push((op.as_str(), TokenKind::ArithmeticOperator(op)));
+    }
+    for op in RelationalOps::ALL {
+        out.push((op.as_str(), TokenKind::RelationalOperator(op)));
+    }
+    for op in LogicalOps::ALL {
+        out.push((op.as_str(), TokenKind::LogicalOperator(op)));
+    }
+    for op in AssignmentOps::ALL {
+        out.push((op.as_str(), TokenKind::AssignmentOperator(op)));
+    }
+    for op in BitwiseOps::ALL {
+        out.push((op.as_str(), TokenKind::BitwiseOperator(op)));
+    }
+    for op in SpecialOps::ALL {
+        out.push((op.as_str(), TokenKind::SpecialOperator(op)));
+    }
+    out
+}
+
+/// A form of literal accepted by the lexer.
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct LiteralForm {
+    /// Short name of the literal form (e.g. `"unsigned"`)
+    pub name: &'static str,
+    /// Regular expression matching the form's spelling
+    pub pattern: &'static str,
+    /// A representative example
+    pub example: &'static str,
+}
+
+/// Every literal form the lexer accepts, plus identifiers.
+pub const LITERAL_FORMS: &[LiteralForm] = &[
+    LiteralForm {
+        name: "identifier",
+        pattern: "[A-Za-z_][A-Za-z0-9_]*",
+        example: "my_var1",
+    },
+    LiteralForm {
+        name: "int",
+        pattern: "[0-9](_?[0-9])*",
+        example: "1_000_000",
+    },
+    LiteralForm {
+        name: "unsigned",
+        pattern: "[0-9](_?[0-9])*u",
+        example: "42u",
+    },
+    LiteralForm {
+        name: "binary",
+        pattern: "0b_?[01](_?[01])*u?",
+        example: "0b1010_0001",
+    },
+    LiteralForm {
+        name: "octal",
+        pattern: "0o_?[0-7](_?[0-7])*u?",
+        example: "0o755",
+    },
+    LiteralForm {
+        name: "float",
+        pattern: "[0-9](_?[0-9])*\\.[0-9](_?[0-9])*",
+        example: "3.14",
+    },
+    LiteralForm {
+        name: "typed",
+        pattern: "[0-9](_?[0-9])*([iu](8|16|32|64)|\\.[0-9](_?[0-9])*f(32|64))",
+        example: "100u32",
+    },
+    LiteralForm {
+        name: "string",
+        pattern: "\"([^\"\\\\]|\\\\.)*\"",
+        example: "\"hello\\n\"",
+    },
+    LiteralForm {
+        name: "bool",
+        pattern: "true|false",
+        example: "true",
+    },
+    LiteralForm {
+        name: "interpolated",
+        pattern: "\"([^\"\\\\]|\\\\.|\\$\\{[^}]*\\})*\"",
+        example: "\"hello ${name}\"",
+    },
+    LiteralForm {
+        name: "multiline",
+        pattern: "\"\"\"([^\\\\]|\\\\.)*?\"\"\"",
+        example: "\"\"\"a \"quoted\" word\"\"\"",
+    },
+    LiteralForm {
+        name: "char",
+        pattern: "'([^'\\\\]|\\\\.)'",
+        example: "'a'",
+    },
+    LiteralForm {
+        name: "byte",
+        pattern: "b'([ -&(-\\[\\]-~]|\\\\.)'",
+        example: "b'A'",
+    },
+];
+
+/// Suffixes accepted directly after an integer literal, such as the `u` in
+/// `42u` or the `i8` in `42i8`.
+///
+/// A bare `u` makes the literal unsigned; the others name its type, and the
+/// value must fit that type.
+///
+/// Any other identifier characters glued to a number are rejected with a
+/// [`LexError::InvalidNumberSuffix`](crate::lexerror::LexError::InvalidNumberSuffix)
+/// that lists the allowed suffixes.
+///
+/// # Example
+///
+/// ```
+/// let err = hm_lexer::tokenize("x = 10q;").unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "Invalid suffix 'q' on number at line 1, column 7: expected one of \
+///      'u', 'i8', 'i16', 'i32', 'i64', 'u8', 'u16', 'u32', 'u64'"
+/// );
+/// ```
+pub const INT_SUFFIXES: &[&str] = &["u", "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64"];
+
+/// Suffixes accepted directly after a float literal, such as the `f32` in
+/// `3.14f32`.
+pub const FLOAT_SUFFIXES: &[&str] = &["f32", "f64"];
+
+/// Escape sequences accepted inside string and character literals, paired
+/// with the character they decode to. `\"` is only valid in strings and `\'`
+/// only in character literals.
+pub const ESCAPES: &[(&str, char)] = &[
+    ("\\n", '\n'),
+    ("\\t", '\t'),
+    ("\\r", '\r'),
+    ("\\0", '\0'),
+    ("\\\\", '\\'),
+    ("\\$", '$'),
+    ("\\\"", '"'),
+    ("\\'", '\''),
+];
+
+/// Pattern of a `\u{...}` escape, whose one to six hex digits name the
+/// Unicode scalar value it decodes to, as in `\u{1F600}`.
+pub const UNICODE_ESCAPE: &str = "\\\\u\\{[0-9a-fA-F]{1,6}\\}";
+
+/// Characters commonly pasted from word processors and web pages in place
+/// of ASCII punctuation, with their names and the ASCII character meant.
+///
+/// Outside string and character literals, the lexer rejects these with a
+/// [`LexError::LookalikeCharacter`](crate::lexerror::LexError::LookalikeCharacter)
+/// suggesting the replacement.
+///
+/// # Example
+///
+/// ```
+/// let err = hm_lexer::tokenize("var s = \u{201C}hi\u{201D};").unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "Unexpected left double quotation mark '\u{201C}' at line 1, column 9, \
+///      did you mean '\"'?"
+/// );
+/// ```
+pub const LOOKALIKES: &[(char, &str, char)] = &[
+    ('\u{201C}', "left double quotation mark", '"'),
+    ('\u{201D}', "right double quotation mark", '"'),
+    ('\u{2018}', "left single quotation mark", '\''),
+    ('\u{2019}', "right single quotation mark", '\''),
+    ('\u{2013}', "en dash", '-'),
+    ('\u{2014}', "em dash", '-'),
+    ('\u{2212}', "minus sign", '-'),
+    ('\u{00A0}', "non-breaking space", ' '),
+    ('\u{202F}', "narrow non-breaking space", ' '),
+];
+
+/// Opening marker of a line comment.
+pub const LINE_COMMENT: &str = "//";
+
+/// Opening and closing markers of a block comment. Block comments nest, so
+/// each opening marker needs its own closing one.
+pub const BLOCK_COMMENT: (&str, &str) = ("/*", "*/");
+
+/// Group name of a keyword, mirroring the grouping in [`Keywords`].
+fn keyword_group(kw: &Keywords) -> &'static str {
+    match kw {
+        Keywords::Func
+        | Keywords::Return
+        | Keywords::If
+        | Keywords::Else
+        | Keywords::Elif
+        | Keywords::Loop
+        | Keywords::Switch
+        | Keywords::Case => "control",
+        Keywords::Var | Keywords::Const | Keywords::Final => "binding",
+        Keywords::Type(_) => "type",
+        Keywords::Import => "import",
+        Keywords::Null => "value",
+    }
+}
+
+/// Short human-readable name of a punctuator's family.
+fn family(kind: &TokenKind) -> &'static str {
+    match kind {
+        TokenKind::Delimiter(_) => "delimiter",
+        TokenKind::ArithmeticOperator(_) => "arithmetic",
+        TokenKind::RelationalOperator(_) => "relational",
+        TokenKind::LogicalOperator(_) => "logical",
+        TokenKind::AssignmentOperator(_) => "assignment",
+        TokenKind::BitwiseOperator(_) => "bitwise",
+        TokenKind::SpecialOperator(_) => "special",
+        _ => "other",
+    }
+}
+
+/// Render the lexer's dispatch structure as a Graphviz DOT graph.
+///
+/// The graph starts at a `start` node with one edge per byte class from the
+/// byte-class table. Punctuation is expanded into a trie: each node is a
+/// prefix, double circles mark prefixes that are complete tokens, and edges
+/// to longer spellings show the maximal-munch continuations. Spellings whose
+/// first byte is not routed to the operator or delimiter lexers are drawn in
+/// red, since the lexer can never produce them.
+///
+/// # Example
+///
+/// ```
+/// let dot = hm_lexer::grammar::dispatch_dot();
+/// assert!(dot.starts_with("digraph lexer {"));
+/// assert!(dot.contains("p_3C -> p_3C3D"));
+/// ```
+pub fn dispatch_dot() -> String {
+    let mut dot = String::new();
+    dot.push_str("digraph lexer {\n");
+    dot.push_str("    rankdir=LR;\n");
+    dot.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+    dot.push_str("    start [shape=point];\n");
+
+    // Byte classes that lead to dedicated lexing routines.
+    let routines: [(ByteClass, &str, &str); 4] = [
+        (ByteClass::IdentStart, "ident_start", "identifier / keyword"),
+        (
+            ByteClass::Digit,
+            "digit",
+            "int / unsigned / binary / octal / float literal",
+        ),
+        (ByteClass::Quote, "quote", "char / string literal"),
+        (ByteClass::Invalid, "invalid", "UnexpectedCharacter error"),
+    ];
+    for (class, name, target) in routines {
+        let label = if class == ByteClass::Invalid {
+            "(all other bytes)".to_string()
+        } else {
+            byte_ranges(class)
+        };
+        let _ = writeln!(dot, "    {name} [label=\"{}\\n{target}\"];", escape(&label));
+        let _ = writeln!(dot, "    start -> {name};");
+    }
+
+    // Punctuation trie keyed by prefix.
+    let mut trie: BTreeMap<&str, Option<&'static str>> = BTreeMap::new();
+    let spellings = punctuators();
+    for (spelling, kind) in &spellings {
+        for end in 1..spelling.len() {
+            trie.entry(&spelling[..end]).or_insert(None);
+        }
+        trie.insert(spelling, Some(family(kind)));
+    }
+
+    for (prefix, accepting) in &trie {
+        let id = node_id(prefix);
+        match accepting {
+            Some(family) => {
+                let _ = writeln!(
+                    dot,
+                    "    {id} [shape=doublecircle, label=\"{}\\n{family}\"];",
+                    escape(prefix)
+                );
+            }
+            None => {
+                let _ = writeln!(
+                    dot,
+                    "    {id} [shape=circle, label=\"{}\"];",
+                    escape(prefix)
+                );
+            }
+        }
+
+        let last = prefix.len() - 1;
+        let edge = escape(&prefix[last..]);
+        if last == 0 {
+            let first = prefix.as_bytes()[0];
+            let routed = matches!(
+                byteclass::class_of(first),
+                ByteClass::OperatorStart | ByteClass::Delimiter
+            );
+            let color = if routed { "" } else { ", color=red" };
+            let _ = writeln!(dot, "    start -> {id} [label=\"{edge}\"{color}];");
+        } else {
+            let parent = node_id(&prefix[..last]);
+            let _ = writeln!(dot, "    {parent} -> {id} [label=\"{edge}\"];");
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Describe the complete lexical grammar of this build as JSON.
+///
+/// The document lists every keyword (with its group), delimiter, operator
+/// (with its family), literal form, escape sequence (including the pattern
+/// of `\u{...}` escapes), and comment marker, so
+/// external tools such as tree-sitter grammars, documentation generators, and
+/// fuzzers can consume the grammar without scraping the lexer's source.
+///
+/// # Example
+///
+/// ```
+/// let json = hm_lexer::grammar::describe_json();
+/// assert!(json.contains("{ \"spelling\": \"elif\", \"group\": \"control\" }"));
+/// assert!(json.contains("{ \"spelling\": \"->\", \"family\": \"special\" }"));
+/// ```
+pub fn describe_json() -> String {
+    let mut json = String::new();
+    json.push_str("{\n");
+
+    let keywords = keywords();
+    json.push_str("  \"keywords\": [\n");
+    for (i, (spelling, kind)) in keywords.iter().enumerate() {
+        let group = match kind {
+            TokenKind::Keyword(kw) => keyword_group(kw),
+            _ => "other",
+        };
+        let _ = writeln!(
+            json,
+            "    {{ \"spelling\": {}, \"group\": {} }}{}",
+            json_string(spelling),
+            json_string(group),
+            separator(i, keywords.len())
+        );
+    }
+    json.push_str("  ],\n");
+
+    let (delimiters, operators): (Vec<_>, Vec<_>) = punctuators()
+        .into_iter()
+        .partition(|(_, kind)| matches!(kind, TokenKind::Delimiter(_)));
+    json.push_str("  \"delimiters\": [\n");
+    for (i, (spelling, _)) in delimiters.iter().enumerate() {
+        let _ = writeln!(
+            json,
+            "    {}{}",
+            json_string(spelling),
+            separator(i, delimiters.len())
+        );
+    }
+    json.push_str("  ],\n");
+    json.push_str("  \"operators\": [\n");
+    for (i, (spelling, kind)) in operators.iter().enumerate() {
+        let _ = writeln!(
+            json,
+            "    {{ \"spelling\": {}, \"family\": {} }}{}",
+            json_string(spelling),
+            json_string(family(kind)),
+            separator(i, operators.len())
+        );
+    }
+    json.push_str("  ],\n");
+
+    json.push_str("  \"literals\": [\n");
+    for (i, form) in LITERAL_FORMS.iter().enumerate() {
+        let _ = writeln!(
+            json,
+            "    {{ \"name\": {}, \"pattern\": {}, \"example\": {} }}{}",
+            json_string(form.name),
+            json_string(form.pattern),
+            json_string(form.example),
+            separator(i, LITERAL_FORMS.len())
+        );
+    }
+    json.push_str("  ],\n");
+
+    let suffixes = |list: &[&str]| {
+        list.iter()
+            .map(|s| json_string(s))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    json.push_str("  \"suffixes\": {\n");
+    let _ = writeln!(json, "    \"int\": [{}],", suffixes(INT_SUFFIXES));
+    let _ = writeln!(json, "    \"float\": [{}]", suffixes(FLOAT_SUFFIXES));
+    json.push_str("  },\n");
+
+    json.push_str("  \"escapes\": [\n");
+    for (i, (sequence, decoded)) in ESCAPES.iter().enumerate() {
+        let _ = writeln!(
+            json,
+            "    {{ \"sequence\": {}, \"decodes_to\": {} }}{}",
+            json_string(sequence),
+            json_string(&decoded.to_string()),
+            separator(i, ESCAPES.len())
+        );
+    }
+    json.push_str("  ],\n");
+    let _ = writeln!(
+        json,
+        "  \"unicode_escape\": {},",
+        json_string(UNICODE_ESCAPE)
+    );
+
+    let _ = writeln!(
+        json,
+        "  \"comments\": {{ \"line\": {}, \"block\": [{}, {}] }}",
+        json_string(LINE_COMMENT),
+        json_string(BLOCK_COMMENT.0),
+        json_string(BLOCK_COMMENT.1)
+    );
+    json.push_str("}\n");
+    json
+}
+
+/// A fingerprint of the grammar of this build: everything listed by
+/// [`describe_json`], every error code, and the kind ids of the binary
+/// [`codec`](crate::codec).
+///
+/// Adding, removing, or renumbering any of them changes the fingerprint, and
+/// with it [`lexer_version`](crate::lexer_version), so token streams
+/// serialized by an older grammar are rejected on load.
+pub fn fingerprint() -> u64 {
+    let mut text = describe_json();
+    text.push_str(&crate::codec::kind_table());
+    // FNV-1a, which unlike `std`'s hashers is fixed across Rust releases
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Comma separating JSON array element `i` of `len` from the next one.
+fn separator(i: usize, len: usize) -> &'static str {
+    if i + 1 < len {
+        ","
+    } else {
+        ""
+    }
+}
+
+/// Describe the bytes belonging to `class` as compact ranges, e.g. `0-9 A-Z`.
+fn byte_ranges(class: ByteClass) -> String {
+    let mut parts = Vec::new();
+    let mut b = 0usize;
+    while b < 256 {
+        if byteclass::class_of(b as u8) != class {
+            b += 1;
+            continue;
+        }
+        let start = b;
+        while b + 1 < 256 && byteclass::class_of((b + 1) as u8) == class {
+            b += 1;
+        }
+        if start == b {
+            parts.push(format!("{}", start as u8 as char));
+        } else {
+            parts.push(format!("{}-{}", start as u8 as char, b as u8 as char));
+        }
+        b += 1;
+    }
+    parts.join(" ")
+}
+
+/// DOT node identifier for a punctuation prefix, built from its hex bytes.
+fn node_id(prefix: &str) -> String {
+    let mut id = String::from("p_");
+    for b in prefix.bytes() {
+        let _ = write!(id, "{b:02X}");
+    }
+    id
+}
+
+/// Escape text for use inside a double-quoted DOT string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Render `text` as a JSON string literal.
+pub(crate) fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
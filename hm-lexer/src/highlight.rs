@@ -0,0 +1,170 @@
+//! Protocol-agnostic syntax highlighting.
+//!
+//! [`spans`] classifies every token and comment in a source into a small set
+//! of [`HighlightClass`]es. The result is plain byte ranges, so GUI editors,
+//! web frontends, and terminal pagers can map the classes onto their own
+//! theming without depending on LSP semantic tokens or TextMate scopes.
+
+use std::ops::Range;
+
+use crate::charstream::CharStream;
+use crate::config::LexerConfig;
+use crate::lexer::Lexer;
+use crate::token::keywords::Keywords;
+use crate::token::literals::Literals;
+use crate::token::tokenkind::TokenKind;
+
+/// Highlighting category of a source range.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[non_exhaustive]
+pub enum HighlightClass {
+    /// Reserved word such as `func` or `return`, or a boolean literal
+    Keyword,
+    /// Built-in type keyword such as `i32` or `string`
+    Type,
+    /// User-defined name
+    Identifier,
+    /// String or character literal
+    String,
+    /// Integer, unsigned, or floating point literal
+    Number,
+    /// Line or block comment, or a leading `#!` line
+    Comment,
+    /// Arithmetic, relational, logical, assignment, bitwise, or special operator
+    Operator,
+    /// Delimiter such as `(`, `;`, or `,`
+    Punctuation,
+    /// Input the lexer could not tokenize
+    Error,
+}
+
+impl HighlightClass {
+    /// Classify a token kind. Returns `None` for `Eof`.
+    pub fn of(kind: &TokenKind) -> Option<Self> {
+        let class = match kind {
+            TokenKind::Keyword(Keywords::Type(_)) => HighlightClass::Type,
+            TokenKind::Keyword(_) | TokenKind::Literal(Literals::BoolLiteral(_)) => {
+                HighlightClass::Keyword
+            }
+            TokenKind::Identifier(_) => HighlightClass::Identifier,
+            TokenKind::Literal(
+                Literals::StringLiteral(_)
+                | Literals::MultilineStringLiteral { .. }
+                | Literals::CharacterLiteral(_)
+                | Literals::ByteLiteral(_)
+                | Literals::InterpolatedString(_),
+            ) => HighlightClass::String,
+            TokenKind::Literal(_) => HighlightClass::Number,
+            TokenKind::Delimiter(_) => HighlightClass::Punctuation,
+            TokenKind::ArithmeticOperator(_)
+            | TokenKind::RelationalOperator(_)
+            | TokenKind::LogicalOperator(_)
+            | TokenKind::AssignmentOperator(_)
+            | TokenKind::BitwiseOperator(_)
+            | TokenKind::SpecialOperator(_) => HighlightClass::Operator,
+            TokenKind::Error { .. } => HighlightClass::Error,
+            TokenKind::Eof => return None,
+        };
+        Some(class)
+    }
+}
+
+/// Classify every token and comment in `source` for highlighting.
+///
+/// Lexing runs in error-recovery mode, so malformed input never aborts
+/// highlighting; it is reported as [`HighlightClass::Error`] ranges instead.
+/// Whitespace is not reported. Ranges are byte offsets into `source`, sorted
+/// and non-overlapping.
+///
+/// # Arguments
+///
+/// * `source` - The source text to highlight
+///
+/// # Returns
+///
+/// The classified byte ranges in source order
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::highlight::{spans, HighlightClass};
+/// let spans = spans("var x = 1; // one");
+/// assert!(spans[0] == (0..3, HighlightClass::Keyword));
+/// assert!(spans[3] == (8..9, HighlightClass::Number));
+/// assert!(spans.last() == Some(&(11..17, HighlightClass::Comment)));
+/// ```
+pub fn spans(source: &str) -> Vec<(Range<usize>, HighlightClass)> {
+    let bytes = source.as_bytes();
+    let stream = match CharStream::from_bytes(bytes) {
+        Ok(stream) => stream,
+        Err(_) if bytes.is_empty() => return Vec::new(),
+        Err(_) => return vec![(0..bytes.len(), HighlightClass::Error)],
+    };
+    let config = LexerConfig {
+        error_recovery: true,
+        ..LexerConfig::default()
+    };
+    let mut lexer = Lexer::with_config(stream, config);
+
+    let mut out = Vec::new();
+    let mut previous_end = 0;
+    loop {
+        let Ok(token) = lexer.next_token() else {
+            // Unreachable in recovery mode; highlight the rest as an error.
+            out.push((previous_end..bytes.len(), HighlightClass::Error));
+            break;
+        };
+        for comment in comment_ranges(bytes, previous_end..token.span.start) {
+            out.push((comment, HighlightClass::Comment));
+        }
+        previous_end = token.span.end;
+        match HighlightClass::of(&token.kind) {
+            Some(class) => out.push((token.span.start..token.span.end, class)),
+            None => break,
+        }
+    }
+    out
+}
+
+/// Find the comments inside `gap`, a run of trivia skipped by the lexer.
+pub(crate) fn comment_ranges(bytes: &[u8], gap: Range<usize>) -> Vec<Range<usize>> {
+    let mut out = Vec::new();
+    let mut i = gap.start;
+    while i < gap.end {
+        let rest = &bytes[i..gap.end];
+        let len = if rest.starts_with(b"//") || (i == 0 && rest.starts_with(b"#!")) {
+            rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len())
+        } else if rest.starts_with(b"/*") {
+            block_comment_len(rest)
+        } else {
+            i += 1;
+            continue;
+        };
+        out.push(i..i + len);
+        i += len;
+    }
+    out
+}
+
+/// Length of the block comment at the start of `rest`, counting nested
+/// comments, or all of `rest` if it is unterminated.
+fn block_comment_len(rest: &[u8]) -> usize {
+    let mut depth = 0;
+    let mut i = 0;
+    while i + 1 < rest.len() {
+        match &rest[i..i + 2] {
+            b"/*" => depth += 1,
+            b"*/" => depth -= 1,
+            _ => {
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+        if depth == 0 {
+            return i;
+        }
+    }
+    rest.len()
+}
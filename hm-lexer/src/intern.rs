@@ -0,0 +1,142 @@
+//! Interning of identifier names.
+//!
+//! A [`Lexer`](crate::lexer::Lexer) owns an [`Interner`], which stores each
+//! distinct name once and hands out a [`Symbol`] for it. Every identifier is
+//! interned as it is lexed, and its
+//! [`TokenKind::Identifier`](crate::token::tokenkind::TokenKind::Identifier)
+//! carries the symbol instead of a copy of the name. Symbols are `Copy` and
+//! compare and hash as integers, so a parser can key scopes and symbol tables
+//! by them instead of by string, and resolve them back to text through the
+//! interner returned by [`tokenize`](crate::tokenize) or
+//! [`Lexer::interner`](crate::lexer::Lexer::interner).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Identifies a name stored in an [`Interner`].
+///
+/// Two symbols from the same interner are equal exactly when their names
+/// are. Symbols are only meaningful for the interner that issued them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Position of the name in its interner, in first-seen order.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A set of names, each stored once and addressed by a [`Symbol`].
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::intern::Interner;
+/// let mut interner = Interner::new();
+/// let a = interner.intern("count");
+/// let b = interner.intern("total");
+///
+/// assert_eq!(interner.intern("count"), a);
+/// assert!(a != b);
+/// assert_eq!(interner.resolve(b), "total");
+/// assert!(interner.get("missing").is_none());
+/// assert_eq!(interner.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    /// Symbol of each stored name.
+    symbols: HashMap<Arc<str>, Symbol>,
+    /// Stored names, indexed by `Symbol`. Each shares its allocation with
+    /// its key in `symbols`.
+    names: Vec<Arc<str>>,
+}
+
+impl Interner {
+    /// Create an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The symbol for `name`, storing the name if it is new.
+    ///
+    /// Only the first occurrence of a name allocates.
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(name) {
+            return symbol;
+        }
+        let symbol = Symbol(u32::try_from(self.names.len()).expect("more than u32::MAX names"));
+        let name: Arc<str> = name.into();
+        self.names.push(Arc::clone(&name));
+        self.symbols.insert(name, symbol);
+        symbol
+    }
+
+    /// The symbol for `name`, if it has been interned.
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.symbols.get(name).copied()
+    }
+
+    /// The name `symbol` stands for.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `symbol` was issued by a different interner.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.names[symbol.index()]
+    }
+
+    /// Number of distinct names stored.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether no names are stored.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Iterate over the names with their symbols, in first-seen order.
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (Symbol(index as u32), &**name))
+    }
+}
+
+/// Two interners are equal when they hold the same names under the same
+/// symbols.
+impl PartialEq for Interner {
+    fn eq(&self, other: &Self) -> bool {
+        self.names == other.names
+    }
+}
+
+impl Eq for Interner {}
+
+/// Serialized as the list of names in symbol order, so deserializing gives
+/// back the same symbols (feature `serde`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for Interner {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.names.iter().map(|name| &**name))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Interner {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let names = Vec::<String>::deserialize(deserializer)?;
+        let mut interner = Interner::new();
+        for (index, name) in names.iter().enumerate() {
+            if interner.intern(name).index() != index {
+                return Err(serde::de::Error::custom(format!(
+                    "duplicate interned name {name:?}"
+                )));
+            }
+        }
+        Ok(interner)
+    }
+}
@@ -0,0 +1,194 @@
+//! The spellings the lexer treats as keywords.
+//!
+//! A [`KeywordTable`] starts out with the language's built-in keywords and
+//! can be extended or overridden through
+//! [`LexerConfig::keywords`](crate::config::LexerConfig::keywords), so
+//! embedders trying out language extensions can reserve new words, add
+//! alternative spellings for existing keywords, or mark words as
+//! contextual without forking the crate.
+
+use std::collections::HashMap;
+
+use crate::token::keywords::{Keywords, TypeKind};
+
+/// What a spelling in a [`KeywordTable`] lexes as.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub enum KeywordEntry {
+    /// A reserved word, lexed as `TokenKind::Keyword`.
+    Reserved(Keywords),
+    /// A contextual keyword, still lexed as `TokenKind::Identifier` so it
+    /// remains usable as a name. The marker is chosen by the embedder and
+    /// reported by [`Lexer::contextual_keyword`](crate::lexer::Lexer::contextual_keyword).
+    Contextual(u32),
+}
+
+/// Keyword spellings and what each one lexes as.
+///
+/// `KeywordTable::new()` (also the default) holds every built-in keyword;
+/// [`define`](Self::define) and [`remove`](Self::remove) adjust it. `true`
+/// and `false` are boolean literals rather than keywords, so they are not
+/// in the table, but defining either spelling takes precedence over the
+/// literal.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::charstream::CharStream;
+/// # use hm_lexer::keywordtable::{KeywordEntry, KeywordTable};
+/// # use hm_lexer::lexer::Lexer;
+/// # use hm_lexer::token::keywords::Keywords;
+/// # use hm_lexer::token::tokenkind::TokenKind;
+/// const ASYNC: u32 = 0;
+/// let table = KeywordTable::new()
+///     .define("fn", KeywordEntry::Reserved(Keywords::Func))
+///     .define("async", KeywordEntry::Contextual(ASYNC))
+///     .remove("loop");
+///
+/// let stream = CharStream::from_bytes(b"async fn loop").unwrap();
+/// let mut lexer = Lexer::builder().keywords(table).build(stream);
+///
+/// let first = lexer.next_token().unwrap();
+/// assert!(matches!(first.kind, TokenKind::Identifier(_)));
+/// assert_eq!(lexer.contextual_keyword(&first), Some(ASYNC));
+///
+/// let second = lexer.next_token().unwrap();
+/// assert_eq!(second.kind, TokenKind::Keyword(Keywords::Func));
+/// assert_eq!(second.lexeme, "fn");
+///
+/// let third = lexer.next_token().unwrap();
+/// assert!(matches!(third.kind, TokenKind::Identifier(_)));
+/// ```
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct KeywordTable {
+    /// Whether spellings not in `overrides` fall back to the built-ins.
+    builtins: bool,
+    /// Entries that differ from the built-ins; `None` removes a spelling.
+    overrides: HashMap<Box<str>, Option<KeywordEntry>>,
+}
+
+impl Default for KeywordTable {
+    fn default() -> Self {
+        Self {
+            builtins: true,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl KeywordTable {
+    /// Create a table holding the built-in keywords.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a table with no keywords at all, for languages that define
+    /// their own set from scratch.
+    pub fn empty() -> Self {
+        Self {
+            builtins: false,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Add a spelling, replacing any earlier or built-in entry for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `spelling` - The identifier-shaped source text to match
+    /// * `entry` - What the spelling lexes as
+    pub fn define(mut self, spelling: &str, entry: KeywordEntry) -> Self {
+        self.overrides.insert(spelling.into(), Some(entry));
+        self
+    }
+
+    /// Stop treating a spelling as a keyword, so it lexes as an identifier.
+    pub fn remove(mut self, spelling: &str) -> Self {
+        if self.builtins && Self::builtin(spelling).is_some() {
+            self.overrides.insert(spelling.into(), None);
+        } else {
+            self.overrides.remove(spelling);
+        }
+        self
+    }
+
+    /// Look up a spelling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::keywordtable::{KeywordEntry, KeywordTable};
+    /// # use hm_lexer::token::keywords::Keywords;
+    /// let table = KeywordTable::new();
+    /// assert!(table.get("if") == Some(KeywordEntry::Reserved(Keywords::If)));
+    /// assert!(table.get("myVar").is_none());
+    /// assert!(KeywordTable::empty().get("if").is_none());
+    /// ```
+    pub fn get(&self, spelling: &str) -> Option<KeywordEntry> {
+        if let Some(&entry) = self.overrides.get(spelling) {
+            return entry;
+        }
+        if self.builtins {
+            Self::builtin(spelling).map(KeywordEntry::Reserved)
+        } else {
+            None
+        }
+    }
+
+    /// Look up a spelling among the built-in keywords.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::keywordtable::KeywordTable;
+    /// # use hm_lexer::token::keywords::{Keywords, TypeKind};
+    /// assert_eq!(KeywordTable::builtin("func"), Some(Keywords::Func));
+    /// assert_eq!(KeywordTable::builtin("i32"), Some(Keywords::Type(TypeKind::Int32)));
+    /// assert_eq!(KeywordTable::builtin("true"), None);
+    /// ```
+    pub fn builtin(spelling: &str) -> Option<Keywords> {
+        match spelling {
+            // Control Flow
+            "func" => Some(Keywords::Func),
+            "return" => Some(Keywords::Return),
+            "if" => Some(Keywords::If),
+            "else" => Some(Keywords::Else),
+            "elif" => Some(Keywords::Elif),
+            "loop" => Some(Keywords::Loop),
+            "switch" => Some(Keywords::Switch),
+            "case" => Some(Keywords::Case),
+
+            // Variable/Binding
+            "var" => Some(Keywords::Var),
+            "const" => Some(Keywords::Const),
+            "final" => Some(Keywords::Final),
+
+            // Integer Types
+            "i8" => Some(Keywords::Type(TypeKind::Int8)),
+            "i16" => Some(Keywords::Type(TypeKind::Int16)),
+            "i32" => Some(Keywords::Type(TypeKind::Int32)),
+            "i64" => Some(Keywords::Type(TypeKind::Int64)),
+            "u8" => Some(Keywords::Type(TypeKind::Unsigned8)),
+            "u16" => Some(Keywords::Type(TypeKind::Unsigned16)),
+            "u32" => Some(Keywords::Type(TypeKind::Unsigned32)),
+            "u64" => Some(Keywords::Type(TypeKind::Unsigned64)),
+
+            // Floating Point Types
+            "f32" => Some(Keywords::Type(TypeKind::Float32)),
+            "f64" => Some(Keywords::Type(TypeKind::Float64)),
+
+            // Other Types
+            "string" => Some(Keywords::Type(TypeKind::String)),
+            "char" => Some(Keywords::Type(TypeKind::Char)),
+            "struct" => Some(Keywords::Type(TypeKind::Struct)),
+            "bool" => Some(Keywords::Type(TypeKind::Bool)),
+
+            "import" => Some(Keywords::Import),
+
+            "null" => Some(Keywords::Null),
+
+            _ => None,
+        }
+    }
+}
@@ -3,16 +3,38 @@
 //! The [`Lexer`] consumes characters from a [`CharStream`] and produces [`Token`]s.
 //! It handles keywords, identifiers, literals (strings, characters, numbers), and operators.
 
+pub(crate) mod byteclass;
 mod delimiters;
+mod edition;
+mod interpolation;
 mod macros;
+mod multiline;
 mod operators;
 mod parsing;
+mod radix;
+mod recovery;
 mod token_builder;
 mod trivia;
+mod unicode_escape;
 
-use crate::charstream::CharStream;
-use crate::lexerror::LexError;
-use crate::token::{span::Span, tokenkind::TokenKind, Token};
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+
+use self::byteclass::ByteClass;
+use self::interpolation::Mode;
+use crate::charstream::{CharStream, Checkpoint};
+use crate::config::{Edition, LexerBuilder, LexerConfig};
+use crate::deprecation::DeprecationWarning;
+use crate::estimate_token_count;
+use crate::grammar::LOOKALIKES;
+use crate::intern::Interner;
+use crate::keywordtable::KeywordEntry;
+use crate::lexerror::{LexError, LexResult};
+use crate::lineindex::{self, LineIndex};
+use crate::token::{owned_lexeme, span::Span, tokenkind::TokenKind, Lexeme, Token};
+use crate::tokensource::TokenSource;
 
 
 /// The main lexer that converts a byte stream into a sequence of tokens.
@@ -27,11 +49,60 @@ use crate::token::{span::Span, tokenkind::TokenKind, Token};
 /// - **Keyword recognition**: Automatically identifies language keywords
 /// - **Literal parsing**: Handles strings, characters, and numeric literals
 /// - **Error reporting**: Provides detailed error messages with line/column info
-/// - **Trivia skipping**: Automatically skips whitespace and comments
+/// - **Trivia skipping**: Automatically skips whitespace and comments, or
+///   keeps them with [`next_token_with_trivia`](Self::next_token_with_trivia)
 /// - **Position tracking**: Maintains accurate source location metadata
+/// - **Error recovery**: Optionally resynchronizes after errors (see [`LexerConfig`])
 pub struct Lexer {
     /// The underlying byte stream being tokenized.
     stream: CharStream,
+
+    /// Options controlling lexer behavior.
+    config: LexerConfig,
+
+    /// Deprecated spellings encountered so far, in source order.
+    warnings: Vec<DeprecationWarning>,
+
+    /// Errors recovered from so far in recovery mode, in source order.
+    errors: Vec<LexError>,
+
+    /// Tokens lexed ahead by [`TokenSource::lookahead`] and not yet
+    /// returned, each with the state the lexer was in just before it.
+    pending: VecDeque<(LexerState, Token)>,
+
+    /// Modes entered and not yet left, innermost last; empty while lexing
+    /// top-level tokens.
+    modes: Vec<Mode>,
+
+    /// Whether iteration has yielded `Eof` or an error, after which the
+    /// [`Iterator`] implementation yields nothing more.
+    finished: bool,
+
+    /// Names of the identifiers lexed so far.
+    interner: Interner,
+
+    /// Where each line of the input starts, computed by the first error
+    /// located under [`LexerConfig::offsets_only`] and reused for every
+    /// later one.
+    line_starts: OnceCell<Vec<usize>>,
+}
+
+/// A saved lexer position, returned by [`Lexer::save`] (or
+/// [`Lexer::checkpoint`]) and accepted by [`Lexer::restore`] (or
+/// [`Lexer::rewind`]).
+///
+/// Saving is O(1) and copies no tokens: a state records where the next
+/// token starts, and tokens buffered by lookahead after that point are
+/// lexed again on demand after a restore.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct LexerState {
+    /// Where the next token starts in the stream
+    checkpoint: Checkpoint,
+    /// Number of deprecation warnings recorded before `checkpoint`
+    warnings: usize,
+    /// Number of recovered errors recorded before `checkpoint`
+    errors: usize,
 }
 
 impl Lexer {
@@ -45,7 +116,209 @@ impl Lexer {
     ///
     /// A new [`Lexer`] instance ready to tokenize the input
     pub fn new(stream: CharStream) -> Self {
-        Self { stream }
+        Self::with_config(stream, LexerConfig::default())
+    }
+
+    /// Create a new lexer with custom configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - A [`CharStream`] positioned at the start of the input
+    /// * `config` - The [`LexerConfig`] controlling lexer behavior
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::config::{LexerConfig, SyncStrategy};
+    /// # use hm_lexer::lexer::Lexer;
+    /// # use hm_lexer::token::tokenkind::TokenKind;
+    /// let config = LexerConfig {
+    ///     error_recovery: true,
+    ///     sync_strategy: SyncStrategy::Whitespace,
+    ///     ..LexerConfig::default()
+    /// };
+    /// let mut lexer = Lexer::with_config(CharStream::from_bytes(b"@#$ x").unwrap(), config);
+    /// let garbage = lexer.next_token().unwrap();
+    /// assert!(matches!(garbage.kind, TokenKind::Error { .. }));
+    /// assert_eq!(garbage.lexeme, "@#$");
+    /// assert_eq!(lexer.next_token().unwrap().lexeme, "x");
+    /// ```
+    pub fn with_config(mut stream: CharStream, config: LexerConfig) -> Self {
+        stream.set_column_unit(config.column_unit);
+        stream.set_line_endings(config.line_endings);
+        stream.set_tab_width(config.tab_width);
+        stream.set_char_tracking(config.char_offsets);
+        stream.set_line_tracking(!config.offsets_only);
+        Self {
+            stream,
+            config,
+            warnings: Vec::new(),
+            errors: Vec::new(),
+            pending: VecDeque::new(),
+            modes: Vec::new(),
+            finished: false,
+            interner: Interner::new(),
+            line_starts: OnceCell::new(),
+        }
+    }
+
+    /// Start building a lexer with non-default options.
+    ///
+    /// See [`LexerBuilder`]; [`Lexer::new`] remains the way to lex with
+    /// every option at its default.
+    pub fn builder() -> LexerBuilder {
+        LexerBuilder::default()
+    }
+
+    /// The configuration this lexer was created with.
+    pub fn config(&self) -> &LexerConfig {
+        &self.config
+    }
+
+    /// Deprecation warnings recorded so far.
+    ///
+    /// Warnings are only produced when
+    /// [`LexerConfig::deprecations`] is non-empty.
+    pub fn warnings(&self) -> &[DeprecationWarning] {
+        &self.warnings
+    }
+
+    /// Remove and return the deprecation warnings recorded so far.
+    pub fn take_warnings(&mut self) -> Vec<DeprecationWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// The names of every identifier lexed so far, for resolving the
+    /// [`Symbol`](crate::intern::Symbol) of a [`TokenKind::Identifier`].
+    ///
+    /// Names stay interned across [`restore`](Self::restore), so symbols
+    /// handed out before a rewind remain valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexer::Lexer;
+    /// # use hm_lexer::token::tokenkind::TokenKind;
+    /// let mut lexer = Lexer::new(CharStream::from_bytes(b"x = x + y").unwrap());
+    /// let tokens: Vec<_> = lexer.by_ref().map(Result::unwrap).collect();
+    ///
+    /// let TokenKind::Identifier(x) = tokens[0].kind else {
+    ///     panic!("expected an identifier");
+    /// };
+    /// assert_eq!(tokens[2].kind, TokenKind::Identifier(x));
+    /// assert!(tokens[4].kind != TokenKind::Identifier(x));
+    /// assert_eq!(lexer.interner().resolve(x), "x");
+    /// assert_eq!(lexer.interner().len(), 2);
+    /// ```
+    pub fn interner(&self) -> &Interner {
+        &self.interner
+    }
+
+    /// Consume the lexer and keep its interned names, e.g. to hand them
+    /// to a parser once lexing is done.
+    pub fn into_interner(self) -> Interner {
+        self.interner
+    }
+
+    /// The marker of a contextual keyword token.
+    ///
+    /// Contextual keywords defined in [`LexerConfig::keywords`] lex as
+    /// identifiers; this reports the marker they were defined with. `token`
+    /// must have been lexed by this lexer, whose interner holds its name.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(marker)` if `token` is an identifier whose spelling is a
+    ///   [`KeywordEntry::Contextual`] entry
+    /// - `None` for any other token
+    pub fn contextual_keyword(&self, token: &Token) -> Option<u32> {
+        match &token.kind {
+            TokenKind::Identifier(symbol) => {
+                match self.config.keywords.get(self.interner.resolve(*symbol)) {
+                    Some(KeywordEntry::Contextual(marker)) => Some(marker),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Errors recovered from so far, one per `TokenKind::Error` token.
+    ///
+    /// Errors are only recorded when [`LexerConfig::error_recovery`] is
+    /// enabled; otherwise they are returned by [`next_token`](Self::next_token).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::config::LexerConfig;
+    /// # use hm_lexer::lexer::Lexer;
+    /// # use hm_lexer::lexerror::LexError;
+    /// let config = LexerConfig {
+    ///     error_recovery: true,
+    ///     ..LexerConfig::default()
+    /// };
+    /// let mut lexer = Lexer::with_config(CharStream::from_bytes(b"x @ y").unwrap(), config);
+    /// while !lexer.next_token().unwrap().is_eof() {}
+    /// let [LexError::UnexpectedCharacter { ch, span, .. }] = lexer.errors() else {
+    ///     panic!("expected one unexpected character");
+    /// };
+    /// assert_eq!((*ch, span.column_start), ('@', 3));
+    /// ```
+    pub fn errors(&self) -> &[LexError] {
+        &self.errors
+    }
+
+    /// Remove and return the errors recovered from so far.
+    pub fn take_errors(&mut self) -> Vec<LexError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Lex the rest of the input, recovering after every error, and return
+    /// the tokens together with all the errors found and the interned names.
+    ///
+    /// Recovery is enabled whatever the configuration says, so each error
+    /// also appears as a `TokenKind::Error` token in the returned vector,
+    /// which always ends with the `Eof` token. Errors recovered from before
+    /// this call are included.
+    ///
+    /// # Returns
+    ///
+    /// A `(tokens, errors, interner)` triple; `errors` is empty if the input
+    /// is valid, and `interner` resolves the tokens' identifier symbols
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexer::Lexer;
+    /// let source = "var a = @;\nvar b = \"open\nvar c = 'xy';\n";
+    /// let lexer = Lexer::new(CharStream::from_bytes(source.as_bytes()).unwrap());
+    /// let (tokens, errors, _) = lexer.tokenize_all();
+    /// let lines: Vec<_> = errors.iter().map(|err| err.to_string()).collect();
+    /// assert_eq!(lines.len(), 3);
+    /// assert!(lines[0].starts_with("Unexpected character '@' at line 1"));
+    /// assert!(lines[1].starts_with("Unterminated string literal at line 2"));
+    /// assert!(lines[2].starts_with("Invalid character literal at line 3"));
+    /// assert!(tokens.last().unwrap().is_eof());
+    /// ```
+    pub fn tokenize_all(mut self) -> (Vec<Token>, Vec<LexError>, Interner) {
+        self.config.error_recovery = true;
+        let mut tokens = Vec::with_capacity(estimate_token_count(self.stream.as_bytes()));
+        loop {
+            let token = match self.next_token() {
+                Ok(token) => token,
+                Err(_) => unreachable!("recovery mode turns every error into a token"),
+            };
+            let is_eof = token.is_eof();
+            tokens.push(token);
+            if is_eof {
+                return (tokens, self.errors, self.interner);
+            }
+        }
     }
 
     /// Extract the next token from the input stream.
@@ -60,6 +333,9 @@ impl Lexer {
     /// - `Ok(Token)` containing the next token and its metadata
     /// - `Err(LexError)` if an invalid character or malformed literal is encountered
     ///
+    /// In recovery mode errors are never returned; instead the malformed
+    /// region is skipped and reported as a `TokenKind::Error` token.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -72,15 +348,152 @@ impl Lexer {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn next_token(&mut self) -> Result<Token, LexError> {
+    pub fn next_token(&mut self) -> LexResult<Token> {
+        match self.pending.pop_front() {
+            Some((_, token)) => Ok(token),
+            None => self.lex_token(),
+        }
+    }
+
+    /// The token [`next_token`](Self::next_token) will return, without
+    /// consuming it.
+    ///
+    /// Shorthand for [`TokenSource::current`] that needs no trait import.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexer::Lexer;
+    /// let mut lexer = Lexer::new(CharStream::from_bytes(b"x = 1").unwrap());
+    /// assert_eq!(lexer.peek_token().unwrap().lexeme, "x");
+    /// assert_eq!(lexer.peek_nth(2).unwrap().span.start, 4);
+    /// assert_eq!(lexer.next_token().unwrap().lexeme, "x");
+    /// assert_eq!(lexer.peek_token().unwrap().lexeme, "=");
+    /// ```
+    pub fn peek_token(&mut self) -> LexResult<&Token> {
+        self.lookahead(0)
+    }
+
+    /// The token `n` positions ahead, without consuming anything;
+    /// `peek_nth(0)` is [`peek_token`](Self::peek_token).
+    ///
+    /// Shorthand for [`TokenSource::lookahead`] that needs no trait import.
+    /// Tokens lexed to reach position `n` are buffered, spans intact, and
+    /// returned by later calls to [`next_token`](Self::next_token).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&Token)`, which is `Eof` for positions past the end of input
+    /// - `Err(LexError)` if lexing up to that position fails
+    pub fn peek_nth(&mut self, n: usize) -> LexResult<&Token> {
+        self.lookahead(n)
+    }
+
+    /// Consume and return the next token; the same as
+    /// [`next_token`](Self::next_token), for parsers written in terms of
+    /// [`peek_token`](Self::peek_token) and `bump`.
+    pub fn bump(&mut self) -> LexResult<Token> {
+        self.next_token()
+    }
+
+    /// Capture the current position, including any tokens already buffered
+    /// by lookahead, so that [`restore`](Self::restore) can return to it.
+    ///
+    /// Backtracking parsers save before trying one alternative and restore
+    /// if it fails, then try the next.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexer::Lexer;
+    /// # use hm_lexer::tokensource::TokenSource;
+    /// let mut lexer = Lexer::new(CharStream::from_bytes(b"a < b > c").unwrap());
+    /// lexer.next_token().unwrap();
+    /// lexer.lookahead(2).unwrap();
+    ///
+    /// let state = lexer.save();
+    /// let speculated: Vec<_> = (0..3).map(|_| lexer.next_token().unwrap().lexeme).collect();
+    /// assert_eq!(speculated, ["<", "b", ">"]);
+    ///
+    /// lexer.restore(state);
+    /// assert_eq!(lexer.next_token().unwrap().lexeme, "<");
+    /// ```
+    pub fn save(&self) -> LexerState {
+        match self.pending.front() {
+            Some((state, _)) => *state,
+            None => self.state(),
+        }
+    }
+
+    /// Return to a position captured by [`save`](Self::save) on this lexer.
+    ///
+    /// Lookahead buffered since then is discarded and deprecation warnings
+    /// and recovered errors recorded after the saved position are dropped,
+    /// so re-lexing the same text does not report them twice.
+    pub fn restore(&mut self, state: LexerState) {
+        self.pending.clear();
+        self.finished = false;
+        self.stream.rewind(state.checkpoint);
+        self.warnings.truncate(state.warnings);
+        self.errors.truncate(state.errors);
+    }
+
+    /// Capture the current position for speculative lexing; the same as
+    /// [`save`](Self::save), named to match [`CharStream::checkpoint`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexer::Lexer;
+    /// // Is `a<b>(c)` a generic call or two comparisons? Try generics first.
+    /// let mut lexer = Lexer::new(CharStream::from_bytes(b"a<b>(c)").unwrap());
+    /// lexer.next_token().unwrap();
+    /// let checkpoint = lexer.checkpoint();
+    /// let generic: Vec<_> = (0..3).map(|_| lexer.next_token().unwrap().lexeme).collect();
+    /// assert_eq!(generic, ["<", "b", ">"]);
+    ///
+    /// lexer.rewind(checkpoint);
+    /// let next = lexer.next_token().unwrap();
+    /// assert_eq!((next.lexeme.as_ref(), next.span.column_start), ("<", 2));
+    /// ```
+    pub fn checkpoint(&self) -> LexerState {
+        self.save()
+    }
+
+    /// Backtrack to a position captured by
+    /// [`checkpoint`](Self::checkpoint); the same as
+    /// [`restore`](Self::restore).
+    pub fn rewind(&mut self, checkpoint: LexerState) {
+        self.restore(checkpoint);
+    }
+
+    /// The state the lexer is in right now, ignoring the lookahead buffer.
+    fn state(&self) -> LexerState {
+        LexerState {
+            checkpoint: self.stream.checkpoint(),
+            warnings: self.warnings.len(),
+            errors: self.errors.len(),
+        }
+    }
+
+    /// Lex the next token directly from the stream, bypassing the
+    /// lookahead buffer.
+    fn lex_token(&mut self) -> LexResult<Token> {
         // Skip trivia (whitespace and comments)
-        self.skip_trivia();
+        let trivia = self.skip_trivia();
 
-        // Capture the start position for the token's span
+        // Capture the start position for the token's span; after an
+        // unterminated block comment this is the comment's `/*`
         let (start_idx, start_line, start_col) = self.stream.current_position();
+        let start_char = self.stream.char_index();
 
-        // Check for EOF
-        if self.stream.is_eof() {
+        if trivia.is_err() {
+            // The comment runs to the end of the input
+            self.stream.skip_while(|_| true);
+        } else if self.stream.is_eof() {
             let span = Span {
                 start: start_idx,
                 end: start_idx,
@@ -88,66 +501,264 @@ impl Lexer {
                 column_start: start_col,
                 line_end: start_line,
                 column_end: start_col,
+                char_offsets: start_char.map(|c| (c, c)),
             };
             return Ok(Token {
                 kind: TokenKind::Eof,
                 span,
-                lexeme: String::new(),
+                lexeme: Lexeme::default(),
+            });
+        }
+
+        let result = match trivia.and_then(|()| self.dispatch()) {
+            Ok(token) => {
+                if !self.config.deprecations.is_empty() {
+                    self.check_deprecated(&token);
+                }
+                Ok(token)
+            }
+            Err(err) if self.config.error_recovery => {
+                let err = self.locate_error(err);
+                Ok(self.recover(err, (start_idx, start_line, start_col)))
+            }
+            Err(err) => Err(self.locate_error(err)),
+        };
+
+        // Every token ends where the stream stopped, so character offsets
+        // are filled in here rather than by each sub-lexer.
+        match (result, start_char) {
+            (Ok(mut token), Some(start_char)) => {
+                token.span.char_offsets = self.stream.char_index().map(|end| (start_char, end));
+                Ok(token)
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// The span from `start`, a position captured earlier, to the cursor.
+    pub(super) fn span_from(
+        &self,
+        (start, line_start, column_start): (usize, usize, usize),
+    ) -> Span {
+        let (end, line_end, column_end) = self.stream.current_position();
+        Span {
+            start,
+            end,
+            line_start,
+            column_start,
+            line_end,
+            column_end,
+            char_offsets: None,
+        }
+    }
+
+    /// The span from `start` to `len` bytes past the cursor, which is left
+    /// where it is.
+    pub(super) fn span_ahead(&mut self, start: (usize, usize, usize), len: usize) -> Span {
+        let cursor = self.stream.current_position();
+        self.stream.advance_n(len);
+        let span = self.span_from(start);
+        self.stream.back_up(cursor);
+        span
+    }
+
+    /// The lexeme for bytes `start..end` of the input, or an empty one when
+    /// [`LexerConfig::lazy_lexemes`] is set, in which case nothing is
+    /// copied.
+    pub(super) fn source_lexeme(&self, start: usize, end: usize) -> Lexeme {
+        if self.config.lazy_lexemes {
+            Lexeme::default()
+        } else {
+            owned_lexeme(&String::from_utf8_lossy(self.stream.slice(start, end)))
+        }
+    }
+
+    /// The source text of a token lexed from this lexer's input, whether
+    /// or not its lexeme was kept.
+    pub(super) fn token_text(&self, token: &Token) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.stream.slice(token.span.start, token.span.end))
+    }
+
+    /// Whether the byte just before offset `start` is a `-`, as for the
+    /// literal of `-128i8`.
+    pub(super) fn follows_minus(&self, start: usize) -> bool {
+        start > 0 && self.stream.slice(start - 1, start) == b"-"
+    }
+
+    /// The span from `start` through the UTF-8 character at the cursor,
+    /// judged by its leading byte, which is left unconsumed.
+    pub(super) fn span_through_char(&mut self, start: (usize, usize, usize)) -> Span {
+        let width = self
+            .stream
+            .peek()
+            .map_or(0, |b| (b.leading_ones() as usize).max(1));
+        self.span_ahead(start, width)
+    }
+
+    /// The error for a character at the cursor that cannot start a token,
+    /// which is left unconsumed. Known lookalikes of ASCII punctuation get a
+    /// [`LexError::LookalikeCharacter`] naming the character meant.
+    fn unexpected_character(&mut self) -> LexError {
+        let span = self.span_through_char(self.stream.current_position());
+        let ch = match self.stream.peek_char() {
+            Ok(Some((ch, _))) => ch,
+            _ => self.stream.peek().unwrap_or_default() as char,
+        };
+        match LOOKALIKES.iter().find(|(lookalike, _, _)| *lookalike == ch) {
+            Some(&(ch, name, suggestion)) => LexError::LookalikeCharacter {
+                ch,
+                name,
+                suggestion,
+                span,
+                context: None,
+            },
+            None => LexError::UnexpectedCharacter {
+                ch,
+                span,
+                context: None,
+            },
+        }
+    }
+
+    /// Fill in the location of `err` when lexing with
+    /// [`LexerConfig::offsets_only`], which leaves it unset.
+    fn locate_error(&self, err: LexError) -> LexError {
+        if !self.config.offsets_only {
+            return err;
+        }
+        let source = self.stream.as_bytes();
+        let line_starts = self
+            .line_starts
+            .get_or_init(|| lineindex::line_starts(source));
+        LineIndex::with_line_starts(source, line_starts.into())
+            .with_column_unit(self.config.column_unit)
+            .with_line_endings(self.config.line_endings)
+            .with_tab_width(self.config.tab_width)
+            .resolve_error(err)
+    }
+
+    /// Record a warning if `token` is a keyword, identifier, or punctuator
+    /// whose spelling is listed in the configured deprecation table.
+    fn check_deprecated(&mut self, token: &Token) {
+        if matches!(
+            token.kind,
+            TokenKind::Literal(_) | TokenKind::Error { .. } | TokenKind::Eof
+        ) {
+            return;
+        }
+        let text = self.token_text(token);
+        if let Some(deprecation) = self.config.deprecations.get(&text) {
+            self.warnings.push(DeprecationWarning {
+                spelling: text.into_owned(),
+                replacement: deprecation.replacement.clone(),
+                message: deprecation.message.clone(),
+                span: token.span,
             });
         }
+    }
 
+    /// Lex a single token starting at the current (non-trivia, non-EOF) byte.
+    fn dispatch(&mut self) -> LexResult<Token> {
         // Peek at the next character and dispatch
         let byte = self.stream.peek().unwrap();
 
-        let token = match byte {
-            // Character literals
-            b'\'' => self.lex_character_literal()?,
-
-            // String literals
-            b'"' => self.lex_string_literal()?,
+        let token = match byteclass::class_of(byte) {
+            // Character and string literals
+            ByteClass::Quote if byte == b'\'' => self.lex_character_literal()?,
+            ByteClass::Quote if self.at_triple_quote() => self.lex_multiline_string_literal()?,
+            ByteClass::Quote if self.config.concat_adjacent_strings => {
+                let first = self.lex_string_literal()?;
+                self.concat_adjacent_strings(first)?
+            }
+            ByteClass::Quote => self.lex_string_literal()?,
 
             // Identifiers and keywords
-            b'a'..=b'z' | b'A'..=b'Z' | b'_' => self.lex_identifier_or_keyword()?,
+            ByteClass::IdentStart if byte == b'b' && self.stream.peek_n(1) == Some(b'\'') => {
+                self.lex_byte_literal()?
+            }
+            ByteClass::IdentStart => self.lex_identifier_or_keyword()?,
 
             // Numeric literals
-            b'0'..=b'9' => self.lex_number()?,
-
-            // Delimiters (simple punctuation)
-            b'(' | b')' | b'{' | b'}' | b'[' | b']' | b';' | b',' | b'.' | b'?' => {
-                delimiters::lex_delimiter(&mut self.stream, byte)
-            }
+            ByteClass::Digit => self.lex_number()?,
 
-            // Colon (can be : or ::)
-            b':' => delimiters::lex_colon(&mut self.stream),
+            // Delimiters (simple punctuation, `:` and `::`)
+            ByteClass::Delimiter => delimiters::lex_delimiter(&mut self.stream, byte),
 
             // Operators (all delegated to operators module)
-            b'=' | b'+' | b'-' | b'*' | b'/' | b'%' | b'<' | b'>' | b'!' | b'&' | b'|'
-            | b'^' | b'~' => operators::lex_operator(&mut self.stream, byte)?,
+            ByteClass::OperatorStart => operators::lex_operator(&mut self.stream, byte)?,
 
             // Unexpected character
-            _ => {
-                let ch = byte as char;
-                return Err(LexError::UnexpectedCharacter {
-                    ch,
-                    line: start_line,
-                    column: start_col,
-                });
-            }
+            ByteClass::Invalid => return Err(self.unexpected_character()),
         };
 
+        if self.config.edition < Edition::LATEST {
+            self.check_edition(&token)?;
+        }
+
         Ok(token)
     }
 }
 
+/// Iterating a lexer yields every token up to and including `Eof`, then
+/// `None`. Without error recovery, iteration also ends after the first
+/// error, as [`tokenize`](crate::tokenize) does.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::charstream::CharStream;
+/// # use hm_lexer::lexer::Lexer;
+/// let lexer = Lexer::new(CharStream::from_bytes(b"x = 1;").unwrap());
+/// let lexemes: Vec<_> = lexer.map(|token| token.unwrap().lexeme).collect();
+/// assert_eq!(lexemes, ["x", "=", "1", ";", ""]);
+///
+/// let mut lexer = Lexer::new(CharStream::from_bytes(b"x @ y").unwrap());
+/// assert!(lexer.next().unwrap().is_ok());
+/// assert!(lexer.next().unwrap().is_err());
+/// assert!(lexer.next().is_none());
+/// ```
 impl Iterator for Lexer {
-    type Item = Result<Token, LexError>;
+    type Item = LexResult<Token>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let t = self.next_token();
-        match t {
-            Ok(token) if token.is_eof() => None,
-            _ => Some(t),
+        if self.finished {
+            return None;
+        }
+        let result = self.next_token();
+        self.finished = match &result {
+            Ok(token) => token.is_eof(),
+            Err(_) => true,
+        };
+        Some(result)
+    }
+
+    /// Every token but `Eof` consumes at least one byte, so at most one
+    /// item per remaining byte, plus `Eof`, is left.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            return (0, Some(0));
+        }
+        let remaining = self.stream.as_bytes().len() - self.stream.index();
+        (1, Some(self.pending.len() + remaining + 1))
+    }
+}
+
+impl FusedIterator for Lexer {}
+
+impl TokenSource for Lexer {
+    /// Lexes ahead as far as needed and buffers the tokens, which
+    /// [`Lexer::next_token`] returns before lexing further.
+    fn lookahead(&mut self, n: usize) -> LexResult<&Token> {
+        while self.pending.len() <= n {
+            let state = self.state();
+            let token = self.lex_token()?;
+            self.pending.push_back((state, token));
         }
+        Ok(&self.pending[n].1)
+    }
+
+    fn bump(&mut self) -> LexResult<Token> {
+        self.next_token()
     }
 }
 
@@ -0,0 +1,61 @@
+//! Byte classification table for token dispatch.
+//!
+//! Every byte value maps to a [`ByteClass`] describing which kind of token
+//! can start with it. The table is computed at compile time, so dispatching
+//! in [`Lexer::next_token`](crate::lexer::Lexer::next_token) is a single
+//! indexed load instead of a chain of range comparisons. Adding a new token
+//! start only requires updating [`classify`].
+
+/// The coarse category of a byte at the start of a token.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub enum ByteClass {
+    /// Byte that cannot start any token
+    Invalid,
+    /// ASCII letter or underscore (identifiers and keywords)
+    IdentStart,
+    /// ASCII digit (numeric literals)
+    Digit,
+    /// First byte of an operator (`=`, `+`, `-`, `*`, `/`, `%`, `<`, `>`, `!`, `&`, `|`, `^`, `~`)
+    OperatorStart,
+    /// Punctuation delimiter (`(`, `)`, `{`, `}`, `[`, `]`, `;`, `,`, `.`, `?`, `:`)
+    Delimiter,
+    /// Opening quote of a character (`'`) or string (`"`) literal
+    Quote,
+}
+
+/// Lookup table mapping each byte value to its [`ByteClass`].
+static BYTE_CLASSES: [ByteClass; 256] = build_table();
+
+/// Build the lookup table by classifying every possible byte value.
+const fn build_table() -> [ByteClass; 256] {
+    let mut table = [ByteClass::Invalid; 256];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = classify(i as u8);
+        i += 1;
+    }
+    table
+}
+
+/// Classify a single byte. This is the single source of truth for which
+/// bytes can begin which tokens.
+const fn classify(byte: u8) -> ByteClass {
+    match byte {
+        b'a'..=b'z' | b'A'..=b'Z' | b'_' => ByteClass::IdentStart,
+        b'0'..=b'9' => ByteClass::Digit,
+        b'\'' | b'"' => ByteClass::Quote,
+        b'(' | b')' | b'{' | b'}' | b'[' | b']' | b';' | b',' | b'.' | b'?' | b':' => {
+            ByteClass::Delimiter
+        }
+        b'=' | b'+' | b'-' | b'*' | b'/' | b'%' | b'<' | b'>' | b'!' | b'&' | b'|' | b'^'
+        | b'~' => ByteClass::OperatorStart,
+        _ => ByteClass::Invalid,
+    }
+}
+
+/// Look up the [`ByteClass`] of a byte.
+#[inline]
+pub fn class_of(byte: u8) -> ByteClass {
+    BYTE_CLASSES[byte as usize]
+}
@@ -5,11 +5,16 @@
 
 use super::token_builder::TokenBuilder;
 use crate::charstream::CharStream;
+use crate::token::operators::assignment::AssignmentOps;
 use crate::token::operators::SpecialOps;
 use crate::token::{delimiters::Delimiters, tokenkind::TokenKind, Token};
 
 /// Tokenize a delimiter based on the byte character.
 ///
+/// A `:` is forwarded to [`lex_colon`] so `::` is recognized, a `.` to
+/// [`lex_dot`] so `...` is, and a `?` to [`lex_question`] so `??` and
+/// `??=` are.
+///
 /// # Arguments
 ///
 /// * `stream` - The character stream
@@ -19,6 +24,16 @@ use crate::token::{delimiters::Delimiters, tokenkind::TokenKind, Token};
 ///
 /// The tokenized delimiter
 pub fn lex_delimiter(stream: &mut CharStream, byte: u8) -> Token {
+    if byte == b':' {
+        return lex_colon(stream);
+    }
+    if byte == b'.' {
+        return lex_dot(stream);
+    }
+    if byte == b'?' {
+        return lex_question(stream);
+    }
+
     let builder = TokenBuilder::new(stream);
     let d = |kind| TokenKind::Delimiter(kind);
 
@@ -31,8 +46,6 @@ pub fn lex_delimiter(stream: &mut CharStream, byte: u8) -> Token {
         b']' => builder.single_char_token(d(Delimiters::RightBracket), "]"),
         b';' => builder.single_char_token(d(Delimiters::Semicolon), ";"),
         b',' => builder.single_char_token(d(Delimiters::Comma), ","),
-        b'.' => builder.single_char_token(d(Delimiters::Dot), "."),
-        b'?' => builder.single_char_token(d(Delimiters::QuestionMark), "?"),
         _ => unreachable!("Invalid delimiter character reached, {}. This shouldn't be possible please debug.", byte),
     }
 }
@@ -52,3 +65,50 @@ pub fn lex_colon(stream: &mut CharStream) -> Token {
         builder.single_char_token(TokenKind::Delimiter(Delimiters::Colon), ":")
     }
 }
+
+/// Tokenize `.` (member access) or `...` (variadic ellipsis).
+///
+/// Two dots are not a token of their own, so `..` lexes as two `Dot`s.
+///
+/// # Returns
+///
+/// - `...` → `TokenKind::Ellipsis`
+/// - `.` → `TokenKind::Dot`
+pub fn lex_dot(stream: &mut CharStream) -> Token {
+    let is_ellipsis = stream.peek_n(1) == Some(b'.') && stream.peek_n(2) == Some(b'.');
+    let builder = TokenBuilder::new(stream);
+    if is_ellipsis {
+        builder.multi_char_token(3, TokenKind::SpecialOperator(SpecialOps::Ellipsis), "...")
+    } else {
+        builder.single_char_token(TokenKind::Delimiter(Delimiters::Dot), ".")
+    }
+}
+
+/// Tokenize `?` (optional), `??` (null coalescing), or `??=` (null-coalescing
+/// assignment).
+///
+/// # Returns
+///
+/// - `??=` → `TokenKind::NullCoalescingAssign`
+/// - `??` → `TokenKind::NullCoalescing`
+/// - `?` → `TokenKind::QuestionMark`
+pub fn lex_question(stream: &mut CharStream) -> Token {
+    let is_coalescing = stream.peek_n(1) == Some(b'?');
+    let is_assign = is_coalescing && stream.peek_n(2) == Some(b'=');
+    let builder = TokenBuilder::new(stream);
+    if is_assign {
+        builder.multi_char_token(
+            3,
+            TokenKind::AssignmentOperator(AssignmentOps::NullCoalescingAssign),
+            "??=",
+        )
+    } else if is_coalescing {
+        builder.multi_char_token(
+            2,
+            TokenKind::SpecialOperator(SpecialOps::NullCoalescing),
+            "??",
+        )
+    } else {
+        builder.single_char_token(TokenKind::Delimiter(Delimiters::QuestionMark), "?")
+    }
+}
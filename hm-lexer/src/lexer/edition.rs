@@ -0,0 +1,69 @@
+//! Edition gating.
+//!
+//! Token forms introduced after the first edition are listed in [`GATES`].
+//! They are lexed as usual and then rejected with
+//! [`LexError::RequiresEdition`] when the configured edition predates them,
+//! so the error covers the whole token and recovery resumes right after it.
+
+use crate::config::Edition;
+use crate::lexer::Lexer;
+use crate::lexerror::{LexError, LexResult};
+use crate::token::tokenkind::TokenKind;
+use crate::token::Token;
+
+/// A token form that needs a newer edition than the first.
+struct Gate {
+    /// How the token's lexeme starts
+    prefix: &'static str,
+    /// Name used in diagnostics
+    feature: &'static str,
+    /// The first edition accepting the form
+    since: Edition,
+}
+
+/// Literal forms and the edition that introduced them.
+const GATES: &[Gate] = &[
+    Gate {
+        prefix: "0b",
+        feature: "Binary literals",
+        since: Edition::V2,
+    },
+    Gate {
+        prefix: "0o",
+        feature: "Octal literals",
+        since: Edition::V2,
+    },
+    Gate {
+        prefix: "b'",
+        feature: "Byte literals",
+        since: Edition::V2,
+    },
+];
+
+impl Lexer {
+    /// Reject `token` if the configured edition predates its form.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the edition accepts the token
+    /// - `Err(LexError::RequiresEdition)` spanning the token otherwise
+    pub(super) fn check_edition(&self, token: &Token) -> LexResult<()> {
+        if !matches!(token.kind, TokenKind::Literal(_)) {
+            return Ok(());
+        }
+        let edition = self.config.edition;
+        let text = self.token_text(token);
+        match GATES
+            .iter()
+            .find(|gate| edition < gate.since && text.starts_with(gate.prefix))
+        {
+            Some(gate) => Err(LexError::RequiresEdition {
+                feature: gate.feature.to_string(),
+                required: gate.since,
+                span: token.span,
+                context: None,
+            }),
+            None => Ok(()),
+        }
+    }
+}
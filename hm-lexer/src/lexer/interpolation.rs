@@ -0,0 +1,90 @@
+//! `${...}` interpolations inside string literals.
+//!
+//! When a plain string reaches `${`, the lexer switches back to lexing
+//! ordinary tokens until the `}` that balances it, then resumes reading the
+//! string. Each `${` pushes a mode onto the lexer's mode stack and its
+//! closing `}` pops it, so strings may be nested inside interpolations to
+//! any depth up to [`MAX_INTERPOLATION_DEPTH`].
+
+use crate::lexer::Lexer;
+use crate::lexerror::{LexError, LexResult};
+use crate::token::delimiters::Delimiters;
+use crate::token::tokenkind::TokenKind;
+use crate::token::Token;
+
+/// How deeply interpolations may nest, as in `"${ "${ x }" }"`, before the
+/// lexer gives up instead of exhausting its stack.
+pub(crate) const MAX_INTERPOLATION_DEPTH: usize = 64;
+
+/// A lexing mode pushed while inside a construct that changes how tokens
+/// end.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub(crate) enum Mode {
+    /// Inside `${...}`; holds the number of `{` opened in the expression
+    /// and not yet closed
+    Interpolation { braces: usize },
+}
+
+impl Lexer {
+    /// Lex the expression of an interpolation, with the stream at its `$`.
+    ///
+    /// # Arguments
+    ///
+    /// * `string_start` - Position of the enclosing string's opening quote,
+    ///   where the span of an unclosed interpolation's error starts
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Vec<Token>)` with the tokens between `${` and the balancing
+    ///   `}`, both of which are consumed
+    /// - `Err(LexError::UnterminatedString)` if the input ends first
+    /// - `Err(LexError::UnexpectedCharacter)` at the `$` if the
+    ///   interpolation would exceed [`MAX_INTERPOLATION_DEPTH`]
+    /// - Any error from lexing the expression's tokens
+    pub(super) fn lex_interpolation(
+        &mut self,
+        string_start: (usize, usize, usize),
+    ) -> LexResult<Vec<Token>> {
+        if self.modes.len() >= MAX_INTERPOLATION_DEPTH {
+            return Err(LexError::UnexpectedCharacter {
+                ch: '$',
+                span: self.span_through_char(self.stream.current_position()),
+                context: Some("string interpolation nested too deeply".into()),
+            });
+        }
+
+        self.stream.advance_n(2); // consume `${`
+        self.modes.push(Mode::Interpolation { braces: 0 });
+        let result = self.interpolation_tokens(string_start);
+        self.modes.pop();
+        result
+    }
+
+    /// Collect tokens until the `}` that closes the innermost interpolation.
+    fn interpolation_tokens(
+        &mut self,
+        string_start: (usize, usize, usize),
+    ) -> LexResult<Vec<Token>> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = self.lex_token()?;
+            let Some(Mode::Interpolation { braces }) = self.modes.last_mut() else {
+                unreachable!("interpolation mode is pushed by lex_interpolation");
+            };
+            match token.kind {
+                TokenKind::Eof => {
+                    return Err(LexError::UnterminatedString {
+                        span: self.span_from(string_start),
+                        context: Some("unclosed `${` in string literal".into()),
+                    });
+                }
+                TokenKind::Delimiter(Delimiters::RightBrace) if *braces == 0 => return Ok(tokens),
+                TokenKind::Delimiter(Delimiters::RightBrace) => *braces -= 1,
+                TokenKind::Delimiter(Delimiters::LeftBrace) => *braces += 1,
+                _ => {}
+            }
+            tokens.push(token);
+        }
+    }
+}
@@ -1,10 +1,12 @@
 /// Macro to decode escape sequences in string literals.
 /// It consumes the backslash and checks the next character to determine
-/// the appropriate escape sequence. If the escape sequence is valid, it
-/// returns the corresponding character. If invalid, it returns a LexError.
+/// the appropriate escape sequence, including `\u{...}` escapes. If the
+/// escape sequence is valid, it returns the corresponding character. If
+/// invalid, it returns a LexError spanning from `$start`, the literal's
+/// position, through the escape.
 #[macro_export]
 macro_rules! decode_escape {
-    ($lexer:expr, $quote:expr, $start_line:expr, $start_col:expr) => {{
+    ($lexer:expr, $quote:expr, $start:expr) => {{
         $lexer.stream.advance(); // consume backslash
 
         match $lexer.stream.peek() {
@@ -28,6 +30,25 @@ macro_rules! decode_escape {
                 $lexer.stream.advance();
                 Ok('\\')
             }
+            Some(b'$') => {
+                $lexer.stream.advance();
+                Ok('$')
+            }
+            Some(b'u') => {
+                let rest = &$lexer.stream.as_bytes()[$lexer.stream.index() + 1..];
+                match $crate::lexer::unicode_escape::decode_unicode_escape(rest) {
+                    Ok((ch, len)) => {
+                        $lexer.stream.advance_n(1 + len);
+                        Ok(ch)
+                    }
+                    Err(invalid) => {
+                        // The sequence starts at the backslash, just behind
+                        // the cursor
+                        let span = $lexer.span_ahead($start, invalid.sequence.len() - 1);
+                        Err(invalid.into_error(span))
+                    }
+                }
+            }
             Some(b) if b == $quote => {
                 $lexer.stream.advance();
                 Ok(b as char)
@@ -39,8 +60,8 @@ macro_rules! decode_escape {
                 };
                 Err(LexError::InvalidEscape {
                     sequence: seq,
-                    line: $start_line,
-                    column: $start_col,
+                    span: $lexer.span_through_char($start),
+                    context: None,
                 })
             }
         }
@@ -81,11 +102,12 @@ macro_rules! single_char_token {
             column_start: $start_col,
             line_end: end_line,
             column_end: end_col,
+            char_offsets: None,
         };
         Token {
             kind: $kind,
             span,
-            lexeme: String::from($lexeme),
+            lexeme: $crate::token::static_lexeme($lexeme),
         }
     }};
 }
@@ -0,0 +1,171 @@
+//! Triple-quoted (multiline) string literals.
+//!
+//! A literal opened with `"""` runs until the next unescaped `"""` and may
+//! span lines. Unlike plain strings, its text is first captured raw and then
+//! processed as a whole: optional indentation stripping runs before escape
+//! decoding, so escapes such as `\t` never count as indentation.
+
+use crate::config::LineEndings;
+use crate::lexer::unicode_escape::decode_unicode_escape;
+use crate::lexer::Lexer;
+use crate::lexerror::{LexError, LexResult};
+use crate::token::literals::Literals;
+use crate::token::span::Span;
+use crate::token::tokenkind::TokenKind;
+use crate::token::Token;
+
+impl Lexer {
+    /// Tokenize a triple-quoted string literal.
+    ///
+    /// The stream must be positioned at the opening `"""`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Token)` with `Literals::MultilineStringLiteral`
+    /// - `Err(LexError::UnterminatedString)` if EOF is reached before the closing `"""`
+    /// - `Err(LexError::InvalidEscape)` if an escape sequence is invalid
+    /// - `Err(LexError::InvalidUtf8)` if the content is not valid UTF-8
+    pub(super) fn lex_multiline_string_literal(&mut self) -> LexResult<Token> {
+        let start = self.stream.current_position();
+        let (start_idx, start_line, start_col) = start;
+
+        self.stream.advance_n(3); // consume opening """
+        let content_start = self.stream.index();
+
+        loop {
+            match self.stream.peek() {
+                None => {
+                    return Err(LexError::UnterminatedString {
+                        span: self.span_from(start),
+                        context: None,
+                    });
+                }
+                Some(b'"') if self.at_triple_quote() => break,
+                Some(b'\\') => self.stream.advance_n(2),
+                Some(_) => {
+                    self.stream.advance();
+                }
+            }
+        }
+
+        let content_end = self.stream.index();
+        self.stream.advance_n(3); // consume closing """
+
+        let raw = std::str::from_utf8(self.stream.slice(content_start, content_end))
+            .map_err(|_| LexError::InvalidUtf8 {
+                span: self.span_from(start),
+                context: Some("invalid UTF-8 in string literal".into()),
+            })?
+            .to_owned();
+        let text = if self.config.line_endings == LineEndings::Normalize {
+            raw.replace("\r\n", "\n")
+        } else {
+            raw.clone()
+        };
+        let trimmed = if self.config.trim_indent {
+            trim_indent(&text)
+        } else {
+            text
+        };
+        let value = if self.config.keep_escapes {
+            trimmed
+        } else {
+            decode_escapes(&trimmed, self.span_from(start))?
+        };
+
+        let (end_idx, end_line, end_col) = self.stream.current_position();
+        let lexeme = self.source_lexeme(start_idx, end_idx);
+
+        Ok(Token {
+            kind: TokenKind::Literal(Literals::MultilineStringLiteral { raw, value }),
+            span: Span {
+                start: start_idx,
+                end: end_idx,
+                line_start: start_line,
+                column_start: start_col,
+                line_end: end_line,
+                column_end: end_col,
+                char_offsets: None,
+            },
+            lexeme,
+        })
+    }
+
+    /// Whether the stream is positioned at `"""`.
+    pub(super) fn at_triple_quote(&self) -> bool {
+        self.stream.peek() == Some(b'"')
+            && self.stream.peek_n(1) == Some(b'"')
+            && self.stream.peek_n(2) == Some(b'"')
+    }
+}
+
+/// Remove the indentation common to all non-blank lines of `text`.
+///
+/// A blank first line (the remainder of the opening delimiter's line) and a
+/// blank last line (the indentation before the closing delimiter) are
+/// dropped. Blank lines in between become empty.
+fn trim_indent(text: &str) -> String {
+    let is_blank = |line: &str| line.trim_matches([' ', '\t', '\r']).is_empty();
+
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    if lines.len() > 1 && lines.first().is_some_and(|l| is_blank(l)) {
+        lines.remove(0);
+    }
+    if lines.len() > 1 && lines.last().is_some_and(|l| is_blank(l)) {
+        lines.pop();
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|line| !is_blank(line))
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| if is_blank(line) { "" } else { &line[indent..] })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Decode the escape sequences in `text`, which must not contain a closing
+/// `"""`. Errors cover `literal`, the whole literal's span.
+fn decode_escapes(text: &str, literal: Span) -> LexResult<String> {
+    let mut decoded = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            decoded.push(c);
+            continue;
+        }
+        let escaped = match chars.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some('\\') => '\\',
+            Some('$') => '$',
+            Some('"') => '"',
+            Some('u') => {
+                let (ch, len) = decode_unicode_escape(chars.as_str().as_bytes())
+                    .map_err(|invalid| invalid.into_error(literal))?;
+                chars = chars.as_str()[len..].chars();
+                ch
+            }
+            other => {
+                let sequence = match other {
+                    Some(c) => format!("\\{c}"),
+                    None => "\\(EOF)".to_string(),
+                };
+                return Err(LexError::InvalidEscape {
+                    sequence,
+                    span: literal,
+                    context: None,
+                });
+            }
+        };
+        decoded.push(escaped);
+    }
+    Ok(decoded)
+}
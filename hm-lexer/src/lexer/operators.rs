@@ -5,7 +5,7 @@
 //! the repetitive operator-matching logic from the main lexer.
 
 use crate::charstream::CharStream;
-use crate::lexerror::LexError;
+use crate::lexerror::LexResult;
 use crate::token::operators::arithmetic::ArithmeticOps;
 use crate::token::operators::assignment::AssignmentOps;
 use crate::token::operators::bitwise::BitwiseOps;
@@ -30,7 +30,7 @@ use super::token_builder::TokenBuilder;
 /// # Returns
 ///
 /// The tokenized operator or an error
-pub fn lex_operator(stream: &mut CharStream, byte: u8) -> Result<Token, LexError> {
+pub fn lex_operator(stream: &mut CharStream, byte: u8) -> LexResult<Token> {
     match byte {
         b'=' => lex_equals(stream),
         b'+' => lex_plus(stream),
@@ -62,7 +62,7 @@ pub fn lex_operator(stream: &mut CharStream, byte: u8) -> Result<Token, LexError
 }
 
 /// Tokenize `=` or `==`
-fn lex_equals(stream: &mut CharStream) -> Result<Token, LexError> {
+fn lex_equals(stream: &mut CharStream) -> LexResult<Token> {
     let is_equal = stream.peek_n(1) == Some(b'=');
     let builder = TokenBuilder::new(stream);
     if is_equal {
@@ -80,7 +80,7 @@ fn lex_equals(stream: &mut CharStream) -> Result<Token, LexError> {
 }
 
 /// Tokenize `+` or `+=`
-fn lex_plus(stream: &mut CharStream) -> Result<Token, LexError> {
+fn lex_plus(stream: &mut CharStream) -> LexResult<Token> {
     let is_assign = stream.peek_n(1) == Some(b'=');
     let builder = TokenBuilder::new(stream);
     if is_assign {
@@ -98,7 +98,7 @@ fn lex_plus(stream: &mut CharStream) -> Result<Token, LexError> {
 }
 
 /// Tokenize `-` or `-=` or `->`
-fn lex_minus(stream: &mut CharStream) -> Result<Token, LexError> {
+fn lex_minus(stream: &mut CharStream) -> LexResult<Token> {
     let next = stream.peek_n(1);
     let builder = TokenBuilder::new(stream);
     if next == Some(b'=') {
@@ -121,32 +121,46 @@ fn lex_minus(stream: &mut CharStream) -> Result<Token, LexError> {
     }
 }
 
-/// Tokenize `*`, `**`, or `*=`
-fn lex_asterisk(stream: &mut CharStream) -> Result<Token, LexError> {
+/// Tokenize `*`, `*=`, or (feature `experimental-ops`) `**` or `**=`
+fn lex_asterisk(stream: &mut CharStream) -> LexResult<Token> {
     let next = stream.peek_n(1);
+    #[cfg(feature = "experimental-ops")]
+    let is_exponent_assign = next == Some(b'*') && stream.peek_n(2) == Some(b'=');
     let builder = TokenBuilder::new(stream);
     if next == Some(b'=') {
-        Ok(builder.multi_char_token(
+        return Ok(builder.multi_char_token(
             2,
             TokenKind::AssignmentOperator(AssignmentOps::MultiplyAssign),
             "*=",
-        ))
-    } else if next == Some(b'*') {
-        Ok(builder.multi_char_token(
+        ));
+    }
+
+    #[cfg(feature = "experimental-ops")]
+    if is_exponent_assign {
+        return Ok(builder.multi_char_token(
+            3,
+            TokenKind::AssignmentOperator(AssignmentOps::ExponentAssign),
+            "**=",
+        ));
+    }
+
+    #[cfg(feature = "experimental-ops")]
+    if next == Some(b'*') {
+        return Ok(builder.multi_char_token(
             2,
             TokenKind::ArithmeticOperator(ArithmeticOps::Exponent),
             "**",
-        ))
-    } else {
-        Ok(builder.single_char_token(
-            TokenKind::ArithmeticOperator(ArithmeticOps::Asterisk),
-            "*",
-        ))
+        ));
     }
+
+    Ok(builder.single_char_token(
+        TokenKind::ArithmeticOperator(ArithmeticOps::Asterisk),
+        "*",
+    ))
 }
 
 /// Tokenize `/` or `/=`
-fn lex_slash(stream: &mut CharStream) -> Result<Token, LexError> {
+fn lex_slash(stream: &mut CharStream) -> LexResult<Token> {
     let is_assign = stream.peek_n(1) == Some(b'=');
     let builder = TokenBuilder::new(stream);
     if is_assign {
@@ -164,7 +178,7 @@ fn lex_slash(stream: &mut CharStream) -> Result<Token, LexError> {
 }
 
 /// Tokenize `%` or `%=`
-fn lex_modulo(stream: &mut CharStream) -> Result<Token, LexError> {
+fn lex_modulo(stream: &mut CharStream) -> LexResult<Token> {
     let is_assign = stream.peek_n(1) == Some(b'=');
     let builder = TokenBuilder::new(stream);
     if is_assign {
@@ -182,7 +196,7 @@ fn lex_modulo(stream: &mut CharStream) -> Result<Token, LexError> {
 }
 
 /// Tokenize `<`, `<=`, or `<<`
-fn lex_less_than(stream: &mut CharStream) -> Result<Token, LexError> {
+fn lex_less_than(stream: &mut CharStream) -> LexResult<Token> {
     let next = stream.peek_n(1);
     let builder = TokenBuilder::new(stream);
     if next == Some(b'=') {
@@ -206,7 +220,7 @@ fn lex_less_than(stream: &mut CharStream) -> Result<Token, LexError> {
 }
 
 /// Tokenize `>`, `>=`, or `>>`
-fn lex_greater_than(stream: &mut CharStream) -> Result<Token, LexError> {
+fn lex_greater_than(stream: &mut CharStream) -> LexResult<Token> {
     let next = stream.peek_n(1);
     let builder = TokenBuilder::new(stream);
     if next == Some(b'=') {
@@ -230,7 +244,7 @@ fn lex_greater_than(stream: &mut CharStream) -> Result<Token, LexError> {
 }
 
 /// Tokenize `!` or `!=`
-fn lex_not(stream: &mut CharStream) -> Result<Token, LexError> {
+fn lex_not(stream: &mut CharStream) -> LexResult<Token> {
     let is_not_equal = stream.peek_n(1) == Some(b'=');
     let builder = TokenBuilder::new(stream);
     if is_not_equal {
@@ -248,7 +262,7 @@ fn lex_not(stream: &mut CharStream) -> Result<Token, LexError> {
 }
 
 /// Tokenize `&` or `&&`
-fn lex_ampersand(stream: &mut CharStream) -> Result<Token, LexError> {
+fn lex_ampersand(stream: &mut CharStream) -> LexResult<Token> {
     let is_logical = stream.peek_n(1) == Some(b'&');
     let builder = TokenBuilder::new(stream);
     if is_logical {
@@ -271,7 +285,7 @@ fn lex_ampersand(stream: &mut CharStream) -> Result<Token, LexError> {
 ///
 /// - `||` → `LogicalOperator::Or`
 /// - `|` → `BitwiseOperator::Or`
-fn lex_pipe(stream: &mut CharStream) -> Result<Token, LexError> {
+fn lex_pipe(stream: &mut CharStream) -> LexResult<Token> {
     let is_logical = stream.peek_n(1) == Some(b'|');
     let builder = TokenBuilder::new(stream);
     if is_logical {
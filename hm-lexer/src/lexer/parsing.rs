@@ -4,13 +4,20 @@
 //! including character literals, string literals, identifiers, keywords,
 //! and numeric literals (integers and floats).
 
+use std::borrow::Cow;
+
 use crate::decode_escape;
+use crate::keywordtable::KeywordEntry;
 use crate::lexer::Lexer;
-use crate::lexerror::LexError;
-use crate::token::literals::Literals;
+use crate::lexerror::{CharLiteralReason, LexError, LexResult, NumberClass};
+use crate::token::literals::{Literals, NumericSuffix, StringPart};
 use crate::token::span::Span;
 use crate::token::tokenkind::TokenKind;
-use crate::token::Token;
+use crate::token::{owned_lexeme, static_lexeme, Lexeme, Token};
+
+/// Context of the error for a raw newline inside a plain string literal.
+const NEWLINE_IN_STRING: &str =
+    "newline in string literal; use a triple-quoted string to span lines";
 
 impl Lexer {
     /// Tokenize a character literal (single-quoted).
@@ -27,43 +34,115 @@ impl Lexer {
     /// - `\0` → null byte
     /// - `\\` → backslash
     /// - `\'` → single quote
+    /// - `\u{XXXX}` → the Unicode scalar value named by the hex digits
+    ///
+    /// The character itself may be any UTF-8 encoded scalar value, as in
+    /// `'é'`.
     ///
     /// # Returns
     ///
-    /// - `Ok(Token)` with `TokenKind::CharacterLiteral`
-    /// - `Err(LexError::UnterminatedString)` if closing quote is missing
+    /// - `Ok(Token)` with `Literals::CharacterLiteral`
+    /// - `Err(LexError::InvalidCharLiteral)` if the literal is empty, holds
+    ///   more than one character, or has no closing quote
     /// - `Err(LexError::InvalidEscape)` if escape sequence is invalid
-    pub(super) fn lex_character_literal(&mut self) -> Result<Token, LexError> {
-        let (start_idx, start_line, start_col) = self.stream.current_position();
+    /// - `Err(LexError::InvalidUtf8)` if the character is not valid UTF-8
+    ///
+    /// An empty or over-long literal is consumed through its closing quote,
+    /// so lexing resumes right after it.
+    pub(super) fn lex_character_literal(&mut self) -> LexResult<Token> {
+        let start = self.stream.current_position();
+        let ch = self.lex_quoted_char(start)?;
+        Ok(self.char_token(start, Literals::CharacterLiteral(ch)))
+    }
+
+    /// Tokenize a byte literal: `b` followed by a character literal.
+    ///
+    /// The content follows the same rules as a character literal but must
+    /// be ASCII, so its value fits in a `u8`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Token)` with `Literals::ByteLiteral`
+    /// - `Err(LexError::InvalidCharLiteral)` with reason `NonAscii` for
+    ///   content outside ASCII, or any error a character literal can raise
+    pub(super) fn lex_byte_literal(&mut self) -> LexResult<Token> {
+        let start = self.stream.current_position();
+        self.stream.advance(); // consume b
+
+        if self.stream.peek_n(1).is_some_and(|b| !b.is_ascii()) {
+            // Consume the whole non-ASCII character and the closing quote so
+            // lexing resumes after the literal.
+            self.stream.advance();
+            self.stream.skip_while(|b| !b.is_ascii());
+            self.stream.match_byte(b'\'');
+            return Err(self.invalid_char_literal(CharLiteralReason::NonAscii, start));
+        }
 
+        let ch = self.lex_quoted_char(start)?;
+        if !ch.is_ascii() {
+            return Err(self.invalid_char_literal(CharLiteralReason::NonAscii, start));
+        }
+        Ok(self.char_token(start, Literals::ByteLiteral(ch as u8)))
+    }
+
+    /// Consume a single-quoted character, from the opening quote through
+    /// the closing quote, and return its value.
+    ///
+    /// `start` is where the whole literal began, which is where error spans
+    /// start.
+    fn lex_quoted_char(&mut self, start: (usize, usize, usize)) -> LexResult<char> {
         self.stream.advance(); // consume opening '
 
         let ch = match self.stream.peek() {
             None => {
-                return Err(LexError::UnterminatedString {
-                    line: start_line,
-                    column: start_col,
-                });
+                return Err(
+                    self.invalid_char_literal(CharLiteralReason::MissingClosingQuote, start)
+                );
             }
-            Some(b'\\') => decode_escape!(self, b'\'', start_line, start_col)?,
-            Some(b) => {
+            Some(b'\'') => {
                 self.stream.advance();
-                b as char
+                return Err(self.invalid_char_literal(CharLiteralReason::Empty, start));
             }
+            Some(b'\\') => decode_escape!(self, b'\'', start)?,
+            Some(_) => self.lex_literal_char(start, "character")?,
         };
 
         if !self.stream.match_byte(b'\'') {
-            return Err(LexError::UnterminatedString {
-                line: start_line,
-                column: start_col,
-            });
+            // Look ahead on the same line for the closing quote: if there is
+            // one, the literal simply holds too many characters.
+            let after_char = self.stream.current_position();
+            loop {
+                match self.stream.peek() {
+                    Some(b'\'') => {
+                        self.stream.advance();
+                        return Err(
+                            self.invalid_char_literal(CharLiteralReason::TooManyCharacters, start)
+                        );
+                    }
+                    None | Some(b'\n') => break,
+                    Some(b'\\') => self.stream.advance_n(2),
+                    Some(_) => {
+                        self.stream.advance();
+                    }
+                }
+            }
+            self.stream.back_up(after_char);
+            return Err(self.invalid_char_literal(CharLiteralReason::MissingClosingQuote, start));
         }
 
+        Ok(ch)
+    }
+
+    /// Build a literal token spanning from `start` to the current position,
+    /// with the raw source (including quotes) as its lexeme.
+    fn char_token(
+        &self,
+        (start_idx, start_line, start_col): (usize, usize, usize),
+        literal: Literals,
+    ) -> Token {
         let (end_idx, end_line, end_col) = self.stream.current_position();
 
-        // Lexeme is the raw source including quotes
-        let lexeme_bytes = self.stream.slice(start_idx, end_idx);
-        let lexeme = String::from_utf8_lossy(lexeme_bytes).to_string();
+        let lexeme = self.source_lexeme(start_idx, end_idx);
 
         let span = Span {
             start: start_idx,
@@ -72,13 +151,61 @@ impl Lexer {
             column_start: start_col,
             line_end: end_line,
             column_end: end_col,
+            char_offsets: None,
         };
 
-        Ok(Token {
-            kind: TokenKind::Literal(Literals::CharacterLiteral(ch)),
+        Token {
+            kind: TokenKind::Literal(literal),
             span,
             lexeme,
-        })
+        }
+    }
+
+    /// Consume one character of a literal's content, decoding UTF-8.
+    ///
+    /// ASCII bytes are taken as they are; a multi-byte sequence is validated
+    /// as a whole, so text such as `"café"` keeps its `é`.
+    ///
+    /// # Arguments
+    ///
+    /// * `literal_start` - Position of the literal, where error spans start
+    /// * `literal` - Name of the literal for error messages, e.g. `"string"`
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(char)` with the character, which has been consumed
+    /// - `Err(LexError::InvalidUtf8)` if the bytes at the cursor are not valid
+    ///   UTF-8; only the first of them is consumed
+    fn lex_literal_char(
+        &mut self,
+        literal_start: (usize, usize, usize),
+        literal: &str,
+    ) -> LexResult<char> {
+        match self.stream.advance_char() {
+            Ok(Some(ch)) => Ok(ch),
+            Ok(None) => unreachable!("callers check for end of input"),
+            Err(_) => {
+                self.stream.advance();
+                Err(LexError::InvalidUtf8 {
+                    span: self.span_from(literal_start),
+                    context: Some(format!("invalid UTF-8 in {literal} literal").into()),
+                })
+            }
+        }
+    }
+
+    /// Build an [`LexError::InvalidCharLiteral`] spanning from `start` to the
+    /// current position.
+    fn invalid_char_literal(
+        &self,
+        reason: CharLiteralReason,
+        start: (usize, usize, usize),
+    ) -> LexError {
+        LexError::InvalidCharLiteral {
+            reason,
+            span: self.span_from(start),
+            context: None,
+        }
     }
 
     /// Tokenize a string literal (double-quoted).
@@ -95,38 +222,77 @@ impl Lexer {
     /// - `\0` → null byte
     /// - `\\` → backslash
     /// - `\"` → double quote
+    /// - `\$` → dollar sign, so `\${` does not start an interpolation
+    /// - `\u{1F600}` → the Unicode scalar value named by one to six hex
+    ///   digits
+    ///
+    /// With [`LexerConfig::keep_escapes`](crate::config::LexerConfig::keep_escapes)
+    /// enabled, escapes are copied through undecoded instead.
+    ///
+    /// # Interpolation
+    ///
+    /// `${` switches back to lexing ordinary tokens until the `}` that
+    /// balances it, see [`lex_interpolation`](Self::lex_interpolation). A
+    /// string with at least one interpolation is returned as
+    /// `Literals::InterpolatedString`.
     ///
     /// # Returns
     ///
     /// - `Ok(Token)` with `TokenKind::StringLiteral` and decoded content
+    /// - `Ok(Token)` with `Literals::InterpolatedString` if the string
+    ///   contains `${...}`
     /// - `Err(LexError::UnterminatedString)` if EOF is reached before closing quote
+    /// - `Err(LexError::UnterminatedString)` if a raw newline is reached before
+    ///   the closing quote; strings that span lines must be triple-quoted
     /// - `Err(LexError::InvalidEscape)` if escape sequence is invalid
-    pub(super) fn lex_string_literal(&mut self) -> Result<Token, LexError> {
-        let (start_idx, start_line, start_col) = self.stream.current_position();
+    /// - `Err(LexError::InvalidUtf8)` if the content is not valid UTF-8
+    pub(super) fn lex_string_literal(&mut self) -> LexResult<Token> {
+        let start = self.stream.current_position();
+        let (start_idx, start_line, start_col) = start;
 
         self.stream.advance(); // consume opening "
 
         let mut decoded = String::new();
+        let mut parts = Vec::new();
 
         loop {
             match self.stream.peek() {
                 None => {
                     return Err(LexError::UnterminatedString {
-                        line: start_line,
-                        column: start_col,
+                        span: self.span_from(start),
+                        context: None,
                     });
                 }
                 Some(b'"') => {
                     self.stream.advance();
                     break;
                 }
+                Some(b'\n') => {
+                    return Err(LexError::UnterminatedString {
+                        span: self.span_from(start),
+                        context: Some(NEWLINE_IN_STRING.into()),
+                    });
+                }
+                Some(b'$') if self.stream.peek_n(1) == Some(b'{') => {
+                    if !decoded.is_empty() {
+                        parts.push(StringPart::Text(std::mem::take(&mut decoded)));
+                    }
+                    let expr = self.lex_interpolation(start)?;
+                    parts.push(StringPart::Expr(expr));
+                }
+                Some(b'\\') if self.config.keep_escapes => {
+                    decoded.push('\\');
+                    self.stream.advance();
+                    if self.stream.peek().is_some() {
+                        decoded.push(self.lex_literal_char(start, "string")?);
+                    }
+                }
                 Some(b'\\') => {
-                    let ch = decode_escape!(self, b'"', start_line, start_col)?;
+                    let ch = decode_escape!(self, b'"', start)?;
                     decoded.push(ch);
                 }
-                Some(b) => {
-                    decoded.push(b as char);
-                    self.stream.advance();
+                Some(_) => {
+                    decoded.push(self.lex_literal_char(start, "string")?);
                 }
             }
         }
@@ -134,8 +300,7 @@ impl Lexer {
         let (end_idx, end_line, end_col) = self.stream.current_position();
 
         // Lexeme is the raw source including quotes
-        let lexeme_bytes = self.stream.slice(start_idx, end_idx);
-        let lexeme = String::from_utf8_lossy(lexeme_bytes).to_string();
+        let lexeme = self.source_lexeme(start_idx, end_idx);
 
         let span = Span {
             start: start_idx,
@@ -144,10 +309,69 @@ impl Lexer {
             column_start: start_col,
             line_end: end_line,
             column_end: end_col,
+            char_offsets: None,
+        };
+
+        let literal = if parts.is_empty() {
+            Literals::StringLiteral(decoded)
+        } else {
+            if !decoded.is_empty() {
+                parts.push(StringPart::Text(decoded));
+            }
+            Literals::InterpolatedString(parts)
+        };
+
+        Ok(Token {
+            kind: TokenKind::Literal(literal),
+            span,
+            lexeme,
+        })
+    }
+
+    /// Append every string literal that follows `first`, separated only by
+    /// trivia, into one token spanning all of them.
+    ///
+    /// Used when [`LexerConfig::concat_adjacent_strings`] is enabled. The
+    /// stream is left just after the last joined literal; trivia after it is
+    /// left for the next token.
+    ///
+    /// [`LexerConfig::concat_adjacent_strings`]: crate::config::LexerConfig::concat_adjacent_strings
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Token)` with the combined `Literals::StringLiteral`
+    /// - `Err(LexError)` if one of the following literals is malformed
+    pub(super) fn concat_adjacent_strings(&mut self, first: Token) -> LexResult<Token> {
+        let Token {
+            kind: TokenKind::Literal(Literals::StringLiteral(mut joined)),
+            mut span,
+            ..
+        } = first
+        else {
+            return Ok(first);
         };
 
+        loop {
+            let before_trivia = self.stream.current_position();
+            if self.skip_trivia().is_err()
+                || self.stream.peek() != Some(b'"')
+                || self.at_triple_quote()
+            {
+                self.stream.back_up(before_trivia);
+                break;
+            }
+            let next = self.lex_string_literal()?;
+            if let TokenKind::Literal(Literals::StringLiteral(s)) = next.kind {
+                joined.push_str(&s);
+            }
+            span.end = next.span.end;
+            span.line_end = next.span.line_end;
+            span.column_end = next.span.column_end;
+        }
+
+        let lexeme = self.source_lexeme(span.start, span.end);
         Ok(Token {
-            kind: TokenKind::Literal(Literals::StringLiteral(decoded)),
+            kind: TokenKind::Literal(Literals::StringLiteral(joined)),
             span,
             lexeme,
         })
@@ -158,12 +382,21 @@ impl Lexer {
     /// Identifiers start with a letter or underscore and continue with
     /// alphanumeric characters and underscores. The method checks if the
     /// identifier is a reserved keyword and sets the appropriate token kind.
+    /// `true` and `false` are lexed as `Literals::BoolLiteral` rather than as
+    /// identifiers.
+    ///
+    /// Keyword and boolean tokens carry no owned text: their lexeme borrows
+    /// the static spelling, e.g. from
+    /// [`Keywords::as_str`](crate::token::keywords::Keywords::as_str).
+    /// Identifier names are interned, so each distinct name is stored once
+    /// in the lexer's [`Interner`](crate::intern::Interner).
     ///
     /// # Returns
     ///
-    /// - `Ok(Token)` with `TokenKind::Identifier` or a keyword variant
+    /// - `Ok(Token)` with `TokenKind::Identifier`, a keyword variant, or
+    ///   `Literals::BoolLiteral`
     /// - Never returns an error; all valid identifier sequences are accepted
-    pub(super) fn lex_identifier_or_keyword(&mut self) -> Result<Token, LexError> {
+    pub(super) fn lex_identifier_or_keyword(&mut self) -> LexResult<Token> {
         let (start_idx, start_line, start_col) = self.stream.current_position();
 
         // Consume identifier characters
@@ -173,13 +406,41 @@ impl Lexer {
 
         let (end_idx, end_line, end_col) = self.stream.current_position();
 
-        // Get the lexeme as a string
-        let lexeme_bytes = self.stream.slice(lex_start, lex_end);
-        let lexeme = String::from_utf8_lossy(lexeme_bytes).to_string();
-
-        // Try to parse as keyword
-        let kind =
-            TokenKind::keyword(&lexeme).unwrap_or_else(|| TokenKind::Identifier(lexeme.clone()));
+        // Identifier bytes are ASCII, so the lexeme is always valid UTF-8
+        let text = std::str::from_utf8(self.stream.slice(lex_start, lex_end))
+            .expect("identifier bytes are ASCII");
+
+        // Keywords and booleans borrow their static spelling; identifiers
+        // are interned, and only their lexeme allocates
+        let (kind, lexeme) = match self.config.keywords.get(text) {
+            Some(KeywordEntry::Reserved(kw)) => {
+                // Alternative spellings from a custom table keep their text
+                let lexeme = if text == kw.as_str() {
+                    static_lexeme(kw.as_str())
+                } else {
+                    self.source_lexeme(lex_start, lex_end)
+                };
+                (TokenKind::Keyword(kw), lexeme)
+            }
+            None if text == "true" => (
+                TokenKind::Literal(Literals::BoolLiteral(true)),
+                static_lexeme("true"),
+            ),
+            None if text == "false" => (
+                TokenKind::Literal(Literals::BoolLiteral(false)),
+                static_lexeme("false"),
+            ),
+            // Contextual keywords are still identifiers
+            Some(KeywordEntry::Contextual(_)) | None => {
+                let symbol = self.interner.intern(text);
+                let lexeme = if self.config.lazy_lexemes {
+                    Lexeme::default()
+                } else {
+                    owned_lexeme(text)
+                };
+                (TokenKind::Identifier(symbol), lexeme)
+            }
+        };
 
         let span = Span {
             start: start_idx,
@@ -188,6 +449,7 @@ impl Lexer {
             column_start: start_col,
             line_end: end_line,
             column_end: end_col,
+            char_offsets: None,
         };
 
         Ok(Token { kind, span, lexeme })
@@ -199,19 +461,41 @@ impl Lexer {
     /// - Signed integers: `123`
     /// - Unsigned integers: `123u`
     /// - Floating point numbers: `123.45`
+    /// - Type suffixes: `42i8`, `100u32`, `3.14f32`
+    /// - Binary and octal integers: `0b1010_0001`, `0o755`; see
+    ///   [`lex_radix_literal`](Self::lex_radix_literal)
+    /// - `_` digit separators in any of the above: `1_000_000`
     ///
     /// # Returns
     ///
     /// - `Ok(Token)` with `TokenKind::IntLiteral` for signed integers
     /// - `Ok(Token)` with `TokenKind::UnsignedIntLiteral` for unsigned integers (ending with `u`)
     /// - `Ok(Token)` with `TokenKind::FloatLiteral` for floating point numbers
+    /// - `Ok(Token)` with `Literals::NumericLiteral` for numbers with a type
+    ///   suffix, as in `42i8` or `3.14f32`
     /// - `Err(LexError::InvalidNumber)` if the number is malformed or out of range
-    /// - `Err(LexError::InvalidNumber)` if `u` suffix is used with a decimal point
-    pub(super) fn lex_number(&mut self) -> Result<Token, LexError> {
-        let (start_idx, start_line, start_col) = self.stream.current_position();
+    /// - `Err(LexError::InvalidNumber)` if the value does not fit the type
+    ///   named by its suffix, as in `300u8`
+    /// - `Err(LexError::InvalidNumber)` located at a doubled or trailing `_`,
+    ///   see [`check_separators`](Self::check_separators)
+    /// - `Err(LexError::InvalidNumber)` if a float is followed by another `.` and
+    ///   digit, as in `1.2.3`; the whole run of digits and dots is consumed
+    /// - `Err(LexError::InvalidNumberSuffix)` if identifier characters other
+    ///   than an allowed suffix follow the number, as in `123abc` or `3.0u`;
+    ///   the whole run is consumed
+    pub(super) fn lex_number(&mut self) -> LexResult<Token> {
+        if let Some(radix) = self.radix_prefix() {
+            return self.lex_radix_literal(radix);
+        }
+
+        let start = self.stream.current_position();
+        let (start_idx, start_line, start_col) = start;
 
-        // Consume initial digits
-        let (lex_start, _) = self.stream.consume_while(|b| matches!(b, b'0'..=b'9'));
+        // Consume initial digits and their `_` separators
+        let (lex_start, int_end) = self
+            .stream
+            .consume_while(|b| b.is_ascii_digit() || b == b'_');
+        self.check_separators(start_idx, lex_start, int_end)?;
 
         // Check for decimal point (floating point number)
         let is_float = if self.stream.peek() == Some(b'.') {
@@ -220,7 +504,10 @@ impl Lexer {
             if matches!(self.stream.peek_n(1), Some(b'0'..=b'9')) {
                 self.stream.advance(); // consume '.'
                 // Consume fractional digits
-                self.stream.consume_while(|b| matches!(b, b'0'..=b'9'));
+                let (frac_start, frac_end) = self
+                    .stream
+                    .consume_while(|b| b.is_ascii_digit() || b == b'_');
+                self.check_separators(start_idx, frac_start, frac_end)?;
                 true
             } else {
                 false
@@ -229,64 +516,117 @@ impl Lexer {
             false
         };
 
-        // Check for 'u' suffix (unsigned integer indicator)
-        let is_unsigned = self.stream.peek() == Some(b'u');
-        if is_unsigned {
-            // Error: cannot use 'u' suffix with floating point numbers
-            if is_float {
-                let (end_idx, _, _) = self.stream.current_position();
-                let lexeme_bytes = self.stream.slice(lex_start, end_idx);
-                let lexeme = String::from_utf8_lossy(lexeme_bytes).to_string();
-                return Err(LexError::InvalidNumber {
-                    lexeme,
-                    line: start_line,
-                    column: start_col,
-                });
-            }
-            self.stream.advance(); // consume 'u'
+        // A second decimal point, as in a version string `1.2.3`, would
+        // otherwise silently split into float, dot, and integer tokens
+        if is_float
+            && self.stream.peek() == Some(b'.')
+            && matches!(self.stream.peek_n(1), Some(b'0'..=b'9'))
+        {
+            let (_, end_idx) = self
+                .stream
+                .consume_while(|b| b.is_ascii_digit() || b == b'.' || b == b'_');
+            let lexeme_bytes = self.stream.slice(lex_start, end_idx);
+            return Err(LexError::InvalidNumber {
+                lexeme: String::from_utf8_lossy(lexeme_bytes).into_owned(),
+                span: self.span_from(start),
+                context: Some("a number can have only one decimal point".into()),
+            });
+        }
+
+        // Identifier characters glued to the number form its suffix, which
+        // must be one of those allowed for this class of literal
+        let (suffix_start, suffix_end) = self
+            .stream
+            .consume_while(|b| b.is_ascii_alphanumeric() || b == b'_');
+        let suffix = self.stream.slice(suffix_start, suffix_end);
+        let allowed = if is_float {
+            NumberClass::Float
+        } else {
+            NumberClass::Int
+        };
+        if !suffix.is_empty() && !allowed.suffixes().iter().any(|s| s.as_bytes() == suffix) {
+            let (end_idx, end_line, end_col) = self.stream.current_position();
+            return Err(LexError::InvalidNumberSuffix {
+                suffix: String::from_utf8_lossy(suffix).into(),
+                span: Span {
+                    start: start_idx,
+                    end: end_idx,
+                    line_start: start_line,
+                    column_start: start_col,
+                    line_end: end_line,
+                    column_end: end_col,
+                    char_offsets: None,
+                },
+                allowed,
+                context: None,
+            });
         }
+        let is_unsigned = suffix == b"u";
+        let typed = std::str::from_utf8(suffix)
+            .ok()
+            .and_then(NumericSuffix::from_spelling);
 
         let (end_idx, end_line, end_col) = self.stream.current_position();
 
-        // Get the lexeme as a string
-        let lexeme_bytes = self.stream.slice(lex_start, end_idx);
-        let lexeme = String::from_utf8_lossy(lexeme_bytes).to_string();
+        // Number literals are ASCII, so this borrows rather than copies
+        let text = String::from_utf8_lossy(self.stream.slice(lex_start, end_idx));
 
-        // Parse as integer or float
-        let kind = if is_float {
+        // Parse as integer or float, ignoring the suffix and digit separators
+        let digits = &text[..text.len() - suffix.len()];
+        let digits = if digits.contains('_') {
+            Cow::Owned(digits.replace('_', ""))
+        } else {
+            Cow::Borrowed(digits)
+        };
+        let kind = if let Some(typed) = typed {
+            match typed.parse_value(&digits, 10, self.follows_minus(start_idx)) {
+                Some(value) => TokenKind::Literal(Literals::NumericLiteral {
+                    value,
+                    suffix: typed,
+                }),
+                None => {
+                    return Err(LexError::InvalidNumber {
+                        lexeme: text.to_string(),
+                        span: self.span_from(start),
+                        context: Some(
+                            format!("literal out of range for {}", typed.as_str()).into(),
+                        ),
+                    });
+                }
+            }
+        } else if is_float {
             // Validate the float by parsing it
-            match lexeme.parse::<f64>() {
+            match digits.parse::<f64>() {
                 Ok(f) => TokenKind::Literal(Literals::FloatLiteral(f)),
                 Err(_) => {
                     return Err(LexError::InvalidNumber {
-                        lexeme,
-                        line: start_line,
-                        column: start_col,
+                        lexeme: text.to_string(),
+                        span: self.span_from(start),
+                        context: None,
                     });
                 }
             }
         } else if is_unsigned {
-            // Try to parse as unsigned integer (remove the 'u' suffix)
-            let num_str = &lexeme[..lexeme.len() - 1];
-            match num_str.parse::<u64>() {
+            // Try to parse as unsigned integer
+            match digits.parse::<u64>() {
                 Ok(val) => TokenKind::Literal(Literals::UnsignedIntLiteral(val)),
                 Err(_) => {
                     return Err(LexError::InvalidNumber {
-                        lexeme,
-                        line: start_line,
-                        column: start_col,
+                        lexeme: text.to_string(),
+                        span: self.span_from(start),
+                        context: None,
                     });
                 }
             }
         } else {
             // Try to parse as signed integer
-            match lexeme.parse::<i64>() {
+            match digits.parse::<i64>() {
                 Ok(val) => TokenKind::Literal(Literals::IntLiteral(val)),
                 Err(_) => {
                     return Err(LexError::InvalidNumber {
-                        lexeme,
-                        line: start_line,
-                        column: start_col,
+                        lexeme: text.to_string(),
+                        span: self.span_from(start),
+                        context: None,
                     });
                 }
             }
@@ -299,8 +639,10 @@ impl Lexer {
             column_start: start_col,
             line_end: end_line,
             column_end: end_col,
+            char_offsets: None,
         };
 
+        let lexeme = self.source_lexeme(lex_start, end_idx);
         Ok(Token { kind, span, lexeme })
     }
 }
\ No newline at end of file
@@ -0,0 +1,237 @@
+//! Integer literals with a radix prefix.
+//!
+//! A literal starting with `0b` is read in base 2 and one starting with
+//! `0o` in base 8, so `0o755` is 493. The usual integer suffixes apply
+//! after the digits, so `0b1010_0001u` is an unsigned literal and `0o755u16`
+//! a `u16` one.
+//!
+//! Digits of any number literal may be separated by single underscores, as
+//! in `1_000_000`; they stay in the lexeme and are ignored in the value. A
+//! separator must be followed by a digit, so `1__0` and `1_` are errors,
+//! though one may follow a radix prefix directly, as in `0b_1010`.
+
+use std::borrow::Cow;
+
+use crate::lexer::Lexer;
+use crate::lexerror::{LexError, LexResult, NumberClass};
+use crate::token::literals::{Literals, NumericSuffix};
+use crate::token::span::Span;
+use crate::token::tokenkind::TokenKind;
+use crate::token::Token;
+
+/// A radix prefix and the base it selects.
+#[derive(Clone, Copy)]
+pub(super) struct Radix {
+    /// The letter after the leading `0`
+    letter: u8,
+    /// The base digits are read in
+    base: u32,
+    /// Name used in diagnostics
+    name: &'static str,
+}
+
+/// Radix prefixes recognized after a leading `0`.
+const RADIXES: &[Radix] = &[
+    Radix {
+        letter: b'b',
+        base: 2,
+        name: "binary",
+    },
+    Radix {
+        letter: b'o',
+        base: 8,
+        name: "octal",
+    },
+];
+
+impl Lexer {
+    /// The radix selected by a prefix at the current position, if any.
+    pub(super) fn radix_prefix(&self) -> Option<Radix> {
+        if self.stream.peek() != Some(b'0') {
+            return None;
+        }
+        let letter = self.stream.peek_n(1)?;
+        RADIXES.iter().copied().find(|radix| radix.letter == letter)
+    }
+
+    /// Tokenize an integer literal with a radix prefix such as `0b` or `0o`.
+    ///
+    /// The stream must be positioned at the leading `0` of a prefix returned
+    /// by [`radix_prefix`](Self::radix_prefix).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Token)` with `Literals::IntLiteral`, or
+    ///   `Literals::UnsignedIntLiteral` with a `u` suffix, or
+    ///   `Literals::NumericLiteral` with a type suffix such as `u8`
+    /// - `Err(LexError::InvalidNumber)` located at the prefix if no digit
+    ///   follows it, as in `0b;`
+    /// - `Err(LexError::InvalidNumber)` located at the first decimal digit
+    ///   that is not valid in the radix, as in the `2` of `0b102`; the whole
+    ///   run of identifier characters is consumed
+    /// - `Err(LexError::InvalidNumber)` located at a misplaced `_`, see
+    ///   [`check_separators`](Self::check_separators)
+    /// - `Err(LexError::InvalidNumberSuffix)` for identifier characters other
+    ///   than an allowed suffix after the digits
+    /// - `Err(LexError::InvalidNumber)` if the value does not fit the literal
+    ///   type or the type named by its suffix
+    pub(super) fn lex_radix_literal(&mut self, radix: Radix) -> LexResult<Token> {
+        let (start_idx, start_line, start_col) = self.stream.current_position();
+        self.stream.advance_n(2); // consume `0` and the radix letter
+
+        let (digits_start, digits_end) = self
+            .stream
+            .consume_while(|b| b == b'_' || (b as char).is_digit(radix.base));
+
+        let invalid = |lexer: &Lexer, at, context: String| LexError::InvalidNumber {
+            lexeme: String::from_utf8_lossy(lexer.stream.slice(start_idx, lexer.stream.index()))
+                .into_owned(),
+            span: lexer.span_from(at),
+            context: Some(context.into()),
+        };
+
+        if let Some(digit) = self.stream.peek().filter(u8::is_ascii_digit) {
+            let at = self.stream.current_position();
+            self.stream
+                .skip_while(|b| b.is_ascii_alphanumeric() || b == b'_');
+            return Err(invalid(
+                self,
+                at,
+                format!(
+                    "invalid digit '{}' in {} literal",
+                    digit as char, radix.name
+                ),
+            ));
+        }
+
+        if self
+            .stream
+            .slice(digits_start, digits_end)
+            .iter()
+            .all(|&b| b == b'_')
+        {
+            return Err(invalid(
+                self,
+                (start_idx, start_line, start_col),
+                format!(
+                    "expected at least one digit after `0{}` prefix",
+                    radix.letter as char
+                ),
+            ));
+        }
+
+        self.check_separators(start_idx, digits_start, digits_end)?;
+
+        let (suffix_start, suffix_end) = self
+            .stream
+            .consume_while(|b| b.is_ascii_alphanumeric() || b == b'_');
+        let suffix = self.stream.slice(suffix_start, suffix_end);
+        let (end_idx, end_line, end_col) = self.stream.current_position();
+        let span = Span {
+            start: start_idx,
+            end: end_idx,
+            line_start: start_line,
+            column_start: start_col,
+            line_end: end_line,
+            column_end: end_col,
+            char_offsets: None,
+        };
+        let allowed = NumberClass::Int;
+        if !suffix.is_empty() && !allowed.suffixes().iter().any(|s| s.as_bytes() == suffix) {
+            return Err(LexError::InvalidNumberSuffix {
+                suffix: String::from_utf8_lossy(suffix).into(),
+                span,
+                allowed,
+                context: None,
+            });
+        }
+
+        let digits = String::from_utf8_lossy(self.stream.slice(digits_start, digits_end));
+        let digits = if digits.contains('_') {
+            Cow::Owned(digits.replace('_', ""))
+        } else {
+            digits
+        };
+        let text = String::from_utf8_lossy(self.stream.slice(start_idx, end_idx));
+        let typed = std::str::from_utf8(suffix)
+            .ok()
+            .and_then(NumericSuffix::from_spelling);
+        let literal = if let Some(typed) = typed {
+            let negated = self.follows_minus(start_idx);
+            let Some(value) = typed.parse_value(&digits, radix.base, negated) else {
+                return Err(LexError::InvalidNumber {
+                    lexeme: text.into_owned(),
+                    span,
+                    context: Some(format!("literal out of range for {}", typed.as_str()).into()),
+                });
+            };
+            Some(Literals::NumericLiteral {
+                value,
+                suffix: typed,
+            })
+        } else if suffix == b"u" {
+            u64::from_str_radix(&digits, radix.base)
+                .ok()
+                .map(Literals::UnsignedIntLiteral)
+        } else {
+            i64::from_str_radix(&digits, radix.base)
+                .ok()
+                .map(Literals::IntLiteral)
+        };
+        let Some(literal) = literal else {
+            return Err(LexError::InvalidNumber {
+                lexeme: text.into_owned(),
+                span,
+                context: None,
+            });
+        };
+
+        Ok(Token {
+            kind: TokenKind::Literal(literal),
+            span,
+            lexeme: self.source_lexeme(start_idx, end_idx),
+        })
+    }
+    /// Check the `_` separators in the digit run `run_start..run_end` of a
+    /// number literal starting at `literal_start`.
+    ///
+    /// The stream must be positioned at `run_end`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if every `_` in the run is followed by a digit
+    /// - `Err(LexError::InvalidNumber)` located at the first `_` that is not,
+    ///   as in `1__0` or `1_u`; the rest of the literal's identifier
+    ///   characters are consumed
+    pub(super) fn check_separators(
+        &mut self,
+        literal_start: usize,
+        run_start: usize,
+        run_end: usize,
+    ) -> LexResult<()> {
+        let run = self.stream.slice(run_start, run_end);
+        let Some(offset) =
+            (0..run.len()).find(|&i| run[i] == b'_' && run.get(i + 1).is_none_or(|&b| b == b'_'))
+        else {
+            return Ok(());
+        };
+        let context = if offset + 1 < run.len() {
+            "digit separators `_` cannot be repeated"
+        } else {
+            "a number cannot end with a digit separator `_`"
+        };
+
+        // Number literals are ASCII, so each byte is one column
+        let (index, line, column) = self.stream.current_position();
+        let back = run.len() - offset;
+        let at = (index - back, line, column.saturating_sub(back));
+        self.stream
+            .skip_while(|b| b.is_ascii_alphanumeric() || b == b'_');
+        Err(LexError::InvalidNumber {
+            lexeme: String::from_utf8_lossy(self.stream.slice(literal_start, self.stream.index()))
+                .into_owned(),
+            span: self.span_from(at),
+            context: Some(context.into()),
+        })
+    }
+}
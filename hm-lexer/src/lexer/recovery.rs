@@ -0,0 +1,85 @@
+//! Error recovery.
+//!
+//! When [`LexerConfig::error_recovery`](crate::config::LexerConfig::error_recovery)
+//! is enabled, the lexer never returns an error. It skips the malformed region
+//! and emits it as a [`TokenKind::Error`] token, so the next call to
+//! `next_token` starts at a sensible boundary and every input byte is covered
+//! by some token. The error itself is kept for
+//! [`Lexer::errors`](crate::lexer::Lexer::errors).
+
+use crate::config::SyncStrategy;
+use crate::lexer::Lexer;
+use crate::lexerror::LexError;
+use crate::token::{span::Span, tokenkind::TokenKind, Token};
+
+impl Lexer {
+    /// Turn an error into a `TokenKind::Error` token covering the skipped
+    /// input, recording the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The error that was encountered
+    /// * `start` - Position `(index, line, column)` where the failing token began
+    pub(super) fn recover(&mut self, err: LexError, start: (usize, usize, usize)) -> Token {
+        let (error_start, line_start, column_start) = start;
+        // Malformed char literals, suffixed numbers, and tokens from a newer
+        // edition have already been consumed up to a sensible boundary, and
+        // an unterminated block comment up to the end of the input, so there
+        // is nothing left to skip.
+        if !matches!(
+            err,
+            LexError::InvalidCharLiteral { .. }
+                | LexError::InvalidNumberSuffix { .. }
+                | LexError::RequiresEdition { .. }
+                | LexError::UnterminatedBlockComment { .. }
+        ) {
+            self.synchronize(error_start);
+        }
+
+        let (end_idx, end_line, end_col) = self.stream.current_position();
+        let lexeme = self.source_lexeme(error_start, end_idx);
+
+        let message_code = err.code();
+        self.errors.push(err);
+        Token {
+            kind: TokenKind::Error { message_code },
+            span: Span {
+                start: error_start,
+                end: end_idx,
+                line_start,
+                column_start,
+                line_end: end_line,
+                column_end: end_col,
+                char_offsets: None,
+            },
+            lexeme,
+        }
+    }
+
+    /// Skip input after an error according to the configured [`SyncStrategy`].
+    ///
+    /// At least one byte past `error_start` is always consumed, so repeated
+    /// errors cannot stall the lexer.
+    ///
+    /// # Arguments
+    ///
+    /// * `error_start` - Byte offset where the failing token began
+    pub(super) fn synchronize(&mut self, error_start: usize) {
+        if self.stream.index() == error_start {
+            self.stream.advance();
+        }
+
+        match self.config.sync_strategy {
+            SyncStrategy::Whitespace => {
+                self.stream.skip_while(|b| !b.is_ascii_whitespace());
+            }
+            SyncStrategy::StatementTerminator => {
+                self.stream.skip_while(|b| b != b';');
+            }
+            SyncStrategy::NextLine => {
+                self.stream.skip_while(|b| b != b'\n');
+                self.stream.advance();
+            }
+        }
+    }
+}
@@ -5,7 +5,7 @@
 //! for single and multi-character tokens.
 
 use crate::charstream::CharStream;
-use crate::token::{span::Span, tokenkind::TokenKind, Token};
+use crate::token::{span::Span, static_lexeme, tokenkind::TokenKind, Token};
 
 /// A helper for constructing tokens with automatic span tracking.
 ///
@@ -41,7 +41,7 @@ impl<'a> TokenBuilder<'a> {
     ///
     /// A complete [`Token`] with proper span information
     #[inline]
-    pub fn single_char_token(self, kind: TokenKind, lexeme: &str) -> Token {
+    pub fn single_char_token(self, kind: TokenKind, lexeme: &'static str) -> Token {
         self.multi_char_token(1, kind, lexeme)
     }
 
@@ -56,7 +56,7 @@ impl<'a> TokenBuilder<'a> {
     /// # Returns
     ///
     /// A complete [`Token`] with proper span information
-    pub fn multi_char_token(self, chars: usize, kind: TokenKind, lexeme: &str) -> Token {
+    pub fn multi_char_token(self, chars: usize, kind: TokenKind, lexeme: &'static str) -> Token {
         self.stream.advance_n(chars);
         let (end_idx, end_line, end_col) = self.stream.current_position();
         Token {
@@ -68,8 +68,9 @@ impl<'a> TokenBuilder<'a> {
                 column_start: self.start_col,
                 line_end: end_line,
                 column_end: end_col,
+                char_offsets: None,
             },
-            lexeme: lexeme.to_string(),
+            lexeme: static_lexeme(lexeme),
         }
     }
 }
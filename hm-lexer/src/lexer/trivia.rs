@@ -1,9 +1,14 @@
 //! Trivia (whitespace and comments) handling.
 //!
 //! This module implements logic for skipping non-semantic elements in the
-//! source code, including whitespace and both line and block comments.
+//! source code, including whitespace and both line and block comments, and
+//! for collecting them as [`Trivia`] pieces when lexing losslessly.
 
 use crate::lexer::Lexer;
+use crate::lexerror::{LexError, LexResult};
+use crate::token::owned_lexeme;
+use crate::token::span::Span;
+use crate::token::trivia::{Trivia, TriviaKind, TriviaToken};
 
 impl Lexer {
     /// Skip whitespace and comments until meaningful content is found.
@@ -11,44 +16,223 @@ impl Lexer {
     /// Trivia includes:
     /// - Whitespace: spaces, tabs, carriage returns, newlines
     /// - Line comments: `// ...` until end of line
-    /// - Block comments: `/* ... */` with nesting support
+    /// - Block comments: `/* ... */`, which nest, so `/* a /* b */ c */` is
+    ///   a single comment
+    /// - A shebang line such as `#!/usr/bin/env hm`, only at byte offset 0
     ///
     /// The stream position advances past all trivia, leaving the cursor
     /// at either a non-trivia character or EOF.
-    pub(super) fn skip_trivia(&mut self) {
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` once the cursor is past the trivia
+    /// - `Err(LexError::UnterminatedBlockComment)` if a `/*` is never closed;
+    ///   the cursor is left on that `/*`
+    pub(super) fn skip_trivia(&mut self) -> LexResult<()> {
+        while self.trivia_piece()?.is_some() {}
+        Ok(())
+    }
+
+    /// Extract the next token along with the trivia before and after it.
+    ///
+    /// Leading trivia is everything between the cursor and the token;
+    /// trailing trivia runs up to and including the first newline after
+    /// it. Trivia at the end of the input is the leading trivia of `Eof`,
+    /// so concatenating every piece and lexeme reproduces the source.
+    ///
+    /// Tokens buffered by lookahead are discarded and lexed again, since
+    /// their trivia was skipped without being recorded.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(TriviaToken)` with the token and its trivia
+    /// - `Err(LexError)` under the same conditions as
+    ///   [`next_token`](Self::next_token); the leading trivia is consumed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::charstream::CharStream;
+    /// # use hm_lexer::lexer::Lexer;
+    /// # use hm_lexer::token::trivia::TriviaKind;
+    /// let mut lexer = Lexer::new(CharStream::from_bytes(b"  x // note\ny").unwrap());
+    /// let x = lexer.next_token_with_trivia().unwrap();
+    /// assert_eq!(x.leading[0].kind, TriviaKind::Whitespace);
+    /// assert_eq!(x.token.lexeme, "x");
+    /// let trailing: Vec<_> = x.trailing.iter().map(|t| t.kind).collect();
+    /// assert_eq!(
+    ///     trailing,
+    ///     [TriviaKind::Whitespace, TriviaKind::LineComment, TriviaKind::Newline]
+    /// );
+    /// assert!(lexer.next_token_with_trivia().unwrap().leading.is_empty());
+    /// ```
+    pub fn next_token_with_trivia(&mut self) -> LexResult<TriviaToken> {
+        if !self.pending.is_empty() {
+            let state = self.save();
+            self.restore(state);
+        }
+
+        let leading = self.collect_trivia(false);
+        let token = self.lex_token()?;
+        let trailing = if token.is_eof() {
+            Vec::new()
+        } else {
+            self.collect_trivia(true)
+        };
+        Ok(TriviaToken {
+            leading,
+            token,
+            trailing,
+        })
+    }
+
+    /// Consume trivia pieces at the cursor, stopping after the first
+    /// newline if `trailing` is set.
+    ///
+    /// An unterminated block comment also stops collection, with the cursor
+    /// on its `/*`, so that lexing the next token reports it.
+    fn collect_trivia(&mut self, trailing: bool) -> Vec<Trivia> {
+        let mut pieces = Vec::new();
         loop {
-            match self.stream.peek() {
-                None => break,
-                Some(b' ') | Some(b'\t') | Some(b'\r') | Some(b'\n') => {
-                    self.stream.advance();
-                }
-                Some(b'/') => {
-                    if self.stream.peek_n(1) == Some(b'/') {
-                        // Line comment: skip until newline
-                        self.stream.advance_n(2); // Consume 2
-                        while let Some(b) = self.stream.peek() {
-                            if b == b'\n' {
-                                break;
-                            }
-                            self.stream.advance();
-                        }
-                    } else if self.stream.peek_n(1) == Some(b'*') {
-                        // Block comment: skip until */
-                        self.stream.advance_n(2); // Consume 2
-                        while let Some(b) = self.stream.peek() {
-                            if b == b'*' && self.stream.peek_n(1) == Some(b'/') {
-                                self.stream.advance_n(2); // Consume 2
-                                break;
-                            }
-                            self.stream.advance();
-                        }
-                    } else {
-                        // Not a comment, stop skipping trivia
-                        break;
+            let (start, line_start, column_start) = self.stream.current_position();
+            let start_char = self.stream.char_index();
+            let Ok(Some(kind)) = self.trivia_piece() else {
+                break;
+            };
+            let (end, line_end, column_end) = self.stream.current_position();
+            let char_offsets = start_char.zip(self.stream.char_index());
+            pieces.push(Trivia {
+                kind,
+                span: Span {
+                    start,
+                    end,
+                    line_start,
+                    column_start,
+                    line_end,
+                    column_end,
+                    char_offsets,
+                },
+                text: owned_lexeme(&String::from_utf8_lossy(self.stream.slice(start, end))),
+            });
+            if trailing && kind == TriviaKind::Newline {
+                break;
+            }
+        }
+        pieces
+    }
+
+    /// Consume the rest of the line, stopping before its `\n` or `\r\n`.
+    fn skip_line(&mut self) {
+        let end = line_end(self.stream.as_bytes(), self.stream.index());
+        self.stream.advance_to(end);
+    }
+
+    /// Consume one piece of trivia at the cursor.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(TriviaKind))` with the kind of the piece consumed
+    /// - `Ok(None)` if the cursor is at EOF or a non-trivia byte
+    /// - `Err(LexError::UnterminatedBlockComment)` if a block comment is
+    ///   never closed; nothing is consumed
+    fn trivia_piece(&mut self) -> LexResult<Option<TriviaKind>> {
+        let Some(byte) = self.stream.peek() else {
+            return Ok(None);
+        };
+        let kind = match byte {
+            b'\n' => {
+                self.stream.advance();
+                TriviaKind::Newline
+            }
+            b'\r' if self.stream.peek_n(1) == Some(b'\n') => {
+                self.stream.advance_n(2);
+                TriviaKind::Newline
+            }
+            b' ' | b'\t' | b'\r' => {
+                let end = whitespace_end(self.stream.as_bytes(), self.stream.index());
+                self.stream.advance_to(end);
+                TriviaKind::Whitespace
+            }
+            b'/' if self.stream.peek_n(1) == Some(b'/') => {
+                // Line comment: skip until newline
+                self.skip_line();
+                TriviaKind::LineComment
+            }
+            b'#' if self.stream.index() == 0 && self.stream.peek_n(1) == Some(b'!') => {
+                // Shebang: only meaningful as the first line of a script
+                self.skip_line();
+                TriviaKind::Shebang
+            }
+            b'/' if self.stream.peek_n(1) == Some(b'*') => {
+                // Block comment: skip until the */ matching this /*, so
+                // commented-out code may itself contain block comments
+                let start = self.stream.current_position();
+                match block_comment_end(self.stream.as_bytes(), start.0) {
+                    Some(end) => self.stream.advance_to(end),
+                    None => {
+                        let span = self.span_ahead(start, self.stream.as_bytes().len() - start.0);
+                        return Err(LexError::UnterminatedBlockComment {
+                            span,
+                            context: None,
+                        });
                     }
                 }
-                _ => break,
+                TriviaKind::BlockComment
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(kind))
+    }
+}
+
+/// End of the run of spaces, tabs, and lone `\r`s starting at `start`; a
+/// `\r\n` ends the run.
+fn whitespace_end(bytes: &[u8], start: usize) -> usize {
+    let mut end = start;
+    while let Some(&b) = bytes.get(end) {
+        let ends_line = b == b'\r' && bytes.get(end + 1) == Some(&b'\n');
+        if !matches!(b, b' ' | b'\t' | b'\r') || ends_line {
+            break;
+        }
+        end += 1;
+    }
+    end
+}
+
+/// End of the line starting at `start`, before its `\n` or `\r\n`, or the
+/// end of the input.
+fn line_end(bytes: &[u8], start: usize) -> usize {
+    match memchr::memchr(b'\n', &bytes[start..]) {
+        Some(newline) if newline > 0 && bytes[start + newline - 1] == b'\r' => start + newline - 1,
+        Some(newline) => start + newline,
+        None => bytes.len(),
+    }
+}
+
+/// End of the nested block comment whose `/*` is at `start`, just past its
+/// matching `*/`, or `None` if it is never closed.
+///
+/// Only `/` and `*` can open or close a comment, so the scan jumps between
+/// them with `memchr2` instead of stepping through every byte.
+fn block_comment_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 1;
+    let mut at = start + 2;
+    while let Some(found) = memchr::memchr2(b'/', b'*', &bytes[at..]) {
+        at += found;
+        match (bytes[at], bytes.get(at + 1)) {
+            (b'/', Some(b'*')) => {
+                depth += 1;
+                at += 2;
+            }
+            (b'*', Some(b'/')) => {
+                depth -= 1;
+                at += 2;
+                if depth == 0 {
+                    return Some(at);
+                }
             }
+            _ => at += 1,
         }
     }
-}
\ No newline at end of file
+    None
+}
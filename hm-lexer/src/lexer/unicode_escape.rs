@@ -0,0 +1,95 @@
+//! `\u{...}` escapes in string and character literals.
+//!
+//! The braces hold one to six hex digits naming a Unicode scalar value, so
+//! `"\u{1F600}"` is a one-character string holding 😀. Surrogates and values
+//! above `10FFFF` are rejected, and the error's sequence carries the digits
+//! that were written.
+
+use crate::lexerror::LexError;
+use crate::token::span::Span;
+
+/// Most hex digits a `\u{...}` escape may hold.
+const MAX_DIGITS: usize = 6;
+
+/// A rejected `\u{...}` escape, not yet placed in the source.
+pub(crate) struct InvalidUnicodeEscape {
+    /// The escape as written, from the backslash; always ASCII
+    pub(crate) sequence: String,
+    /// What is wrong with it
+    context: String,
+}
+
+impl InvalidUnicodeEscape {
+    /// Turn this into a [`LexError::InvalidEscape`] covering `span`.
+    pub(crate) fn into_error(self, span: Span) -> LexError {
+        LexError::InvalidEscape {
+            sequence: self.sequence,
+            span,
+            context: Some(self.context.into()),
+        }
+    }
+}
+
+/// Decode a `\u{...}` escape.
+///
+/// # Arguments
+///
+/// * `rest` - The bytes after the escape's `\u`
+///
+/// # Returns
+///
+/// - `Ok((char, usize))` with the decoded character and the number of bytes
+///   of `rest` it spans, through the closing `}`
+/// - `Err(InvalidUnicodeEscape)` if the braces are missing, empty, hold too
+///   many or non-hex digits, or name something that is not a scalar value
+pub(crate) fn decode_unicode_escape(rest: &[u8]) -> Result<(char, usize), InvalidUnicodeEscape> {
+    let invalid = |sequence: String, context: String| InvalidUnicodeEscape { sequence, context };
+
+    if rest.first() != Some(&b'{') {
+        return Err(invalid(
+            "\\u".to_string(),
+            "expected `{` after `\\u`".to_string(),
+        ));
+    }
+    let digits_len = rest[1..]
+        .iter()
+        .take_while(|b| b.is_ascii_hexdigit())
+        .count();
+    // Hex digits are ASCII, so the slice is valid UTF-8.
+    let digits = std::str::from_utf8(&rest[1..1 + digits_len]).unwrap_or_default();
+
+    match rest.get(1 + digits_len) {
+        Some(b'}') => {}
+        Some(b) if b.is_ascii_alphanumeric() => {
+            return Err(invalid(
+                format!("\\u{{{digits}{}", *b as char),
+                format!("`{}` is not a hex digit", *b as char),
+            ));
+        }
+        _ => {
+            return Err(invalid(
+                format!("\\u{{{digits}"),
+                "unterminated unicode escape; expected `}`".to_string(),
+            ));
+        }
+    }
+
+    let sequence = format!("\\u{{{digits}}}");
+    if digits.is_empty() {
+        return Err(invalid(sequence, "empty unicode escape".to_string()));
+    }
+    if digits_len > MAX_DIGITS {
+        return Err(invalid(
+            sequence,
+            format!("unicode escape must have at most {MAX_DIGITS} hex digits"),
+        ));
+    }
+    let value = u32::from_str_radix(digits, 16).unwrap_or(u32::MAX);
+    match char::from_u32(value) {
+        Some(ch) => Ok((ch, digits_len + 2)),
+        None => Err(invalid(
+            sequence,
+            format!("`{digits}` is not a Unicode scalar value"),
+        )),
+    }
+}
@@ -3,83 +3,675 @@
 //! This module defines all possible errors that can occur during the
 //! tokenization process, with detailed location information for error reporting.
 
+use std::fmt;
+use std::ops::Range;
+
 use thiserror::Error;
 
+use crate::config::Edition;
+use crate::token::span::Span;
+
+/// Result type used throughout the lexer.
+pub type LexResult<T> = Result<T, LexError>;
+
 /// Errors that can occur during lexical analysis.
 ///
-/// All errors include line and column information to help with debugging
-/// and user-friendly error reporting.
-#[derive(Error)]
-#[cfg_attr(debug_assertions, derive(Debug))]
+/// Errors found in the source carry a [`Span`] covering the offending text,
+/// so editors can underline all of it; the message names the line and
+/// column where it starts. [`span`](Self::span), [`line`](Self::line), and
+/// [`column`](Self::column) read the location of any variant. Variants
+/// carrying a location also have an optional free-form `context` appended
+/// to their message.
+///
+/// The enum is `#[non_exhaustive]` so new error kinds can be added without
+/// breaking downstream matches.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum LexError {
     /// Unexpected character at the given position.
-    #[error("Unexpected character '{ch}' at line {line}, column {column}")]
+    #[error(
+        "Unexpected character '{ch}' at line {}, column {}{}",
+        .span.line_start,
+        .span.column_start,
+        context_suffix(.context)
+    )]
     UnexpectedCharacter {
         /// The unexpected character encountered
         ch: char,
-        /// Line number where the error occurred
-        line: usize,
-        /// Column number where the error occurred
-        column: usize,
+        /// Where the character is
+        span: Span,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
+    },
+
+    /// A character that looks like ASCII punctuation or whitespace but is
+    /// not, such as a smart quote or a non-breaking space; see
+    /// [`LOOKALIKES`](crate::grammar::LOOKALIKES).
+    #[error(
+        "Unexpected {name} '{ch}' at line {}, column {}, did you mean '{suggestion}'?{}",
+        .span.line_start,
+        .span.column_start,
+        context_suffix(.context)
+    )]
+    LookalikeCharacter {
+        /// The character found
+        ch: char,
+        /// The character's Unicode name, in lower case
+        // The path to `str` keeps serde's derive from borrowing it from the
+        // input, which would make `LexError` deserializable only from
+        // `'static` data.
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "lookalike_name"))]
+        name: &'static core::primitive::str,
+        /// The ASCII character it was probably meant to be
+        suggestion: char,
+        /// Where the character is
+        span: Span,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
     },
 
     /// Unterminated string literal.
-    #[error("Unterminated string literal at line {line}, column {column}")]
+    #[error(
+        "Unterminated string literal at line {}, column {}{}",
+        .span.line_start,
+        .span.column_start,
+        context_suffix(.context)
+    )]
     UnterminatedString {
-        /// Line number where the string started
-        line: usize,
-        /// Column number where the string started
-        column: usize,
+        /// From the opening quote to the end of the input, or to the raw
+        /// newline that ended the string
+        span: Span,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
+    },
+
+    /// Block comment with no closing `*/` before the end of the input.
+    #[error(
+        "Unterminated block comment at line {}, column {}{}",
+        .span.line_start,
+        .span.column_start,
+        context_suffix(.context)
+    )]
+    UnterminatedBlockComment {
+        /// From the `/*` to the end of the input
+        span: Span,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
     },
 
     /// Invalid escape sequence in a string.
-    #[error("Invalid escape sequence '{sequence}' at line {line}, column {column}")]
+    #[error(
+        "Invalid escape sequence '{sequence}' at line {}, column {}{}",
+        .span.line_start,
+        .span.column_start,
+        context_suffix(.context)
+    )]
     InvalidEscape {
         /// The invalid escape sequence text
         sequence: String,
-        /// Line number where the escape sequence was found
-        line: usize,
-        /// Column number where the escape sequence was found
-        column: usize,
+        /// From the literal's opening quote through the escape sequence; the
+        /// whole literal for a triple-quoted string
+        span: Span,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
+    },
+
+    /// Malformed character literal.
+    #[error(
+        "Invalid character literal at line {}, column {}: {reason}{}",
+        .span.line_start,
+        .span.column_start,
+        context_suffix(.context)
+    )]
+    InvalidCharLiteral {
+        /// What is wrong with the literal
+        reason: CharLiteralReason,
+        /// The malformed literal, from the opening quote through the closing
+        /// quote if there is one
+        span: Span,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
     },
 
     /// Invalid number format.
-    #[error("Invalid number format '{lexeme}' at line {line}, column {column}")]
+    #[error(
+        "Invalid number format '{lexeme}' at line {}, column {}{}",
+        .span.line_start,
+        .span.column_start,
+        context_suffix(.context)
+    )]
     InvalidNumber {
         /// The malformed number lexeme
         lexeme: String,
-        /// Line number where the number started
-        line: usize,
-        /// Column number where the number started
-        column: usize,
+        /// From where the problem was found, the literal's start unless the
+        /// context says otherwise, to the end of the lexeme
+        span: Span,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
+    },
+
+    /// Unknown suffix on a numeric literal, such as `10q`, `3.0u`, or the
+    /// identifier glued to `123abc`.
+    #[error(
+        "Invalid suffix '{suffix}' on number at line {}, column {}: {}{}",
+        .span.line_end,
+        .span.column_end - .suffix.len(),
+        expected_suffixes(.allowed.suffixes()),
+        context_suffix(.context)
+    )]
+    InvalidNumberSuffix {
+        /// The identifier characters following the number
+        suffix: Box<str>,
+        /// The whole literal, suffix included. The suffix is ASCII and ends
+        /// the literal, so it occupies the last `suffix.len()` bytes and
+        /// columns of the span.
+        span: Span,
+        /// The class of literal, which decides the suffixes it accepts
+        allowed: NumberClass,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
     },
 
     /// Unexpected end of file.
-    #[error("Unexpected end of file at line {line}, column {column}")]
+    #[error(
+        "Unexpected end of file at line {}, column {}{}",
+        .span.line_start,
+        .span.column_start,
+        context_suffix(.context)
+    )]
     UnexpectedEof {
-        /// Line number where EOF was encountered
-        line: usize,
-        /// Column number where EOF was encountered
-        column: usize,
+        /// Where the input ended
+        span: Span,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
     },
 
     /// Invalid UTF-8 sequence encountered.
-    #[error("Invalid UTF-8 sequence at line {line}, column {column}")]
+    #[error(
+        "Invalid UTF-8 sequence at line {}, column {}{}",
+        .span.line_start,
+        .span.column_start,
+        context_suffix(.context)
+    )]
     InvalidUtf8 {
-        /// Line number where the invalid UTF-8 was found
-        line: usize,
-        /// Column number where the invalid UTF-8 was found
-        column: usize,
+        /// From the literal's opening quote through the first invalid byte;
+        /// the whole literal for a triple-quoted string
+        span: Span,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
+    },
+
+    /// A token form newer than the configured
+    /// [`LexerConfig::edition`](crate::config::LexerConfig::edition).
+    #[error(
+        "{feature} require edition {required} at line {}, column {}{}",
+        .span.line_start,
+        .span.column_start,
+        context_suffix(.context)
+    )]
+    RequiresEdition {
+        /// The kind of token, e.g. "Binary literals"
+        feature: String,
+        /// The first edition accepting it
+        required: Edition,
+        /// The whole token
+        span: Span,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
     },
 
     /// Empty input provided.
+    ///
+    /// No longer produced: empty input lexes to a lone `Eof` token. The
+    /// variant is kept so error codes and encoded streams stay stable.
     #[error("Cannot create CharStream from empty input")]
     EmptyInput,
 
-    /// Input too large to process.
-    #[error("Input too large to process: {size} bytes")]
+    /// Input too large to process; see
+    /// [`MAX_INPUT_LEN`](crate::charstream::MAX_INPUT_LEN).
+    #[error("Input too large to process: {size} bytes{}", context_suffix(.context))]
     InputTooLarge {
         /// The size of the input in bytes
         size: usize,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
+    },
+
+    /// The input could not be read.
+    #[error("Failed to read '{path}': {message}{}", context_suffix(.context))]
+    Io {
+        /// The file being read
+        path: String,
+        /// The underlying I/O error's message
+        message: String,
+        /// Optional free-form detail about the error
+        context: Option<Box<str>>,
     },
 }
+
+/// Why a character literal was rejected.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::lexerror::{CharLiteralReason, LexError};
+/// let err = hm_lexer::tokenize("var c = 'ab';").unwrap_err();
+/// let LexError::InvalidCharLiteral { reason, span, .. } = err else {
+///     panic!("expected an invalid char literal");
+/// };
+/// assert!(reason == CharLiteralReason::TooManyCharacters);
+/// assert_eq!((span.start, span.end), (8, 12));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CharLiteralReason {
+    /// More than one character between the quotes, e.g. `'ab'`
+    TooManyCharacters,
+    /// Nothing between the quotes: `''`
+    Empty,
+    /// No closing quote before the end of the line or input
+    MissingClosingQuote,
+    /// A byte literal such as `b'é'` holding a character outside ASCII
+    NonAscii,
+}
+
+impl fmt::Display for CharLiteralReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CharLiteralReason::TooManyCharacters => "too many characters",
+            CharLiteralReason::Empty => "empty character literal",
+            CharLiteralReason::MissingClosingQuote => "missing closing quote",
+            CharLiteralReason::NonAscii => "byte literal must be ASCII",
+        })
+    }
+}
+
+/// The class of a numeric literal, which decides the suffixes it accepts.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::lexerror::{LexError, NumberClass};
+/// let err = hm_lexer::tokenize("x = 3.0u;").unwrap_err();
+/// let LexError::InvalidNumberSuffix { allowed, .. } = err else {
+///     panic!("expected an invalid suffix");
+/// };
+/// assert_eq!(allowed, NumberClass::Float);
+/// assert_eq!(allowed.suffixes(), ["f32", "f64"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NumberClass {
+    /// An integer literal; see [`INT_SUFFIXES`](crate::grammar::INT_SUFFIXES)
+    Int,
+    /// A float literal; see [`FLOAT_SUFFIXES`](crate::grammar::FLOAT_SUFFIXES)
+    Float,
+}
+
+impl NumberClass {
+    /// The suffixes a literal of this class accepts.
+    pub fn suffixes(self) -> &'static [&'static str] {
+        match self {
+            NumberClass::Int => crate::grammar::INT_SUFFIXES,
+            NumberClass::Float => crate::grammar::FLOAT_SUFFIXES,
+        }
+    }
+}
+
+/// A stable, machine-readable identifier for each kind of [`LexError`].
+///
+/// Codes are carried by [`TokenKind::Error`](crate::token::tokenkind::TokenKind::Error)
+/// tokens in recovery mode and can be shown to users alongside messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorCode {
+    /// See [`LexError::UnexpectedCharacter`]
+    UnexpectedCharacter,
+    /// See [`LexError::UnterminatedString`]
+    UnterminatedString,
+    /// See [`LexError::InvalidEscape`]
+    InvalidEscape,
+    /// See [`LexError::InvalidNumber`]
+    InvalidNumber,
+    /// See [`LexError::UnexpectedEof`]
+    UnexpectedEof,
+    /// See [`LexError::InvalidUtf8`]
+    InvalidUtf8,
+    /// See [`LexError::EmptyInput`]
+    EmptyInput,
+    /// See [`LexError::InputTooLarge`]
+    InputTooLarge,
+    /// See [`LexError::InvalidCharLiteral`]
+    InvalidCharLiteral,
+    /// See [`LexError::InvalidNumberSuffix`]
+    InvalidNumberSuffix,
+    /// See [`LexError::UnterminatedBlockComment`]
+    UnterminatedBlockComment,
+    /// See [`LexError::LookalikeCharacter`]
+    LookalikeCharacter,
+    /// See [`LexError::Io`]
+    Io,
+    /// See [`LexError::RequiresEdition`]
+    RequiresEdition,
+}
+
+impl ErrorCode {
+    /// Every error code, in declaration order.
+    pub const ALL: [ErrorCode; 14] = [
+        ErrorCode::UnexpectedCharacter,
+        ErrorCode::UnterminatedString,
+        ErrorCode::InvalidEscape,
+        ErrorCode::InvalidNumber,
+        ErrorCode::UnexpectedEof,
+        ErrorCode::InvalidUtf8,
+        ErrorCode::EmptyInput,
+        ErrorCode::InputTooLarge,
+        ErrorCode::InvalidCharLiteral,
+        ErrorCode::InvalidNumberSuffix,
+        ErrorCode::UnterminatedBlockComment,
+        ErrorCode::LookalikeCharacter,
+        ErrorCode::Io,
+        ErrorCode::RequiresEdition,
+    ];
+
+    /// Look up a code by its textual form, the inverse of [`Self::as_str`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::lexerror::ErrorCode;
+    /// assert!(ErrorCode::from_code("L0009") == Some(ErrorCode::InvalidCharLiteral));
+    /// assert!(ErrorCode::from_code("E0001").is_none());
+    /// ```
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == code)
+    }
+
+    /// Return the stable textual form of this code (e.g. `"L0001"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::UnexpectedCharacter => "L0001",
+            ErrorCode::UnterminatedString => "L0002",
+            ErrorCode::InvalidEscape => "L0003",
+            ErrorCode::InvalidNumber => "L0004",
+            ErrorCode::UnexpectedEof => "L0005",
+            ErrorCode::InvalidUtf8 => "L0006",
+            ErrorCode::EmptyInput => "L0007",
+            ErrorCode::InputTooLarge => "L0008",
+            ErrorCode::InvalidCharLiteral => "L0009",
+            ErrorCode::InvalidNumberSuffix => "L0010",
+            ErrorCode::UnterminatedBlockComment => "L0011",
+            ErrorCode::LookalikeCharacter => "L0012",
+            ErrorCode::Io => "L0013",
+            ErrorCode::RequiresEdition => "L0014",
+        }
+    }
+}
+
+/// Describe the suffixes a numeric literal accepts.
+pub(crate) fn expected_suffixes(allowed: &[&str]) -> String {
+    if allowed.is_empty() {
+        return "this literal takes no suffix".to_string();
+    }
+    let list: Vec<String> = allowed.iter().map(|s| format!("'{s}'")).collect();
+    format!("expected one of {}", list.join(", "))
+}
+
+/// Deserialize the name of a lookalike character as its entry in
+/// [`LOOKALIKES`](crate::grammar::LOOKALIKES).
+#[cfg(feature = "serde")]
+fn lookalike_name<'de, D>(deserializer: D) -> Result<&'static str, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    let name = String::deserialize(deserializer)?;
+    crate::grammar::LOOKALIKES
+        .iter()
+        .map(|&(_, known, _)| known)
+        .find(|known| *known == name)
+        .ok_or_else(|| serde::de::Error::custom(format!("unknown lookalike '{name}'")))
+}
+
+/// Format an error's optional context as a message suffix.
+fn context_suffix(context: &Option<Box<str>>) -> String {
+    match context {
+        Some(context) => format!(" ({context})"),
+        None => String::new(),
+    }
+}
+
+impl LexError {
+    /// Attach free-form context to this error, replacing any existing context.
+    ///
+    /// Variants without a context field are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::lexerror::LexError;
+    /// let err = hm_lexer::tokenize("x = @").unwrap_err().with_context("while lexing a REPL cell");
+    /// assert!(err.to_string().ends_with("(while lexing a REPL cell)"));
+    /// ```
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        if let Some(slot) = self.context_mut() {
+            *slot = Some(context.into().into_boxed_str());
+        }
+        self
+    }
+
+    /// The free-form context attached to this error, if any.
+    pub fn context(&self) -> Option<&str> {
+        match self {
+            LexError::UnexpectedCharacter { context, .. }
+            | LexError::LookalikeCharacter { context, .. }
+            | LexError::UnterminatedString { context, .. }
+            | LexError::UnterminatedBlockComment { context, .. }
+            | LexError::InvalidEscape { context, .. }
+            | LexError::InvalidCharLiteral { context, .. }
+            | LexError::InvalidNumber { context, .. }
+            | LexError::InvalidNumberSuffix { context, .. }
+            | LexError::UnexpectedEof { context, .. }
+            | LexError::InvalidUtf8 { context, .. }
+            | LexError::RequiresEdition { context, .. }
+            | LexError::InputTooLarge { context, .. }
+            | LexError::Io { context, .. } => context.as_deref(),
+            LexError::EmptyInput => None,
+        }
+    }
+
+    fn context_mut(&mut self) -> Option<&mut Option<Box<str>>> {
+        match self {
+            LexError::UnexpectedCharacter { context, .. }
+            | LexError::LookalikeCharacter { context, .. }
+            | LexError::UnterminatedString { context, .. }
+            | LexError::UnterminatedBlockComment { context, .. }
+            | LexError::InvalidEscape { context, .. }
+            | LexError::InvalidCharLiteral { context, .. }
+            | LexError::InvalidNumber { context, .. }
+            | LexError::InvalidNumberSuffix { context, .. }
+            | LexError::UnexpectedEof { context, .. }
+            | LexError::InvalidUtf8 { context, .. }
+            | LexError::RequiresEdition { context, .. }
+            | LexError::InputTooLarge { context, .. }
+            | LexError::Io { context, .. } => Some(context),
+            LexError::EmptyInput => None,
+        }
+    }
+
+    /// The source text this error covers, or `None` for errors about the
+    /// input as a whole.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let err = hm_lexer::tokenize("x = 1__000;").unwrap_err();
+    /// let span = err.span().unwrap();
+    /// assert_eq!((span.start, span.end), (5, 10));
+    /// assert_eq!((err.line(), err.column()), (Some(1), Some(6)));
+    /// ```
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            LexError::UnexpectedCharacter { span, .. }
+            | LexError::LookalikeCharacter { span, .. }
+            | LexError::UnterminatedString { span, .. }
+            | LexError::UnterminatedBlockComment { span, .. }
+            | LexError::InvalidEscape { span, .. }
+            | LexError::InvalidCharLiteral { span, .. }
+            | LexError::InvalidNumber { span, .. }
+            | LexError::InvalidNumberSuffix { span, .. }
+            | LexError::UnexpectedEof { span, .. }
+            | LexError::InvalidUtf8 { span, .. }
+            | LexError::RequiresEdition { span, .. } => Some(*span),
+            LexError::EmptyInput | LexError::InputTooLarge { .. } | LexError::Io { .. } => None,
+        }
+    }
+
+    /// Move the location carried by the error down by `lines` lines and
+    /// forward by `bytes` bytes; see [`Span::shift`].
+    pub(crate) fn shift(mut self, bytes: usize, lines: usize) -> Self {
+        if let Some(span) = self.span_mut() {
+            span.shift(bytes, lines);
+        }
+        self
+    }
+
+    /// Mutable access to the span returned by [`span`](Self::span).
+    pub(crate) fn span_mut(&mut self) -> Option<&mut Span> {
+        match self {
+            LexError::UnexpectedCharacter { span, .. }
+            | LexError::LookalikeCharacter { span, .. }
+            | LexError::UnterminatedString { span, .. }
+            | LexError::UnterminatedBlockComment { span, .. }
+            | LexError::InvalidEscape { span, .. }
+            | LexError::InvalidCharLiteral { span, .. }
+            | LexError::InvalidNumber { span, .. }
+            | LexError::InvalidNumberSuffix { span, .. }
+            | LexError::UnexpectedEof { span, .. }
+            | LexError::InvalidUtf8 { span, .. }
+            | LexError::RequiresEdition { span, .. } => Some(span),
+            LexError::EmptyInput | LexError::InputTooLarge { .. } | LexError::Io { .. } => None,
+        }
+    }
+
+    /// The line named in this error's message, usually where its span
+    /// starts.
+    pub fn line(&self) -> Option<usize> {
+        self.location().map(|(line, _)| line)
+    }
+
+    /// The column named in this error's message, usually where its span
+    /// starts.
+    pub fn column(&self) -> Option<usize> {
+        self.location().map(|(_, column)| column)
+    }
+
+    /// The line and column named in this error's message. An invalid suffix
+    /// is reported at the suffix, which ends the span.
+    fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            LexError::InvalidNumberSuffix { suffix, span, .. } => {
+                Some((span.line_end, span.column_end - suffix.len()))
+            }
+            _ => self.span().map(|span| (span.line_start, span.column_start)),
+        }
+    }
+
+    /// The bytes a diagnostic underlines: the span, except that an invalid
+    /// suffix is underlined on its own.
+    pub(crate) fn highlighted(&self) -> Option<Range<usize>> {
+        match self {
+            LexError::InvalidNumberSuffix { suffix, span, .. } => {
+                Some(span.end - suffix.len()..span.end)
+            }
+            _ => self.span().map(|span| span.start..span.end),
+        }
+    }
+
+    /// Return the [`ErrorCode`] identifying this kind of error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            LexError::UnexpectedCharacter { .. } => ErrorCode::UnexpectedCharacter,
+            LexError::LookalikeCharacter { .. } => ErrorCode::LookalikeCharacter,
+            LexError::UnterminatedString { .. } => ErrorCode::UnterminatedString,
+            LexError::UnterminatedBlockComment { .. } => ErrorCode::UnterminatedBlockComment,
+            LexError::InvalidEscape { .. } => ErrorCode::InvalidEscape,
+            LexError::InvalidCharLiteral { .. } => ErrorCode::InvalidCharLiteral,
+            LexError::InvalidNumber { .. } => ErrorCode::InvalidNumber,
+            LexError::InvalidNumberSuffix { .. } => ErrorCode::InvalidNumberSuffix,
+            LexError::UnexpectedEof { .. } => ErrorCode::UnexpectedEof,
+            LexError::InvalidUtf8 { .. } => ErrorCode::InvalidUtf8,
+            LexError::RequiresEdition { .. } => ErrorCode::RequiresEdition,
+            LexError::EmptyInput => ErrorCode::EmptyInput,
+            LexError::InputTooLarge { .. } => ErrorCode::InputTooLarge,
+            LexError::Io { .. } => ErrorCode::Io,
+        }
+    }
+}
+
+/// Render `error` the way rustc does, with the offending line of `source`
+/// underlined.
+///
+/// The first line is the error code and message. Errors with a span follow
+/// it with the line their span starts on, and carets under the spanned text;
+/// a span running onto later lines is underlined to the end of its first
+/// line. Tabs before the span are kept so the carets line up with the text.
+///
+/// # Arguments
+///
+/// * `error` - The error to render
+/// * `source` - The source text the error was reported for
+///
+/// # Returns
+///
+/// The rendered error, ending in a newline
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::lexerror::render;
+/// let source = "var x = 1;\nvar s = \"open";
+/// let err = hm_lexer::tokenize(source).unwrap_err();
+/// let expected = concat!(
+///     "error[L0002]: Unterminated string literal at line 2, column 9\n",
+///     "  |\n",
+///     "2 | var s = \"open\n",
+///     "  |         ^^^^^\n",
+/// );
+/// assert_eq!(render(&err, source), expected);
+/// ```
+pub fn render(error: &LexError, source: &str) -> String {
+    let mut out = format!("error[{}]: {error}\n", error.code().as_str());
+    let Some(range) = error.highlighted() else {
+        return out;
+    };
+
+    let floor = |mut offset: usize| {
+        offset = offset.min(source.len());
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    };
+    let start = floor(range.start);
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[start..]
+        .find('\n')
+        .map_or(source.len(), |i| start + i);
+    let line = source[line_start..line_end].trim_end_matches('\r');
+    let end = floor(range.end).min(line_start + line.len()).max(start);
+
+    let number = source[..line_start].matches('\n').count() + 1;
+    let gutter = " ".repeat(number.to_string().len());
+    let indent: String = source[line_start..start]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let carets = "^".repeat(source[start..end].chars().count().max(1));
+    out.push_str(&format!(
+        "{gutter} |\n{number} | {line}\n{gutter} | {indent}{carets}\n"
+    ));
+    out
+}
@@ -11,7 +11,41 @@
 //! - [`token::Token`]: Represents a single token with kind, span, and lexeme
 //! - [`token::tokenkind::TokenKind`]: Enumeration of all possible token types
 //! - [`token::span::Span`]: Tracks byte offsets and line/column positions
+//! - [`token::trivia::TriviaToken`]: A token with its whitespace and comments, for lossless lexing
+//! - [`lineindex::LineIndex`]: On-demand line/column lookup for offset-only spans
+//! - [`source::Source`]: Source text that can be sliced with `&source[span]`
+//! - [`source::SourceFile`]: Named real or virtual files, with provenance for generated code
+//! - [`shared::SharedTokens`]: Tokens sharing an `Arc<SourceFile>` across threads and caches
+//! - [`sourcemap::SourceMap`]: Registry of a compilation's files; resolves `FileSpan`s to `file:line:column`
+//! - [`project`]: Lexes a root file and every module it imports, via a resolver callback
+//! - [`intern::Interner`]: Identifier names stored once and addressed by copyable `Symbol`s
+//! - [`config::LexerConfig`]: Options controlling lexer behavior, assembled
+//!   with [`config::LexerBuilder`]
+//! - [`grammar`]: Keyword and operator listings and the dispatch graph
+//! - [`directive::Directive`]: Typed `// hm:` and `// hm-lint:` comment directives
+//! - [`deprecation::DeprecationTable`]: Deprecated spellings reported as warnings
+//! - [`keywordtable::KeywordTable`]: Built-in keywords, extensible with new or contextual ones
 //! - [`lexerror::LexError`]: Error types that can occur during tokenization
+//! - [`lexerror::render`]: An error with its source line underlined, as rustc prints it
+//! - `diagnostic`: `miette::Diagnostic` for `LexError`, with labels and help (feature `miette`)
+//! - [`codec`]: Compact binary encoding for caching token streams
+//! - [`snapshot`]: Human-readable `.tokens` format for golden tests and bug reports
+//! - `display`: Grapheme-cluster display columns (feature `graphemes`)
+//! - [`highlight`]: Classified byte ranges for editor and web highlighting
+//! - [`stats::LexStats`]: Token, comment, and identifier statistics for a source
+//! - `allocstats`: Counting global allocator for lexer measurements (feature `alloc-stats`)
+//! - [`textmate`]: TextMate scopes and `.tmLanguage.json` generation for editors
+//! - [`tokensource::TokenSource`]: Parser-facing cursor with lookahead over any token feed
+//! - [`tokenstream::TokenStream`]: Eagerly lexed tokens consumed through the same cursor
+//! - [`visitor::TokenVisitor`]: Per-category callbacks driven by [`visitor::walk`]
+//! - [`markdown`]: Lexing fenced Hummingbird blocks in Markdown documentation
+//! - [`overlay`]: Lexing a file with unsaved in-memory edits applied
+//! - [`minimize`]: Shrinks inputs that trigger lexer bugs to minimal reproducers
+//! - [`tokenize`]: One-call helper that lexes a whole source into a `Vec<Token>` and its `Interner`
+//! - [`tokenize_bytes`]: Like [`tokenize`], for bytes not yet known to be UTF-8
+//! - [`tokenize_borrowed`]: Like [`tokenize`], with lexemes borrowed from the source
+//! - [`lex_with_trivia`]: Like [`tokenize`], keeping whitespace and comments on each token
+//! - [`lex_parallel`]: Like [`tokenize`], lexing chunks of a large source on several threads
 //!
 //! # Example
 //!
@@ -33,14 +67,314 @@
 //! # }
 //! ```
 
+/// Allocation counters for measuring the lexer.
+#[cfg(feature = "alloc-stats")]
+pub mod allocstats;
+
 /// Character stream for byte-level input processing.
 pub mod charstream;
 
+/// Lexer configuration options.
+pub mod config;
+
+/// Compact binary encoding for token streams.
+pub mod codec;
+
+/// Deprecation warnings for old keyword and operator spellings.
+pub mod deprecation;
+
+/// `miette` diagnostics for lexer errors.
+#[cfg(feature = "miette")]
+mod diagnostic;
+
+/// Tool directives embedded in comments.
+pub mod directive;
+
+/// Grapheme-aware display columns for diagnostics.
+#[cfg(feature = "graphemes")]
+pub mod display;
+
+/// Heuristics for pre-sizing token buffers.
+pub mod estimate;
+
+/// Introspection of the supported lexical grammar.
+pub mod grammar;
+
+/// Protocol-agnostic syntax highlighting.
+pub mod highlight;
+
+/// Interning of identifier names.
+pub mod intern;
+
+/// Configurable keyword spellings.
+pub mod keywordtable;
+
 /// Main lexer implementation for tokenization.
 pub mod lexer;
 
 /// Error types for lexical analysis.
 pub mod lexerror;
 
+/// Extraction and lexing of code blocks in Markdown.
+pub mod markdown;
+
+/// Byte offset to line and column mapping.
+pub mod lineindex;
+
+/// Reduction of failing inputs to minimal reproducers.
+pub mod minimize;
+
+/// In-memory patches for lexing unsaved editor buffers.
+pub mod overlay;
+
+/// Chunked lexing of large sources on several threads.
+pub mod parallel;
+
+/// Import-following lexing of whole programs.
+pub mod project;
+
+/// Thread-shareable token streams backed by a reference-counted source.
+pub mod shared;
+
+/// Canonical text format for token streams.
+pub mod snapshot;
+
+/// Source text wrapper indexable by [`Span`](token::span::Span), and named
+/// source files.
+pub mod source;
+
+/// File registry for multi-file compilations.
+pub mod sourcemap;
+
+/// Source statistics gathered while lexing.
+pub mod stats;
+
+/// TextMate scope mapping and grammar generation.
+pub mod textmate;
+
 /// Token types and related structures.
 pub mod token;
+
+/// Parser feed interface shared by lazy and eager token sources.
+pub mod tokensource;
+
+/// Eagerly lexed token queues.
+pub mod tokenstream;
+
+/// Visitor-style traversal of token lists.
+pub mod visitor;
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use charstream::CharStream;
+use intern::Interner;
+use lexer::Lexer;
+use lexerror::LexResult;
+use token::trivia::TriviaToken;
+use token::{BorrowedToken, Token};
+
+pub use estimate::estimate_token_count;
+pub use parallel::lex_parallel;
+
+/// Version of this lexer, recorded in serialized token streams so caches
+/// written by another version are rejected instead of misread.
+///
+/// The crate version followed by the [`grammar::fingerprint`] of this build,
+/// so it changes with every grammar or kind id change, not only with
+/// releases.
+///
+/// # Example
+///
+/// ```
+/// let version = hm_lexer::lexer_version();
+/// assert!(version.starts_with(concat!(env!("CARGO_PKG_VERSION"), "+")));
+/// ```
+pub fn lexer_version() -> &'static str {
+    static VERSION: OnceLock<String> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        format!(
+            "{}+{:016x}",
+            env!("CARGO_PKG_VERSION"),
+            grammar::fingerprint()
+        )
+    })
+}
+
+/// Lex an entire source string into a vector of tokens.
+///
+/// The returned vector always ends with the `Eof` token. Its capacity is
+/// pre-sized with [`estimate_token_count`] to avoid repeated reallocation on
+/// large inputs. Lexing stops at the first error. The tokens come with the
+/// [`Interner`] holding the names their identifier symbols stand for.
+///
+/// # Arguments
+///
+/// * `source` - The source code to tokenize
+///
+/// # Returns
+///
+/// - `Ok((Vec<Token>, Interner))` containing every token followed by `Eof`,
+///   and the interned identifier names
+/// - `Err(LexError)` if the input contains invalid tokens
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::tokenize;
+/// # use hm_lexer::token::tokenkind::TokenKind;
+/// let (tokens, names) = tokenize("var x = 42;").unwrap();
+/// assert_eq!(tokens.len(), 6);
+/// assert!(tokens.last().unwrap().is_eof());
+///
+/// let TokenKind::Identifier(x) = tokens[1].kind else {
+///     panic!("expected an identifier");
+/// };
+/// assert_eq!(names.resolve(x), "x");
+/// ```
+pub fn tokenize(source: &str) -> LexResult<(Vec<Token>, Interner)> {
+    tokenize_bytes(source.as_bytes())
+}
+
+/// Lex an entire source given as bytes into a vector of tokens.
+///
+/// Behaves like [`tokenize`], for input read from a file or socket without
+/// checking it is UTF-8 first. Invalid UTF-8 is only an error where it
+/// appears: inside a string literal it is
+/// [`LexError::InvalidUtf8`](lexerror::LexError::InvalidUtf8), elsewhere an
+/// unexpected character.
+///
+/// # Arguments
+///
+/// * `source` - The source code to tokenize
+///
+/// # Returns
+///
+/// - `Ok((Vec<Token>, Interner))` containing every token followed by `Eof`,
+///   and the interned identifier names
+/// - `Err(LexError)` if the input contains invalid tokens
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::lexerror::LexError;
+/// # use hm_lexer::tokenize_bytes;
+/// let (tokens, _) = tokenize_bytes(b"var x = 42;").unwrap();
+/// assert!(tokens.last().unwrap().is_eof());
+///
+/// let err = tokenize_bytes(b"s = \"\xFF\";").unwrap_err();
+/// assert!(matches!(err, LexError::InvalidUtf8 { .. }));
+/// ```
+pub fn tokenize_bytes(source: &[u8]) -> LexResult<(Vec<Token>, Interner)> {
+    let mut lexer = Lexer::new(CharStream::from_bytes(source)?);
+    let mut tokens = Vec::with_capacity(estimate_token_count(source));
+
+    loop {
+        let token = lexer.next_token()?;
+        let is_eof = token.is_eof();
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+
+    Ok((tokens, lexer.into_interner()))
+}
+
+/// Lex an entire source string into tokens that borrow their lexemes from
+/// it.
+///
+/// Behaves like [`tokenize`], but the returned tokens hold `&str` slices of
+/// `source` instead of owned copies, so a large file's token list does not
+/// duplicate its text. The lexer runs with
+/// [`LexerConfig::lazy_lexemes`](config::LexerConfig::lazy_lexemes), so no
+/// lexeme is built only to be thrown away; what is still allocated is the
+/// lexer's copy of the input, each distinct identifier name in the returned
+/// [`Interner`], and the values carried by token kinds, such as decoded
+/// strings. Convert individual tokens with
+/// [`BorrowedToken::into_owned`] when they need to outlive `source`.
+///
+/// # Arguments
+///
+/// * `source` - The source code to tokenize
+///
+/// # Returns
+///
+/// - `Ok((Vec<BorrowedToken>, Interner))` containing every token followed
+///   by `Eof`, and the interned identifier names
+/// - `Err(LexError)` if the input contains invalid tokens
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::tokenize_borrowed;
+/// let source = "var x = 42;";
+/// let (tokens, _) = tokenize_borrowed(source).unwrap();
+/// let lexemes: Vec<&str> = tokens.iter().map(|t| t.lexeme).collect();
+/// assert_eq!(lexemes, ["var", "x", "=", "42", ";", ""]);
+/// ```
+pub fn tokenize_borrowed(source: &str) -> LexResult<(Vec<BorrowedToken<'_>>, Interner)> {
+    let mut lexer = Lexer::builder()
+        .lazy_lexemes(true)
+        .build(CharStream::from_bytes(source.as_bytes())?);
+    let mut tokens = Vec::with_capacity(estimate_token_count(source.as_bytes()));
+
+    loop {
+        let Token { kind, span, .. } = lexer.next_token()?;
+        let is_eof = matches!(kind, token::tokenkind::TokenKind::Eof);
+        tokens.push(BorrowedToken {
+            kind,
+            span,
+            lexeme: &source[span.start..span.end],
+        });
+        if is_eof {
+            break;
+        }
+    }
+
+    Ok((tokens, lexer.into_interner()))
+}
+
+/// Lex an entire source string, keeping the trivia around every token.
+///
+/// Like [`tokenize`], the result ends with `Eof` and lexing stops at the
+/// first error. Concatenating each token's leading trivia, lexeme, and
+/// trailing trivia reproduces `source` exactly; see
+/// [`reconstruct`](token::trivia::reconstruct).
+///
+/// # Arguments
+///
+/// * `source` - The source code to tokenize
+///
+/// # Returns
+///
+/// - `Ok((Vec<TriviaToken>, Interner))` containing every token followed by
+///   `Eof`, and the interned identifier names
+/// - `Err(LexError)` if the input contains invalid tokens
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::lex_with_trivia;
+/// # use hm_lexer::token::trivia::TriviaKind;
+/// let (tokens, _) = lex_with_trivia("x = 1; // done\n").unwrap();
+/// assert_eq!(tokens.len(), 5);
+/// let semicolon = &tokens[3];
+/// assert_eq!(semicolon.trailing[1].kind, TriviaKind::LineComment);
+/// assert_eq!(semicolon.trailing[1].text, "// done");
+/// ```
+pub fn lex_with_trivia(source: &str) -> LexResult<(Vec<TriviaToken>, Interner)> {
+    let mut lexer = Lexer::new(CharStream::from_str(source)?);
+    let mut tokens = Vec::with_capacity(estimate_token_count(source.as_bytes()));
+
+    loop {
+        let token = lexer.next_token_with_trivia()?;
+        let is_eof = token.token.is_eof();
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+
+    Ok((tokens, lexer.into_interner()))
+}
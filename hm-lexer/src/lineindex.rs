@@ -0,0 +1,151 @@
+//! On-demand line and column lookup.
+//!
+//! Tracking lines and columns for every byte is a measurable share of the
+//! lexer's hot loop, yet most tokens never appear in a diagnostic. With
+//! [`LexerConfig::offsets_only`](crate::config::LexerConfig::offsets_only)
+//! the lexer records byte offsets alone, and a [`LineIndex`] built over the
+//! same source fills in line and column for the few spans that need them.
+
+use std::borrow::Cow;
+
+use crate::config::{ColumnCounter, ColumnUnit, LineEndings};
+use crate::lexerror::LexError;
+use crate::token::span::Span;
+
+/// The start offset of every line in a source, for mapping byte offsets to
+/// 1-based lines and columns.
+///
+/// Building the index scans the source once; each lookup is a binary search
+/// over line starts plus a scan of the line up to the offset.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::charstream::CharStream;
+/// # use hm_lexer::config::LexerConfig;
+/// # use hm_lexer::lexer::Lexer;
+/// # use hm_lexer::lineindex::LineIndex;
+/// let source = "var a = 1;\nvar bb = 2;";
+/// let config = LexerConfig {
+///     offsets_only: true,
+///     ..LexerConfig::default()
+/// };
+/// let mut lexer = Lexer::with_config(CharStream::from_bytes(source.as_bytes()).unwrap(), config);
+/// let bb = lexer.nth(6).unwrap().unwrap();
+/// assert_eq!((bb.span.start, bb.span.line_start), (15, 0));
+///
+/// let span = LineIndex::new(source).resolve(bb.span);
+/// assert_eq!((span.line_start, span.column_start), (2, 5));
+/// assert_eq!((span.line_end, span.column_end), (2, 7));
+/// ```
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct LineIndex<'src> {
+    /// The indexed source.
+    source: &'src [u8],
+
+    /// Byte offset at which each line starts; the first entry is always 0.
+    line_starts: Cow<'src, [usize]>,
+
+    /// How the bytes of a line advance the column.
+    columns: ColumnCounter,
+}
+
+impl<'src> LineIndex<'src> {
+    /// Index `source`, counting columns in bytes.
+    pub fn new(source: &'src str) -> Self {
+        Self::from_bytes(source.as_bytes())
+    }
+
+    /// Index raw source bytes, counting columns in bytes.
+    pub fn from_bytes(source: &'src [u8]) -> Self {
+        Self::with_line_starts(source, Cow::Owned(line_starts(source)))
+    }
+
+    /// Index raw source bytes whose [`line_starts`] were computed earlier,
+    /// so an index can be rebuilt without rescanning the source.
+    pub(crate) fn with_line_starts(source: &'src [u8], line_starts: Cow<'src, [usize]>) -> Self {
+        Self {
+            source,
+            line_starts,
+            columns: ColumnCounter::default(),
+        }
+    }
+
+    /// Count columns in `unit`, matching the lexer's
+    /// [`LexerConfig::column_unit`](crate::config::LexerConfig::column_unit).
+    pub fn with_column_unit(mut self, unit: ColumnUnit) -> Self {
+        self.columns.unit = unit;
+        self
+    }
+
+    /// Count `\r\n` line endings as the lexer's
+    /// [`LexerConfig::line_endings`](crate::config::LexerConfig::line_endings)
+    /// does.
+    pub fn with_line_endings(mut self, line_endings: LineEndings) -> Self {
+        self.columns.line_endings = line_endings;
+        self
+    }
+
+    /// Count tabs to the next multiple of `width` as the lexer's
+    /// [`LexerConfig::tab_width`](crate::config::LexerConfig::tab_width)
+    /// does.
+    pub fn with_tab_width(mut self, width: usize) -> Self {
+        self.columns.tab_width = width;
+        self
+    }
+
+    /// Number of lines in the source. A trailing newline starts a final,
+    /// empty line.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The 1-based line and column of byte `offset`.
+    ///
+    /// Offsets past the end of the source are clamped to the end.
+    pub fn line_column(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let column =
+            (line_start..offset).fold(1, |column, i| self.columns.advance(column, self.source, i));
+        (line, column)
+    }
+
+    /// Fill in the line and column fields of `span` from its byte offsets.
+    pub fn resolve(&self, span: Span) -> Span {
+        let (line_start, column_start) = self.line_column(span.start);
+        let (line_end, column_end) = self.line_column(span.end);
+        Span {
+            line_start,
+            column_start,
+            line_end,
+            column_end,
+            ..span
+        }
+    }
+
+    /// Fill in the location of an error raised by an offsets-only lexer
+    /// from the byte offsets of its span.
+    pub(crate) fn resolve_error(&self, mut err: LexError) -> LexError {
+        if let Some(span) = err.span_mut() {
+            *span = self.resolve(*span);
+        }
+        err
+    }
+}
+
+/// The byte offset at which each line of `source` starts; the first entry
+/// is always 0.
+pub(crate) fn line_starts(source: &[u8]) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(
+        source
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| b == b'\n')
+            .map(|(i, _)| i + 1),
+    );
+    starts
+}
@@ -0,0 +1,215 @@
+//! Lexing Hummingbird code embedded in Markdown.
+//!
+//! Documentation and tutorials show code in fenced blocks such as
+//! ```` ```hummingbird ````. [`code_blocks`] finds those blocks and
+//! [`lex_blocks`] lexes each one with spans and error positions remapped to
+//! the Markdown file, so a docs checker can point at the offending line of
+//! the `.md` file rather than at a line of the extracted snippet.
+//!
+//! Block contents are taken verbatim, including any indentation of an
+//! indented fence, which the lexer skips as whitespace. That keeps every
+//! column in a block equal to the column in the file.
+
+use crate::intern::Interner;
+use crate::lexerror::{LexError, LexResult};
+use crate::token::Token;
+
+/// Info-string languages recognized as Hummingbird code.
+pub const LANGUAGES: [&str; 2] = ["hummingbird", "hm"];
+
+/// A fenced code block holding Hummingbird code.
+#[derive(Clone, Copy)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct CodeBlock<'a> {
+    /// The full info string after the opening fence, e.g. `hummingbird ignore`
+    pub info: &'a str,
+    /// The block contents, from the line after the opening fence up to the
+    /// closing fence line
+    pub source: &'a str,
+    /// Byte offset of `source` in the Markdown text
+    pub offset: usize,
+    /// 1-based line of the Markdown text on which `source` starts
+    pub line: usize,
+}
+
+/// The result of lexing one [`CodeBlock`].
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct LexedBlock<'a> {
+    /// The block that was lexed
+    pub block: CodeBlock<'a>,
+    /// Tokens ending with `Eof` and their interned identifier names, or the
+    /// first error, with every position relative to the Markdown text
+    pub result: LexResult<(Vec<Token>, Interner)>,
+}
+
+/// Find every Hummingbird fenced code block in `markdown`.
+///
+/// Both backtick and tilde fences are recognized, indented by up to three
+/// spaces as in CommonMark. A block is kept when the first word of its info
+/// string is one of [`LANGUAGES`]. A block with no closing fence runs to the
+/// end of the text.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::markdown::code_blocks;
+/// let doc = "# Intro\n\n```hummingbird\nvar x = 1;\n```\n\n```sh\nls\n```\n";
+/// let blocks = code_blocks(doc);
+/// assert_eq!(blocks.len(), 1);
+/// assert_eq!(blocks[0].source, "var x = 1;\n");
+/// assert_eq!(blocks[0].line, 4);
+/// ```
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock<'_>> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(Fence, &str, usize, usize)> = None;
+    let mut offset = 0;
+
+    for (index, line) in markdown.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        offset += line.len();
+
+        match open {
+            None => {
+                if let Some((fence, info)) = Fence::opening(line) {
+                    open = Some((fence, info, offset, index + 2));
+                }
+            }
+            Some((fence, info, start, first_line)) => {
+                if fence.closes(line) {
+                    blocks.push(CodeBlock {
+                        info,
+                        source: &markdown[start..line_start],
+                        offset: start,
+                        line: first_line,
+                    });
+                    open = None;
+                }
+            }
+        }
+    }
+
+    if let Some((_, info, start, first_line)) = open {
+        blocks.push(CodeBlock {
+            info,
+            source: &markdown[start..],
+            offset: start,
+            line: first_line,
+        });
+    }
+
+    blocks.retain(|block| is_hummingbird(block.info));
+    blocks
+}
+
+/// Lex every Hummingbird fenced code block in `markdown`.
+///
+/// Each block is lexed on its own with [`tokenize`](crate::tokenize), then
+/// its token spans and any error position are shifted to point into
+/// `markdown`. An empty block yields just an `Eof` token.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::markdown::lex_blocks;
+/// let doc = "Text\n\n```hm\nvar s = \"open;\n```\n";
+/// let lexed = lex_blocks(doc);
+/// let err = lexed[0].result.as_ref().unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "Unterminated string literal at line 4, column 9 \
+///      (newline in string literal; use a triple-quoted string to span lines)"
+/// );
+/// ```
+pub fn lex_blocks(markdown: &str) -> Vec<LexedBlock<'_>> {
+    code_blocks(markdown)
+        .into_iter()
+        .map(|block| LexedBlock {
+            block,
+            result: lex_block(&block),
+        })
+        .collect()
+}
+
+/// Lex every Hummingbird block in `markdown` and return only the errors,
+/// one per failing block, in document order.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::markdown::check;
+/// let doc = "```hummingbird\nvar ok = 1;\n```\n\n```hummingbird\nvar bad = @;\n```\n";
+/// let errors = check(doc);
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].to_string(), "Unexpected character '@' at line 6, column 11");
+/// ```
+pub fn check(markdown: &str) -> Vec<LexError> {
+    lex_blocks(markdown)
+        .into_iter()
+        .filter_map(|lexed| lexed.result.err())
+        .collect()
+}
+
+/// Lex one block and remap its positions into the enclosing document.
+fn lex_block(block: &CodeBlock<'_>) -> LexResult<(Vec<Token>, Interner)> {
+    let lines = block.line - 1;
+    match crate::tokenize(block.source) {
+        Ok((mut tokens, names)) => {
+            for token in &mut tokens {
+                token.shift(block.offset, lines);
+            }
+            Ok((tokens, names))
+        }
+        Err(err) => Err(err.shift(block.offset, lines)),
+    }
+}
+
+/// Whether the first word of an info string names Hummingbird.
+fn is_hummingbird(info: &str) -> bool {
+    info.split_whitespace()
+        .next()
+        .is_some_and(|lang| LANGUAGES.contains(&lang))
+}
+
+/// An opening code fence: its character and length.
+#[derive(Clone, Copy)]
+struct Fence {
+    marker: u8,
+    len: usize,
+}
+
+impl Fence {
+    /// Parse `line` as an opening fence, returning it and its trimmed info
+    /// string.
+    fn opening(line: &str) -> Option<(Fence, &str)> {
+        let (fence, rest) = Self::parse(line)?;
+        let info = rest.trim();
+        // Backtick fences may not have backticks in their info string.
+        if fence.marker == b'`' && info.contains('`') {
+            return None;
+        }
+        Some((fence, info))
+    }
+
+    /// Whether `line` is a closing fence for this opening fence.
+    fn closes(self, line: &str) -> bool {
+        Self::parse(line).is_some_and(|(fence, rest)| {
+            fence.marker == self.marker && fence.len >= self.len && rest.trim().is_empty()
+        })
+    }
+
+    /// Split a run of at least three `` ` `` or `~`, indented by at most
+    /// three spaces, off the start of `line`.
+    fn parse(line: &str) -> Option<(Fence, &str)> {
+        let indent = line.bytes().take_while(|&b| b == b' ').count();
+        if indent > 3 {
+            return None;
+        }
+        let rest = &line[indent..];
+        let marker = *rest
+            .as_bytes()
+            .first()
+            .filter(|&&b| b == b'`' || b == b'~')?;
+        let len = rest.bytes().take_while(|&b| b == marker).count();
+        (len >= 3).then(|| (Fence { marker, len }, &rest[len..]))
+    }
+}
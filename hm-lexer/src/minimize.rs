@@ -0,0 +1,82 @@
+//! Test-case reduction for inputs that trip up the lexer.
+//!
+//! When a fuzzer or a user report produces a large input that triggers a
+//! lexer error, panic, or disagreement with another implementation,
+//! [`minimize`] shrinks it to a small reproducer that still triggers the
+//! same behavior. Reduction removes whole lines first (cheap, and keeps the
+//! result readable) and then individual bytes.
+
+/// Shrink `input` to a smaller input for which `predicate` still holds.
+///
+/// The predicate should return `true` when its argument still exhibits the
+/// interesting behavior (e.g. "lexing fails with `L0003`"). Reduction runs
+/// in two phases, each repeatedly trying to delete chunks of decreasing size
+/// until no single deletion keeps the predicate true:
+///
+/// 1. whole lines (including their trailing `\n`)
+/// 2. individual bytes
+///
+/// The result is 1-minimal with respect to bytes: removing any one byte
+/// makes the predicate false. Bytes are not required to remain valid UTF-8,
+/// since the lexer itself operates on raw bytes.
+///
+/// # Arguments
+///
+/// * `input` - An input for which `predicate` returns `true`
+/// * `predicate` - Returns `true` if a candidate still reproduces the problem
+///
+/// # Returns
+///
+/// The reduced input, or `input` unchanged if the predicate does not hold
+/// for it in the first place
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::minimize::minimize;
+/// let input = b"var a = 1;\nvar b = @;\nvar c = 3;\n";
+/// let reduced = minimize(input, |candidate| candidate.contains(&b'@'));
+/// assert_eq!(reduced, b"@");
+/// ```
+pub fn minimize(input: &[u8], mut predicate: impl FnMut(&[u8]) -> bool) -> Vec<u8> {
+    if !predicate(input) {
+        return input.to_vec();
+    }
+
+    let lines: Vec<&[u8]> = input.split_inclusive(|&b| b == b'\n').collect();
+    let lines = reduce(lines, |candidate| predicate(&candidate.concat()));
+
+    let bytes = lines.concat();
+    reduce(bytes, |candidate| predicate(candidate))
+}
+
+/// Delete chunks of `units` while `test` keeps holding.
+///
+/// Starts with chunks of half the input and halves the chunk size whenever a
+/// full pass makes no progress, finishing with single-unit deletions.
+fn reduce<T: Clone>(mut units: Vec<T>, mut test: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut chunk = units.len().div_ceil(2).max(1);
+    loop {
+        let mut removed_any = false;
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let mut candidate = Vec::with_capacity(units.len() - (end - start));
+            candidate.extend_from_slice(&units[..start]);
+            candidate.extend_from_slice(&units[end..]);
+            if test(&candidate) {
+                units = candidate;
+                removed_any = true;
+            } else {
+                start = end;
+            }
+        }
+
+        if !removed_any {
+            if chunk == 1 {
+                return units;
+            }
+            chunk = chunk.div_ceil(2);
+        }
+    }
+}
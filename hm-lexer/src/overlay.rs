@@ -0,0 +1,137 @@
+//! Lexing with unsaved editor changes.
+//!
+//! Language servers see a document as the file on disk plus the edits the
+//! user has made but not saved. [`apply_patches`] applies such edits in
+//! memory, and [`tokenize_overlay`] lexes the result, so every span refers
+//! to the patched text the user is looking at rather than to the file on
+//! disk. Nothing is written back.
+
+use std::ops::Range;
+
+use thiserror::Error;
+
+use crate::intern::Interner;
+use crate::lexerror::LexError;
+use crate::source::SourceFile;
+use crate::token::Token;
+
+/// One in-memory edit: replace the bytes in `range` with `text`.
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Patch {
+    /// Byte range to replace, in the text as it is after all earlier patches
+    pub range: Range<usize>,
+    /// Replacement text; empty to delete
+    pub text: String,
+}
+
+impl Patch {
+    /// Replace the bytes in `range` with `text`.
+    pub fn new(range: Range<usize>, text: impl Into<String>) -> Self {
+        Self {
+            range,
+            text: text.into(),
+        }
+    }
+}
+
+/// Errors from applying or lexing an overlay.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum OverlayError {
+    /// A patch range is reversed, out of bounds, or splits a character.
+    #[error("Patch {index} has invalid range {start}..{end} for a {len}-byte text")]
+    InvalidRange {
+        /// Position of the patch in the list
+        index: usize,
+        /// Start of the patch range
+        start: usize,
+        /// End of the patch range
+        end: usize,
+        /// Length of the text the patch was applied to
+        len: usize,
+    },
+
+    /// The patched text failed to lex.
+    #[error(transparent)]
+    Lex(#[from] LexError),
+}
+
+/// Apply `patches` to `base` in order.
+///
+/// Patches are applied one after another, as in an LSP `didChange`
+/// notification, so each range refers to the text produced by the patches
+/// before it.
+///
+/// # Arguments
+///
+/// * `base` - The saved text
+/// * `patches` - The unsaved edits, in the order they were made
+///
+/// # Returns
+///
+/// - `Ok(String)` with the patched text
+/// - `Err(OverlayError::InvalidRange)` for the first patch whose range does
+///   not fit the text it applies to
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::overlay::{apply_patches, Patch};
+/// let patched = apply_patches(
+///     "var x = 1;",
+///     &[Patch::new(4..5, "count"), Patch::new(12..13, "42")],
+/// )
+/// .unwrap();
+/// assert_eq!(patched, "var count = 42;");
+/// ```
+pub fn apply_patches(base: &str, patches: &[Patch]) -> Result<String, OverlayError> {
+    let mut text = base.to_string();
+    for (index, patch) in patches.iter().enumerate() {
+        let Range { start, end } = patch.range;
+        if start > end || text.get(start..end).is_none() {
+            return Err(OverlayError::InvalidRange {
+                index,
+                start,
+                end,
+                len: text.len(),
+            });
+        }
+        text.replace_range(start..end, &patch.text);
+    }
+    Ok(text)
+}
+
+/// Lex `base` as modified by `patches`.
+///
+/// Token spans are in the coordinate space of the patched text.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::overlay::{tokenize_overlay, Patch};
+/// let (tokens, _) = tokenize_overlay("var x = 1;", &[Patch::new(8..9, "\"one\"")]).unwrap();
+/// assert_eq!(tokens[3].lexeme, "\"one\"");
+/// assert_eq!(tokens[4].span.start, 13);
+/// ```
+pub fn tokenize_overlay(
+    base: &str,
+    patches: &[Patch],
+) -> Result<(Vec<Token>, Interner), OverlayError> {
+    let text = apply_patches(base, patches)?;
+    Ok(crate::tokenize(&text)?)
+}
+
+impl SourceFile {
+    /// A copy of this file with `patches` applied, keeping its name and
+    /// provenance.
+    ///
+    /// See [`apply_patches`] for how patches are interpreted.
+    pub fn with_overlay(&self, patches: &[Patch]) -> Result<SourceFile, OverlayError> {
+        let text = apply_patches(self.source().as_str(), patches)?;
+        Ok(match self.provenance() {
+            Some(provenance) => SourceFile::synthesized(self.name(), text, provenance.clone()),
+            None => SourceFile::new(self.name(), text),
+        })
+    }
+}
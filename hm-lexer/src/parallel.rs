@@ -0,0 +1,190 @@
+//! Lexing large sources on several threads.
+//!
+//! [`lex_parallel`] splits the source into chunks at line starts, lexes the
+//! chunks concurrently, and stitches the token vectors back together with
+//! every span moved to its place in the whole source and every identifier
+//! reinterned into one [`Interner`]. The result is exactly what
+//! [`tokenize`](crate::tokenize) returns, symbols included.
+//!
+//! A line start is only a safe place to split if no comment or string runs
+//! across it, which cannot be known without lexing everything before it.
+//! Chunks are therefore lexed speculatively. A chunk's tokens are kept when
+//! the chunk before it was kept and it lexed without error: every chunk but
+//! the last ends with a newline, and only tokens or comments that may hold a
+//! newline can cross one, so a boundary inside such a construct makes the
+//! chunk before it fail as unterminated. From the first chunk that fails,
+//! the source is lexed sequentially until a token lines up with one of a
+//! later chunk's, where the remaining speculative results take over again.
+
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::charstream::CharStream;
+use crate::intern::Interner;
+use crate::lexer::Lexer;
+use crate::lexerror::LexResult;
+use crate::token::Token;
+use crate::tokenize;
+
+/// Smallest chunk worth handing to a thread, in bytes. Smaller inputs are
+/// lexed on the calling thread.
+pub const MIN_CHUNK_LEN: usize = 64 * 1024;
+
+/// Lex `source` on up to `n_threads` threads.
+///
+/// Returns the same tokens, or the same first error, as
+/// [`tokenize`](crate::tokenize). Sources shorter than two
+/// [`MIN_CHUNK_LEN`] chunks are lexed on the calling thread.
+///
+/// # Arguments
+///
+/// * `source` - The source code to tokenize
+/// * `n_threads` - Maximum number of threads to use; 0 uses
+///   [`std::thread::available_parallelism`]
+///
+/// # Returns
+///
+/// - `Ok((Vec<Token>, Interner))` containing every token followed by `Eof`,
+///   and the interned identifier names
+/// - `Err(LexError)` if the input contains invalid tokens
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::{lex_parallel, tokenize};
+/// let source = "/* generated */\nvar x = 1;\n".repeat(20_000);
+/// let (tokens, names) = lex_parallel(&source, 4).unwrap();
+/// let (expected, expected_names) = tokenize(&source).unwrap();
+/// assert_eq!(tokens, expected);
+/// assert_eq!(names, expected_names);
+/// ```
+pub fn lex_parallel(source: &str, n_threads: usize) -> LexResult<(Vec<Token>, Interner)> {
+    let n_threads = match n_threads {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        n => n,
+    };
+    let bounds = chunk_bounds(source.as_bytes(), n_threads);
+    if bounds.len() <= 2 {
+        return tokenize(source);
+    }
+
+    let chunks: Vec<LexResult<(Vec<Token>, Interner)>> = thread::scope(|scope| {
+        let handles: Vec<_> = bounds
+            .windows(2)
+            .map(|range| scope.spawn(|| lex_chunk(source, range[0], range[1])))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("lexer thread panicked"))
+            .collect()
+    });
+    stitch(source, &bounds, chunks)
+}
+
+/// Chunk boundaries for `source`: 0, the start of a line near each even
+/// split point, and `source.len()`.
+///
+/// A line starting with `#!` is skipped, since a chunk starting there
+/// would lex it as a shebang.
+fn chunk_bounds(source: &[u8], n_threads: usize) -> Vec<usize> {
+    let chunks = n_threads.min(source.len() / MIN_CHUNK_LEN).max(1);
+    let mut bounds = vec![0];
+    for i in 1..chunks {
+        let mut at = (source.len() / chunks * i).max(bounds[bounds.len() - 1]);
+        let bound = loop {
+            let Some(newline) = memchr::memchr(b'\n', &source[at..]) else {
+                break None;
+            };
+            at += newline + 1;
+            if !source[at..].starts_with(b"#!") {
+                break Some(at);
+            }
+        };
+        match bound {
+            Some(bound) if bound < source.len() => bounds.push(bound),
+            _ => break,
+        }
+    }
+    bounds.push(source.len());
+    bounds
+}
+
+/// Lex `source[start..end]` on its own, with positions relative to the
+/// whole source. `start` must begin a line.
+///
+/// The `Eof` token is dropped unless the chunk runs to the end of the
+/// source. Symbols are those of the chunk's own interner, returned with it.
+fn lex_chunk(source: &str, start: usize, end: usize) -> LexResult<(Vec<Token>, Interner)> {
+    let lines = memchr::memchr_iter(b'\n', &source.as_bytes()[..start]).count();
+    let (mut tokens, names) =
+        tokenize(&source[start..end]).map_err(|err| err.shift(start, lines))?;
+    for token in &mut tokens {
+        token.shift(start, lines);
+    }
+    if end < source.len() {
+        tokens.pop();
+    }
+    Ok((tokens, names))
+}
+
+/// Join the speculative chunk results into the tokens of the whole source,
+/// relexing sequentially wherever a chunk's result cannot be trusted.
+///
+/// Tokens are reinterned in source order as they are joined, so the
+/// symbols come out as a single lexer would number them.
+fn stitch(
+    source: &str,
+    bounds: &[usize],
+    mut chunks: Vec<LexResult<(Vec<Token>, Interner)>>,
+) -> LexResult<(Vec<Token>, Interner)> {
+    let mut tokens = Vec::new();
+    let mut names = Interner::new();
+    let mut chunk = 0;
+    while chunk < chunks.len() {
+        if let Ok((lexed, chunk_names)) = &mut chunks[chunk] {
+            for mut token in lexed.drain(..) {
+                token.reintern(chunk_names, &mut names);
+                tokens.push(token);
+            }
+            chunk += 1;
+            continue;
+        }
+
+        // The cursor is clean at the start of this chunk, so lexing from
+        // here gives the right tokens, or the right first error.
+        let start = bounds[chunk];
+        let lines = memchr::memchr_iter(b'\n', &source.as_bytes()[..start]).count();
+        let mut lexer = Lexer::new(CharStream::from_bytes(&source.as_bytes()[start..])?);
+        loop {
+            let mut token = lexer.next_token().map_err(|err| err.shift(start, lines))?;
+            token.shift(start, lines);
+            while chunk + 1 < chunks.len() && token.span.start >= bounds[chunk + 1] {
+                chunk += 1;
+            }
+            let rest = chunks[chunk]
+                .as_mut()
+                .ok()
+                .and_then(|(lexed, chunk_names)| {
+                    let at = lexed.binary_search_by_key(&token.span.start, |t| t.span.start);
+                    at.ok().map(|at| (lexed.split_off(at), &*chunk_names))
+                });
+            if let Some((rest, chunk_names)) = rest {
+                // Lexing from the same position gives the same tokens, so
+                // the chunk's own result is right from here on.
+                for mut token in rest {
+                    token.reintern(chunk_names, &mut names);
+                    tokens.push(token);
+                }
+                chunk += 1;
+                break;
+            }
+            token.reintern(lexer.interner(), &mut names);
+            let is_eof = token.is_eof();
+            tokens.push(token);
+            if is_eof {
+                return Ok((tokens, names));
+            }
+        }
+    }
+    Ok((tokens, names))
+}
@@ -0,0 +1,297 @@
+//! Lexing a whole program by following its imports.
+//!
+//! [`lex_project`] lexes a root file, finds each `import a::b::c;`
+//! statement in its tokens, asks a caller-supplied resolver for the file
+//! that module lives in, and lexes that file in turn, until every reachable
+//! module has been lexed once. The result is a [`Project`]: a
+//! [`SourceMap`] holding every file, the token stream of each, and one
+//! [`Interner`] for the identifiers of all of them, so the same name has the
+//! same symbol in every module, ready for the compiler driver to parse.
+//!
+//! The resolver decides what a module path means (a path on disk, an entry
+//! in a package cache, an in-memory file in tests), so this module never
+//! touches the file system itself.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::intern::Interner;
+use crate::lexerror::LexError;
+use crate::source::SourceFile;
+use crate::sourcemap::{FileId, SourceMap};
+use crate::token::delimiters::Delimiters;
+use crate::token::keywords::Keywords;
+use crate::token::operators::SpecialOps;
+use crate::token::span::Span;
+use crate::token::tokenkind::TokenKind;
+use crate::token::Token;
+
+/// An `import` statement found in a module.
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Import {
+    /// The module path as written, with segments joined by `::`
+    pub path: String,
+    /// From the `import` keyword through the terminating `;`
+    pub span: Span,
+    /// The file the path resolved to
+    pub target: FileId,
+}
+
+/// One lexed file of a [`Project`].
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Module {
+    /// The file in the project's [`SourceMap`]
+    pub file: FileId,
+    /// Every token of the file, ending with `Eof`, with identifier symbols
+    /// from [`Project::names`]
+    pub tokens: Vec<Token>,
+    /// The file's imports, in source order
+    pub imports: Vec<Import>,
+}
+
+/// Every module reachable from a root file.
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Project {
+    /// All files of the project; the root is registered first
+    pub source_map: SourceMap,
+    /// One module per file, indexed like `source_map`
+    pub modules: Vec<Module>,
+    /// The identifier names of every module
+    pub names: Interner,
+}
+
+impl Project {
+    /// The root module passed to [`lex_project`].
+    pub fn root(&self) -> &Module {
+        &self.modules[0]
+    }
+
+    /// The module lexed from `file`.
+    pub fn module(&self, file: FileId) -> &Module {
+        &self.modules[file.index()]
+    }
+}
+
+/// Errors from [`lex_project`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ProjectError {
+    /// A file failed to lex.
+    #[error("{file}: {error}")]
+    Lex {
+        /// Name of the file that failed
+        file: String,
+        /// The lexer's error
+        error: Box<LexError>,
+    },
+
+    /// The resolver could not provide the file for an import.
+    #[error(
+        "{importer}:{}:{}: cannot resolve import '{path}': {message}",
+        .span.line_start,
+        .span.column_start
+    )]
+    Unresolved {
+        /// Name of the file containing the import
+        importer: String,
+        /// The module path that failed to resolve
+        path: String,
+        /// The import statement
+        span: Box<Span>,
+        /// The resolver's explanation
+        message: String,
+    },
+
+    /// Modules import each other in a cycle.
+    #[error("import cycle: {}", .cycle.join(" -> "))]
+    Cycle {
+        /// File names around the cycle, starting and ending with the same file
+        cycle: Vec<String>,
+    },
+}
+
+/// Lex `root` and every module it transitively imports.
+///
+/// Imports are followed depth-first in source order. Each distinct file,
+/// identified by [`SourceFile::name`], is lexed once, so a module imported
+/// from several places shares one [`FileId`]. A module that imports one of
+/// its own importers is reported as [`ProjectError::Cycle`].
+///
+/// Only well-formed `import` statements (the keyword, `::`-separated
+/// identifiers, and `;`) are followed; anything else is left for the parser
+/// to diagnose.
+///
+/// # Arguments
+///
+/// * `root` - The file compilation starts from
+/// * `resolve` - Called with a module path and the importing file; returns
+///   the imported file or a message explaining why it cannot be loaded
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::project::lex_project;
+/// # use hm_lexer::source::SourceFile;
+/// let root = SourceFile::new("main.hm", "import std::io;\nimport util;\n");
+/// let project = lex_project(root, |path, _importer| match path {
+///     "std::io" => Ok(SourceFile::new("std/io.hm", "import util; func print() {}")),
+///     "util" => Ok(SourceFile::new("util.hm", "var debug = 0;")),
+///     other => Err(format!("no module named {other}")),
+/// })
+/// .unwrap();
+///
+/// assert_eq!(project.modules.len(), 3);
+/// let imports: Vec<_> = project.root().imports.iter().map(|i| i.path.as_str()).collect();
+/// assert_eq!(imports, ["std::io", "util"]);
+/// let util = project.root().imports[1].target;
+/// assert_eq!(project.source_map.get(util).name(), "util.hm");
+/// assert!(project.names.get("debug").is_some());
+/// ```
+pub fn lex_project<F>(root: SourceFile, resolve: F) -> Result<Project, ProjectError>
+where
+    F: FnMut(&str, &SourceFile) -> Result<SourceFile, String>,
+{
+    let mut pipeline = Pipeline {
+        resolve,
+        source_map: SourceMap::new(),
+        modules: HashMap::new(),
+        by_name: HashMap::new(),
+        stack: Vec::new(),
+        names: Interner::new(),
+    };
+    pipeline.load(root)?;
+
+    let Pipeline {
+        source_map,
+        mut modules,
+        names,
+        ..
+    } = pipeline;
+    let modules = source_map
+        .iter()
+        .map(|(id, _)| modules.remove(&id).expect("every registered file is lexed"))
+        .collect();
+    Ok(Project {
+        source_map,
+        modules,
+        names,
+    })
+}
+
+/// State shared across the depth-first walk.
+struct Pipeline<F> {
+    resolve: F,
+    source_map: SourceMap,
+    /// Fully lexed modules
+    modules: HashMap<FileId, Module>,
+    /// Every registered file by name, finished or not
+    by_name: HashMap<String, FileId>,
+    /// Files whose imports are being followed, outermost first
+    stack: Vec<FileId>,
+    /// Identifier names of every module lexed so far
+    names: Interner,
+}
+
+impl<F> Pipeline<F>
+where
+    F: FnMut(&str, &SourceFile) -> Result<SourceFile, String>,
+{
+    /// Register and lex `file`, then load its imports.
+    fn load(&mut self, file: SourceFile) -> Result<FileId, ProjectError> {
+        if let Some(&id) = self.by_name.get(file.name()) {
+            if let Some(position) = self.stack.iter().position(|&open| open == id) {
+                let mut cycle: Vec<String> = self.stack[position..]
+                    .iter()
+                    .map(|&open| self.source_map.get(open).name().to_string())
+                    .collect();
+                cycle.push(file.name().to_string());
+                return Err(ProjectError::Cycle { cycle });
+            }
+            return Ok(id);
+        }
+
+        let (mut tokens, file_names) = file.tokenize().map_err(|error| ProjectError::Lex {
+            file: file.name().to_string(),
+            error: Box::new(error),
+        })?;
+        for token in &mut tokens {
+            token.reintern(&file_names, &mut self.names);
+        }
+        let id = self.source_map.add(file);
+        let name = self.source_map.get(id).name().to_string();
+        self.by_name.insert(name, id);
+
+        self.stack.push(id);
+        let mut imports = Vec::new();
+        for (path, span) in find_imports(&tokens, &self.names) {
+            let importer = Arc::clone(self.source_map.get(id));
+            let imported =
+                (self.resolve)(&path, &importer).map_err(|message| ProjectError::Unresolved {
+                    importer: importer.name().to_string(),
+                    path: path.clone(),
+                    span: Box::new(span),
+                    message,
+                })?;
+            let target = self.load(imported)?;
+            imports.push(Import { path, span, target });
+        }
+        self.stack.pop();
+
+        self.modules.insert(
+            id,
+            Module {
+                file: id,
+                tokens,
+                imports,
+            },
+        );
+        Ok(id)
+    }
+}
+
+/// Find every `import a::b::c;` statement, returning each module path with
+/// the span of its statement.
+fn find_imports(tokens: &[Token], names: &Interner) -> Vec<(String, Span)> {
+    let mut out = Vec::new();
+    for (start, token) in tokens.iter().enumerate() {
+        if !matches!(token.kind, TokenKind::Keyword(Keywords::Import)) {
+            continue;
+        }
+        let mut path = String::new();
+        let mut rest = tokens[start + 1..].iter();
+        let end = loop {
+            let Some(TokenKind::Identifier(segment)) = rest.next().map(|t| &t.kind) else {
+                break None;
+            };
+            path.push_str(names.resolve(*segment));
+            match rest.next() {
+                Some(t) if matches!(t.kind, TokenKind::Delimiter(Delimiters::Semicolon)) => {
+                    break Some(t.span);
+                }
+                Some(t)
+                    if matches!(
+                        t.kind,
+                        TokenKind::SpecialOperator(SpecialOps::ScopingOperator)
+                    ) =>
+                {
+                    path.push_str("::");
+                }
+                _ => break None,
+            }
+        };
+        if let Some(end) = end {
+            let span = Span {
+                end: end.end,
+                line_end: end.line_end,
+                column_end: end.column_end,
+                char_offsets: None,
+                ..token.span
+            };
+            out.push((path, span));
+        }
+    }
+    out
+}
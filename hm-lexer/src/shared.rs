@@ -0,0 +1,113 @@
+//! Token streams that share their source across threads.
+//!
+//! IDE caches and parallel compiler passes hold on to lexed files for a long
+//! time and hand them between threads. [`SharedTokens`] keeps a file's
+//! tokens next to an [`Arc<SourceFile>`] and lexes them with
+//! [`LexerConfig::lazy_lexemes`](crate::config::LexerConfig::lazy_lexemes),
+//! so every holder reads token text straight out of the one shared source
+//! by span. Cloning a `SharedTokens` bumps three reference counts; neither
+//! the source text, the tokens, nor their interned names are copied.
+
+use std::sync::Arc;
+
+use crate::charstream::CharStream;
+use crate::intern::Interner;
+use crate::lexer::Lexer;
+use crate::lexerror::LexResult;
+use crate::source::SourceFile;
+use crate::token::Token;
+
+/// The tokens of a [`SourceFile`], sharing ownership of the file.
+///
+/// `SharedTokens` is `Send + Sync`, so it can be moved into worker threads
+/// or stored in a cache behind a lock.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use std::thread;
+/// # use hm_lexer::shared::SharedTokens;
+/// # use hm_lexer::source::SourceFile;
+/// let file = Arc::new(SourceFile::new("main.hm", "var answer = 42;"));
+/// let tokens = SharedTokens::lex(file).unwrap();
+///
+/// let worker = tokens.clone();
+/// let name = thread::spawn(move || worker.text(&worker.tokens()[1]).to_string())
+///     .join()
+///     .unwrap();
+/// assert_eq!(name, "answer");
+/// assert_eq!(tokens.file().name(), "main.hm");
+/// ```
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct SharedTokens {
+    /// The file the tokens were lexed from.
+    file: Arc<SourceFile>,
+
+    /// Every token of the file, ending with `Eof`.
+    tokens: Arc<[Token]>,
+
+    /// Names of the identifier symbols in `tokens`.
+    names: Arc<Interner>,
+}
+
+impl SharedTokens {
+    /// Lex `file` and keep the tokens alongside it.
+    ///
+    /// The tokens are those [`tokenize`](crate::tokenize) returns, except
+    /// that identifiers and literals carry no lexeme of their own; read
+    /// their text with [`text`](Self::text).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(SharedTokens)` holding every token followed by `Eof`
+    /// - `Err(LexError)` if the file fails to lex
+    pub fn lex(file: Arc<SourceFile>) -> LexResult<Self> {
+        let stream = CharStream::from_bytes(file.source().as_str().as_bytes())?;
+        let mut lexer = Lexer::builder().lazy_lexemes(true).build(stream);
+        let tokens = lexer.by_ref().collect::<LexResult<Vec<Token>>>()?;
+        Ok(Self::from_tokens(file, tokens, lexer.into_interner()))
+    }
+
+    /// Pair tokens lexed elsewhere, e.g. with a custom
+    /// [`LexerConfig`](crate::config::LexerConfig), with the file they came
+    /// from and the interner holding their identifier names.
+    pub fn from_tokens(file: Arc<SourceFile>, tokens: Vec<Token>, names: Interner) -> Self {
+        Self {
+            file,
+            tokens: tokens.into(),
+            names: Arc::new(names),
+        }
+    }
+
+    /// The file the tokens were lexed from.
+    pub fn file(&self) -> &Arc<SourceFile> {
+        &self.file
+    }
+
+    /// The tokens, ending with `Eof`.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// The names the tokens' identifier symbols stand for.
+    pub fn interner(&self) -> &Interner {
+        &self.names
+    }
+
+    /// The source text covered by `token`, borrowed from the shared file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `token` was not lexed from this file and its span does not
+    /// fit the text.
+    pub fn text(&self, token: &Token) -> &str {
+        &self.file.source()[token.span]
+    }
+
+    /// Iterate over the tokens paired with their source text.
+    pub fn iter(&self) -> impl Iterator<Item = (&Token, &str)> {
+        self.tokens.iter().map(|token| (token, self.text(token)))
+    }
+}
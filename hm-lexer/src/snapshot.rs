@@ -0,0 +1,665 @@
+//! Canonical text format for token streams (`.tokens` files).
+//!
+//! Golden tests, external tools, and bug reports need a token dump that is
+//! stable, readable in a diff, and parseable back into tokens. [`serialize`] writes one token per line and [`parse`] reads it
+//! back. Unlike the binary [`codec`](crate::codec), the format is meant to be
+//! read and edited by people.
+//!
+//! # Format
+//!
+//! The first line is the header `# hm-lexer tokens vFORMAT lexer VERSION`,
+//! naming the [`FORMAT_VERSION`] and the
+//! [`lexer_version`](crate::lexer_version) that wrote the snapshot. [`parse`]
+//! rejects snapshots written in another format or by another lexer version,
+//! whose kinds may no longer mean the same thing; regenerate them instead.
+//! Each following line holds one token:
+//!
+//! ```text
+//! LINE:COL-LINE:COL  START..END  [cSTART..END]  KIND [VALUE...] [LEXEME]
+//! ```
+//!
+//! - The positions and byte range come from the token's span; the optional
+//!   `c` range holds its character offsets.
+//! - `KIND` is one of `keyword`, `identifier`, `delimiter`, `operator`,
+//!   `string`, `multiline`, `char`, `byte`, `int`, `unsigned`, `float`,
+//!   `numeric`, `interpolated`, `error`, or `eof`.
+//! - Keywords, delimiters, and operators give their spelling as the value;
+//!   literals give their decoded value (`multiline` gives the raw text, then
+//!   the value; `numeric` gives the type suffix, then the value); errors give
+//!   their code, such as `L0002`.
+//! - `interpolated` gives its number of parts. Each part follows on its own
+//!   line, indented one level: `text` with the quoted text, or `expr` with
+//!   its number of tokens, which follow indented one level further.
+//! - `LEXEME` is the source text as a quoted string. It is left out when it
+//!   is implied by the kind: the spelling of a keyword, delimiter, or
+//!   operator, the name of an identifier, or the empty lexeme of `eof`.
+//!
+//! Strings are double-quoted, with `\\`, `\"`, `\n`, `\r`, `\t`, `\0`, and
+//! `\u{..}` escapes. Blank lines and other lines starting with `#` are
+//! ignored, so snapshots can carry comments.
+//!
+//! ```text
+//! # hm-lexer tokens v2 lexer 0.1.0+3a7bd3e2360a3d29
+//! 1:1-1:4    0..3    keyword var
+//! 1:5-1:6    4..5    identifier "x"
+//! 1:7-1:8    6..7    operator =
+//! 1:9-1:12   8..11   int 7 "007"
+//! 1:12-1:13  11..12  delimiter ;
+//! 1:13-1:13  12..12  eof
+//! ```
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::grammar;
+use crate::intern::Interner;
+use crate::lexerror::ErrorCode;
+use crate::token::literals::{Literals, NumericSuffix, NumericValue, StringPart};
+use crate::token::span::Span;
+use crate::token::tokenkind::TokenKind;
+use crate::token::{owned_lexeme, static_lexeme, Lexeme, Token};
+
+/// Version of the snapshot format written by [`serialize`]. Bump it whenever
+/// the line layout changes.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// What every header line starts with, followed by the format version.
+const HEADER_PREFIX: &str = "# hm-lexer tokens v";
+
+/// The header line of snapshots written by this build.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::snapshot;
+/// let header = snapshot::header();
+/// assert_eq!(header, format!("# hm-lexer tokens v2 lexer {}", hm_lexer::lexer_version()));
+/// ```
+pub fn header() -> String {
+    format!(
+        "{HEADER_PREFIX}{FORMAT_VERSION} lexer {}",
+        crate::lexer_version()
+    )
+}
+
+/// Errors from parsing a snapshot.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    /// The text does not start with a snapshot [`header`].
+    #[error("Not a token snapshot (expected a '{HEADER_PREFIX}N lexer VERSION' header)")]
+    MissingHeader,
+
+    /// The snapshot uses a format this build cannot read.
+    #[error("Unsupported token snapshot format version {found} (expected {expected})")]
+    UnsupportedVersion {
+        /// Format version recorded in the header
+        found: u64,
+        /// Format version written by this build
+        expected: u32,
+    },
+
+    /// The snapshot was written by a different version of the lexer.
+    #[error("Token snapshot was written by lexer {found}, but this is lexer {expected}")]
+    LexerVersionMismatch {
+        /// Lexer version recorded in the header
+        found: String,
+        /// Version of this lexer
+        expected: &'static str,
+    },
+
+    /// A line does not have the expected shape.
+    #[error("Malformed token snapshot at line {line}: expected {expected}")]
+    Malformed {
+        /// 1-based line number in the snapshot
+        line: usize,
+        /// Description of what was expected
+        expected: &'static str,
+    },
+
+    /// A kind, spelling, or error code is not known to this lexer.
+    #[error("Unknown {what} '{found}' at line {line} of token snapshot")]
+    Unknown {
+        /// 1-based line number in the snapshot
+        line: usize,
+        /// What was being looked up: `"kind"`, `"spelling"`, or `"error code"`
+        what: &'static str,
+        /// The unrecognized text
+        found: String,
+    },
+}
+
+/// Write `tokens` in the snapshot format, spelling out identifier names
+/// from `names`, the interner their symbols come from.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::snapshot;
+/// let (tokens, names) = hm_lexer::tokenize("x = 'a';").unwrap();
+/// let text = snapshot::serialize(&tokens, &names);
+/// assert_eq!(text.lines().nth(1).unwrap(), "1:1-1:2    0..1    identifier \"x\"");
+/// assert_eq!(text.lines().nth(3).unwrap(), "1:5-1:8    4..7    char \"a\" \"'a'\"");
+///
+/// let (parsed, parsed_names) = snapshot::parse(&text).unwrap();
+/// assert_eq!(snapshot::serialize(&parsed, &parsed_names), text);
+/// ```
+pub fn serialize(tokens: &[Token], names: &Interner) -> String {
+    let header = header();
+    let mut out = String::with_capacity(header.len() + 1 + tokens.len() * 32);
+    out.push_str(&header);
+    out.push('\n');
+    write_tokens(&mut out, tokens, names, 0);
+    out
+}
+
+/// Append one line per token, indented by `depth` levels, each followed by
+/// the parts of an interpolated string.
+fn write_tokens(out: &mut String, tokens: &[Token], names: &Interner, depth: usize) {
+    for token in tokens {
+        indent(out, depth);
+        let span = &token.span;
+        let position = format!(
+            "{}:{}-{}:{}",
+            span.line_start, span.column_start, span.line_end, span.column_end
+        );
+        let range = format!("{}..{}", span.start, span.end);
+        let _ = write!(out, "{position:<10} {range:<7}");
+        if let Some((start, end)) = span.char_offsets {
+            let _ = write!(out, " c{start}..{end}");
+        }
+
+        let implied = write_kind(out, &token.kind, names);
+        if implied.as_deref() != Some(&*token.lexeme) {
+            out.push(' ');
+            quote(out, &token.lexeme);
+        }
+        out.push('\n');
+
+        if let TokenKind::Literal(Literals::InterpolatedString(parts)) = &token.kind {
+            for part in parts {
+                indent(out, depth + 1);
+                match part {
+                    StringPart::Text(text) => {
+                        out.push_str("text ");
+                        quote(out, text);
+                        out.push('\n');
+                    }
+                    StringPart::Expr(expr) => {
+                        let _ = writeln!(out, "expr {}", expr.len());
+                        write_tokens(out, expr, names, depth + 2);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn indent(out: &mut String, depth: usize) {
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+}
+
+/// Append ` KIND [VALUE...]` for `kind`, returning the lexeme it implies.
+fn write_kind(out: &mut String, kind: &TokenKind, names: &Interner) -> Option<String> {
+    match kind {
+        TokenKind::Keyword(kw) => {
+            let _ = write!(out, " keyword {}", kw.as_str());
+            Some(kw.as_str().to_string())
+        }
+        TokenKind::Delimiter(d) => {
+            let _ = write!(out, " delimiter {}", d.as_str());
+            Some(d.as_str().to_string())
+        }
+        TokenKind::Identifier(symbol) => {
+            let name = names.resolve(*symbol);
+            out.push_str(" identifier ");
+            quote(out, name);
+            Some(name.to_string())
+        }
+        TokenKind::Eof => {
+            out.push_str(" eof");
+            Some(String::new())
+        }
+        TokenKind::Error { message_code } => {
+            let _ = write!(out, " error {}", message_code.as_str());
+            None
+        }
+        TokenKind::Literal(literal) => {
+            match literal {
+                Literals::StringLiteral(s) => {
+                    out.push_str(" string ");
+                    quote(out, s);
+                }
+                Literals::MultilineStringLiteral { raw, value } => {
+                    out.push_str(" multiline ");
+                    quote(out, raw);
+                    out.push(' ');
+                    quote(out, value);
+                }
+                Literals::CharacterLiteral(c) => {
+                    out.push_str(" char ");
+                    quote(out, c.encode_utf8(&mut [0; 4]));
+                }
+                Literals::ByteLiteral(b) => {
+                    let _ = write!(out, " byte {b}");
+                }
+                Literals::IntLiteral(v) => {
+                    let _ = write!(out, " int {v}");
+                }
+                Literals::UnsignedIntLiteral(v) => {
+                    let _ = write!(out, " unsigned {v}");
+                }
+                Literals::FloatLiteral(v) => {
+                    let _ = write!(out, " float {v}");
+                }
+                Literals::InterpolatedString(parts) => {
+                    let _ = write!(out, " interpolated {}", parts.len());
+                }
+                Literals::NumericLiteral { value, suffix } => {
+                    let _ = write!(out, " numeric {}", suffix.as_str());
+                    let _ = match value {
+                        NumericValue::Int(v) => write!(out, " {v}"),
+                        NumericValue::Unsigned(v) => write!(out, " {v}"),
+                        NumericValue::Float(v) => write!(out, " {v}"),
+                        NumericValue::Magnitude(v) => write!(out, " magnitude {v}"),
+                    };
+                }
+                Literals::BoolLiteral(b) => {
+                    let _ = write!(out, " bool {b}");
+                    return Some(b.to_string());
+                }
+            }
+            None
+        }
+        operator => {
+            let spelling = operator.fixed_spelling().unwrap_or_default();
+            let _ = write!(out, " operator {spelling}");
+            Some(spelling.to_string())
+        }
+    }
+}
+
+/// Append `text` as a double-quoted, escaped string.
+fn quote(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' => out.push_str("\\0"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{{{:x}}}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Read tokens from the snapshot format.
+///
+/// # Returns
+///
+/// - `Ok((Vec<Token>, Interner))` with the tokens in file order, and an
+///   interner holding the names of their identifiers
+/// - `Err(SnapshotError)` for a missing header, a malformed line, or a kind,
+///   spelling, or error code this lexer does not know
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::snapshot;
+/// # use hm_lexer::token::tokenkind::TokenKind;
+/// let text = format!(
+///     "{}\n\
+///      1:1-1:7  0..6  keyword return\n\
+///      1:7-1:7  6..6  eof\n",
+///     snapshot::header()
+/// );
+/// let (tokens, _) = snapshot::parse(&text).unwrap();
+/// assert!(matches!(tokens[0].kind, TokenKind::Keyword(_)));
+/// assert_eq!(tokens[0].lexeme, "return");
+/// assert!(tokens[1].is_eof());
+/// ```
+pub fn parse(text: &str) -> Result<(Vec<Token>, Interner), SnapshotError> {
+    let mut lines = text.lines().enumerate();
+    let header = lines.next().map_or("", |(_, line)| line.trim_end());
+    check_header(header)?;
+
+    let mut lines = lines
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+    let mut tokens = Vec::new();
+    let mut names = Interner::new();
+    while let Some((line, text)) = lines.next() {
+        tokens.push(parse_token(&mut lines, &mut names, line, text)?);
+    }
+    Ok((tokens, names))
+}
+
+/// Check that `header` names this build's format and lexer versions.
+fn check_header(header: &str) -> Result<(), SnapshotError> {
+    let versions = header
+        .strip_prefix(HEADER_PREFIX)
+        .ok_or(SnapshotError::MissingHeader)?;
+    let (format, lexer) = versions.split_once(" lexer ").unwrap_or((versions, ""));
+    let format: u64 = format.parse().map_err(|_| SnapshotError::MissingHeader)?;
+    if format != u64::from(FORMAT_VERSION) {
+        return Err(SnapshotError::UnsupportedVersion {
+            found: format,
+            expected: FORMAT_VERSION,
+        });
+    }
+    if lexer != crate::lexer_version() {
+        return Err(SnapshotError::LexerVersionMismatch {
+            found: lexer.to_string(),
+            expected: crate::lexer_version(),
+        });
+    }
+    Ok(())
+}
+
+/// Parse the token on `text`, then the part lines that follow it if it is
+/// an interpolated string.
+fn parse_token<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    names: &mut Interner,
+    line: usize,
+    text: &str,
+) -> Result<Token, SnapshotError> {
+    let (mut token, part_count) = parse_line(names, line, text)?;
+    let TokenKind::Literal(Literals::InterpolatedString(parts)) = &mut token.kind else {
+        return Ok(token);
+    };
+    for _ in 0..part_count {
+        let (line, text) = lines.next().ok_or(SnapshotError::Malformed {
+            line,
+            expected: "a string part",
+        })?;
+        let malformed = |expected| SnapshotError::Malformed { line, expected };
+        let fields = split_fields(text).ok_or(malformed("a closed quoted string"))?;
+        let part = match fields.as_slice() {
+            [tag, value] if tag.text == "text" && value.quoted => {
+                StringPart::Text(value.text.clone())
+            }
+            [tag, count] if tag.text == "expr" && !count.quoted => {
+                let count: usize = number(line, count.text.clone())?;
+                let mut expr = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let (line, text) = lines.next().ok_or(malformed("an expression token"))?;
+                    expr.push(parse_token(lines, names, line, text)?);
+                }
+                StringPart::Expr(expr)
+            }
+            _ => return Err(malformed("`text \"...\"` or `expr COUNT`")),
+        };
+        parts.push(part);
+    }
+    Ok(token)
+}
+
+/// Parse one token line, interning identifier names into `names`; `line`
+/// is its 1-based number, for errors.
+///
+/// Also returns how many part lines follow for an interpolated string.
+fn parse_line(
+    names: &mut Interner,
+    line: usize,
+    text: &str,
+) -> Result<(Token, usize), SnapshotError> {
+    let malformed = |expected| SnapshotError::Malformed { line, expected };
+    let fields = split_fields(text).ok_or(malformed("a closed quoted string"))?;
+    let mut fields = fields.into_iter().peekable();
+
+    let position = fields.next().ok_or(malformed("a position"))?;
+    let (line_start, column_start, line_end, column_end) =
+        parse_position(&position.text).ok_or(malformed("a position like 1:1-1:4"))?;
+    let range = fields.next().ok_or(malformed("a byte range"))?;
+    let (start, end) = parse_range(&range.text).ok_or(malformed("a byte range like 0..3"))?;
+    let char_offsets = match fields.peek() {
+        Some(field) if !field.quoted && is_char_range(&field.text) => {
+            let offsets = parse_range(&field.text[1..]).ok_or(malformed("a range like c0..3"))?;
+            fields.next();
+            Some(offsets)
+        }
+        _ => None,
+    };
+    let span = Span {
+        start,
+        end,
+        line_start,
+        column_start,
+        line_end,
+        column_end,
+        char_offsets,
+    };
+
+    let kind_name = fields.next().ok_or(malformed("a token kind"))?;
+    let mut value = |expected| match fields.next() {
+        Some(field) => Ok(field.text),
+        None => Err(malformed(expected)),
+    };
+    let unknown = |what, found: String| SnapshotError::Unknown { line, what, found };
+
+    let mut part_count = 0;
+    let (kind, implied) = match kind_name.text.as_str() {
+        "keyword" => {
+            let spelling = value("a keyword spelling")?;
+            let kind = TokenKind::keyword(&spelling)
+                .ok_or_else(|| unknown("spelling", spelling.clone()))?;
+            (kind, Some(spelling))
+        }
+        "delimiter" | "operator" => {
+            let spelling = value("a punctuation spelling")?;
+            let kind = grammar::punctuators()
+                .into_iter()
+                .find(|(text, _)| *text == spelling)
+                .map(|(_, kind)| kind)
+                .ok_or_else(|| unknown("spelling", spelling.clone()))?;
+            (kind, Some(spelling))
+        }
+        "identifier" => {
+            let name = value("an identifier name")?;
+            (TokenKind::Identifier(names.intern(&name)), Some(name))
+        }
+        "eof" => (TokenKind::Eof, Some(String::new())),
+        "error" => {
+            let code = value("an error code")?;
+            let message_code =
+                ErrorCode::from_code(&code).ok_or_else(|| unknown("error code", code))?;
+            (TokenKind::Error { message_code }, None)
+        }
+        "string" => (
+            literal(Literals::StringLiteral(value("a string value")?)),
+            None,
+        ),
+        "multiline" => {
+            let raw = value("the raw text")?;
+            let value = value("the string value")?;
+            (
+                literal(Literals::MultilineStringLiteral { raw, value }),
+                None,
+            )
+        }
+        "char" => {
+            let text = value("a character")?;
+            let mut chars = text.chars();
+            let (Some(c), None) = (chars.next(), chars.next()) else {
+                return Err(malformed("exactly one character"));
+            };
+            (literal(Literals::CharacterLiteral(c)), None)
+        }
+        "byte" => (
+            literal(Literals::ByteLiteral(number(line, value("a byte")?)?)),
+            None,
+        ),
+        "int" => (
+            literal(Literals::IntLiteral(number(line, value("an integer")?)?)),
+            None,
+        ),
+        "unsigned" => (
+            literal(Literals::UnsignedIntLiteral(number(
+                line,
+                value("an integer")?,
+            )?)),
+            None,
+        ),
+        "float" => (
+            literal(Literals::FloatLiteral(number(line, value("a float")?)?)),
+            None,
+        ),
+        "interpolated" => {
+            part_count = number(line, value("a part count")?)?;
+            (literal(Literals::InterpolatedString(Vec::new())), None)
+        }
+        "numeric" => {
+            let spelling = value("a type suffix")?;
+            let suffix = NumericSuffix::from_spelling(&spelling)
+                .ok_or_else(|| unknown("suffix", spelling))?;
+            let value = if suffix.is_float() {
+                NumericValue::Float(number(line, value("a float")?)?)
+            } else if suffix.is_unsigned() {
+                NumericValue::Unsigned(number(line, value("an integer")?)?)
+            } else {
+                match value("an integer")? {
+                    text if text == "magnitude" => {
+                        NumericValue::Magnitude(number(line, value("an integer")?)?)
+                    }
+                    text => NumericValue::Int(number(line, text)?),
+                }
+            };
+            (literal(Literals::NumericLiteral { value, suffix }), None)
+        }
+        "bool" => {
+            let text = value("true or false")?;
+            let b = match text.as_str() {
+                "true" => true,
+                "false" => false,
+                _ => return Err(malformed("true or false")),
+            };
+            (literal(Literals::BoolLiteral(b)), Some(text))
+        }
+        _ => return Err(unknown("kind", kind_name.text)),
+    };
+
+    let lexeme: Lexeme = match (fields.next(), implied) {
+        (Some(field), _) if field.quoted => owned_lexeme(&field.text),
+        (Some(_), _) => return Err(malformed("a quoted lexeme")),
+        (None, Some(implied)) => match kind.fixed_spelling() {
+            Some(text) if text == implied => static_lexeme(text),
+            _ => owned_lexeme(&implied),
+        },
+        (None, None) => return Err(malformed("a quoted lexeme")),
+    };
+    if fields.next().is_some() {
+        return Err(malformed("the end of the line"));
+    }
+
+    Ok((Token { kind, span, lexeme }, part_count))
+}
+
+/// Parse a numeric literal value.
+fn number<T: FromStr>(line: usize, text: String) -> Result<T, SnapshotError> {
+    text.parse().map_err(|_| SnapshotError::Malformed {
+        line,
+        expected: "a number",
+    })
+}
+
+fn literal(literal: Literals) -> TokenKind {
+    TokenKind::Literal(literal)
+}
+
+/// A whitespace-separated field of a token line.
+struct Field {
+    text: String,
+    quoted: bool,
+}
+
+/// Split a line into bare words and quoted strings, unescaping the latter.
+///
+/// Returns `None` for an unterminated string or a bad escape.
+fn split_fields(text: &str) -> Option<Vec<Field>> {
+    let mut fields = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if c != '"' {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek().filter(|c| !c.is_whitespace()) {
+                word.push(c);
+                chars.next();
+            }
+            fields.push(Field {
+                text: word,
+                quoted: false,
+            });
+            continue;
+        }
+
+        chars.next();
+        let mut value = String::new();
+        loop {
+            match chars.next()? {
+                '"' => break,
+                '\\' => value.push(match chars.next()? {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    '0' => '\0',
+                    'u' => {
+                        if chars.next()? != '{' {
+                            return None;
+                        }
+                        let hex: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                        char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+                    }
+                    c @ ('\\' | '"') => c,
+                    _ => return None,
+                }),
+                c => value.push(c),
+            }
+        }
+        fields.push(Field {
+            text: value,
+            quoted: true,
+        });
+    }
+    Some(fields)
+}
+
+/// Parse `LINE:COL-LINE:COL`.
+fn parse_position(text: &str) -> Option<(usize, usize, usize, usize)> {
+    let (start, end) = text.split_once('-')?;
+    let (line_start, column_start) = start.split_once(':')?;
+    let (line_end, column_end) = end.split_once(':')?;
+    Some((
+        line_start.parse().ok()?,
+        column_start.parse().ok()?,
+        line_end.parse().ok()?,
+        column_end.parse().ok()?,
+    ))
+}
+
+/// Whether a bare field is a character range such as `c0..3`, as opposed
+/// to a kind name like `char`.
+fn is_char_range(text: &str) -> bool {
+    text.strip_prefix('c')
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Parse `START..END`.
+fn parse_range(text: &str) -> Option<(usize, usize)> {
+    let (start, end) = text.split_once("..")?;
+    Some((start.parse().ok()?, end.parse().ok()?))
+}
@@ -0,0 +1,229 @@
+//! Source text indexable by [`Span`].
+//!
+//! Spans store byte offsets, so slicing the original text with them is the
+//! natural way to recover a token's text. [`Source`] wraps the text and
+//! checks that a span is in bounds and lies on UTF-8 character boundaries, so
+//! error reporters and the parser can write `&source[token.span]`.
+//!
+//! [`SourceFile`] attaches a name to a source. Text that never existed on
+//! disk (expanded imports, REPL cells, macro output) is lexed as a virtual
+//! file whose [`Provenance`] records which user code produced it, so a
+//! diagnostic on a generated token can point back at that code.
+
+use std::ops::Index;
+
+use crate::intern::Interner;
+use crate::lexerror::LexResult;
+use crate::token::span::Span;
+use crate::token::Token;
+
+/// Owned source text that can be indexed by [`Span`].
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::source::Source;
+/// # use hm_lexer::tokenize;
+/// let source = Source::new("var x = 42;");
+/// let (tokens, _) = tokenize(source.as_str()).unwrap();
+/// assert_eq!(&source[tokens[1].span], "x");
+/// ```
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Source {
+    /// The full source text.
+    text: String,
+}
+
+impl Source {
+    /// Wrap source text.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+
+    /// The full source text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Length of the source in bytes.
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// Whether the source is empty.
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Borrow the text covered by `span`.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&str)` if `start <= end <= len` and both ends lie on UTF-8
+    ///   character boundaries
+    /// - `None` otherwise, e.g. for a span produced from a different source
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::source::Source;
+    /// # use hm_lexer::token::span::Span;
+    /// let source = Source::new("é");
+    /// let span = |start, end| Span {
+    ///     start,
+    ///     end,
+    ///     line_start: 1,
+    ///     column_start: 1,
+    ///     line_end: 1,
+    ///     column_end: 1,
+    ///     char_offsets: None,
+    /// };
+    /// assert_eq!(source.get(span(0, 2)), Some("é"));
+    /// assert_eq!(source.get(span(0, 1)), None); // splits `é`
+    /// assert_eq!(source.get(span(0, 3)), None); // out of bounds
+    /// ```
+    pub fn get(&self, span: Span) -> Option<&str> {
+        if span.start > span.end {
+            return None;
+        }
+        self.text.get(span.start..span.end)
+    }
+}
+
+impl Index<Span> for Source {
+    type Output = str;
+
+    /// Borrow the text covered by `span`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the span is out of bounds or does not lie on UTF-8
+    /// character boundaries; use [`Source::get`] to check instead.
+    fn index(&self, span: Span) -> &str {
+        match self.get(span) {
+            Some(text) => text,
+            None => panic!(
+                "span {}..{} is not a valid range of a {}-byte source",
+                span.start,
+                span.end,
+                self.text.len()
+            ),
+        }
+    }
+}
+
+impl From<String> for Source {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&str> for Source {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+/// Where the text of a virtual [`SourceFile`] came from.
+#[derive(Clone)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Provenance {
+    /// Name of the file holding the code that produced the text. It may
+    /// itself be virtual, forming a chain back to user-written code.
+    pub origin: String,
+    /// The span in `origin` that expanded into the text, such as an
+    /// `import` statement or a macro invocation
+    pub expanded_from: Span,
+}
+
+/// A named source file, either read from disk or synthesized.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::source::{Provenance, SourceFile};
+/// let main = SourceFile::new("main.hm", "import prelude;");
+/// let import = main.tokenize().unwrap().0[0].span;
+///
+/// let expanded = SourceFile::synthesized(
+///     "<prelude>",
+///     "func print(s: string) {}",
+///     Provenance {
+///         origin: main.name().to_string(),
+///         expanded_from: import,
+///     },
+/// );
+/// let (tokens, _) = expanded.tokenize().unwrap();
+/// assert_eq!(&expanded.source()[tokens[1].span], "print");
+///
+/// // A diagnostic on `print` can also point at the `import` that caused it.
+/// let provenance = expanded.provenance().unwrap();
+/// assert_eq!(provenance.origin, "main.hm");
+/// assert_eq!(&main.source()[provenance.expanded_from], "import");
+/// ```
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct SourceFile {
+    /// Path or display name of the file.
+    name: String,
+
+    /// The file's text.
+    source: Source,
+
+    /// Where synthesized text came from; `None` for real files.
+    provenance: Option<Provenance>,
+}
+
+impl SourceFile {
+    /// Create a file from text the user wrote, e.g. read from disk.
+    pub fn new(name: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            source: Source::new(text),
+            provenance: None,
+        }
+    }
+
+    /// Create a virtual file from synthesized text.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Display name, conventionally in angle brackets (`<repl:3>`)
+    /// * `text` - The generated source text
+    /// * `provenance` - The user code the text was generated from
+    pub fn synthesized(
+        name: impl Into<String>,
+        text: impl Into<String>,
+        provenance: Provenance,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            source: Source::new(text),
+            provenance: Some(provenance),
+        }
+    }
+
+    /// Path or display name of the file.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The file's text.
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+
+    /// Where the file's text came from, if it was synthesized.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// Whether the file was synthesized rather than written by the user.
+    pub fn is_virtual(&self) -> bool {
+        self.provenance.is_some()
+    }
+
+    /// Lex the whole file with [`tokenize`](crate::tokenize).
+    pub fn tokenize(&self) -> LexResult<(Vec<Token>, Interner)> {
+        crate::tokenize(self.source.as_str())
+    }
+}
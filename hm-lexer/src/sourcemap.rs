@@ -0,0 +1,193 @@
+//! Registry of the files in a compilation.
+//!
+//! A multi-file build refers to files by a small [`FileId`] handed out by
+//! the [`SourceMap`] that owns them, instead of passing names or paths
+//! around. Files are stored behind [`Arc`] so token streams and caches can
+//! share them cheaply. A [`FileSpan`] pairs a [`Span`] with the file it
+//! points into, so diagnostics from different files can be collected
+//! together and resolved to a `file:line:column` [`Location`] at the end.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::lineindex::LineIndex;
+use crate::source::SourceFile;
+use crate::token::span::Span;
+
+/// Identifies a file registered with a [`SourceMap`].
+///
+/// Ids are only meaningful for the map that issued them.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct FileId(u32);
+
+impl FileId {
+    /// Position of the file in its map, in registration order.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Attach this file to a span lexed from it.
+    pub fn span(self, span: Span) -> FileSpan {
+        FileSpan { file: self, span }
+    }
+}
+
+/// A [`Span`] together with the file it points into.
+///
+/// Ordering compares the file first, so sorting diagnostics by `FileSpan`
+/// groups them by file in registration order and then by position.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct FileSpan {
+    /// The file the span was lexed from.
+    pub file: FileId,
+    /// Byte offsets and line/column positions within that file.
+    pub span: Span,
+}
+
+/// The start of a [`FileSpan`] as a file name with a 1-based line and
+/// column, displayed as `name:line:column`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct Location<'a> {
+    /// Path or display name of the file.
+    pub name: &'a str,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number.
+    pub column: usize,
+}
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.name, self.line, self.column)
+    }
+}
+
+/// The files of a compilation, in the order they were added.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::source::SourceFile;
+/// # use hm_lexer::sourcemap::SourceMap;
+/// let mut map = SourceMap::new();
+/// let main = map.add(SourceFile::new("main.hm", "import util;"));
+/// let util = map.add(SourceFile::new("util.hm", "func help() {}"));
+///
+/// assert_eq!(map.get(util).name(), "util.hm");
+/// assert!(map.find("main.hm") == Some(main));
+/// assert_eq!(map.len(), 2);
+/// ```
+#[derive(Clone, Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct SourceMap {
+    /// Registered files, indexed by `FileId`.
+    files: Vec<Arc<SourceFile>>,
+}
+
+impl SourceMap {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `file` and return its id.
+    ///
+    /// Files are not deduplicated; adding the same name twice yields two
+    /// ids.
+    pub fn add(&mut self, file: impl Into<Arc<SourceFile>>) -> FileId {
+        let id = FileId(u32::try_from(self.files.len()).expect("more than u32::MAX files"));
+        self.files.push(file.into());
+        id
+    }
+
+    /// The file registered under `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` was issued by a different map.
+    pub fn get(&self, id: FileId) -> &Arc<SourceFile> {
+        &self.files[id.index()]
+    }
+
+    /// The text `span` covers in its file.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(&str)` if the span is a valid range of the file
+    /// - `None` otherwise, e.g. for a span lexed from a different file
+    ///
+    /// # Panics
+    ///
+    /// Panics if `span.file` was issued by a different map.
+    pub fn text(&self, span: FileSpan) -> Option<&str> {
+        self.get(span.file).source().get(span.span)
+    }
+
+    /// Resolve the start of `span` to a file name, line, and column.
+    ///
+    /// Spans lexed with
+    /// [`LexerConfig::offsets_only`](crate::config::LexerConfig::offsets_only)
+    /// have no line information; their position is computed from the byte
+    /// offset instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `span.file` was issued by a different map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::source::SourceFile;
+    /// # use hm_lexer::sourcemap::SourceMap;
+    /// let mut map = SourceMap::new();
+    /// map.add(SourceFile::new("main.hm", "import util;"));
+    /// let util = map.add(SourceFile::new("util.hm", "func help() {\n    oops\n}"));
+    ///
+    /// let (tokens, _) = map.get(util).tokenize().unwrap();
+    /// let oops = util.span(tokens[5].span);
+    /// assert_eq!(map.text(oops), Some("oops"));
+    /// assert_eq!(map.location(oops).to_string(), "util.hm:2:5");
+    /// ```
+    pub fn location(&self, span: FileSpan) -> Location<'_> {
+        let file = self.get(span.file);
+        let (line, column) = if span.span.line_start == 0 {
+            LineIndex::new(file.source().as_str()).line_column(span.span.start)
+        } else {
+            (span.span.line_start, span.span.column_start)
+        };
+        Location {
+            name: file.name(),
+            line,
+            column,
+        }
+    }
+
+    /// The id of the first file registered with this name.
+    pub fn find(&self, name: &str) -> Option<FileId> {
+        self.files
+            .iter()
+            .position(|file| file.name() == name)
+            .map(|index| FileId(index as u32))
+    }
+
+    /// Number of registered files.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether no files are registered.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Iterate over the files with their ids, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = (FileId, &Arc<SourceFile>)> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| (FileId(index as u32), file))
+    }
+}
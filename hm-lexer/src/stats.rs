@@ -0,0 +1,179 @@
+//! Source statistics gathered while lexing.
+//!
+//! [`LexStats::collect`] lexes a source once and tallies tokens by category,
+//! comment density, line lengths, and identifier usage, along with how long
+//! lexing took. It backs `hm-lex stats` and is cheap enough to run over a
+//! whole codebase for a quick health snapshot.
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "alloc-stats")]
+use crate::allocstats::LexTally;
+use crate::charstream::CharStream;
+use crate::config::LexerConfig;
+use crate::highlight::comment_ranges;
+use crate::lexer::Lexer;
+use crate::token::tokenkind::TokenKind;
+
+/// Statistics about a lexed source.
+#[derive(Clone, Default)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct LexStats {
+    /// Size of the source in bytes.
+    pub bytes: usize,
+
+    /// Number of lines in the source.
+    pub lines: usize,
+
+    /// Number of tokens produced, excluding the final `Eof`.
+    pub tokens: usize,
+
+    /// Token counts keyed by category name (`"keyword"`, `"type"`,
+    /// `"identifier"`, `"literal"`, `"delimiter"`, `"operator"`, `"error"`).
+    pub by_category: BTreeMap<&'static str, usize>,
+
+    /// Number of comments.
+    pub comments: usize,
+
+    /// Total bytes inside comments, including their markers.
+    pub comment_bytes: usize,
+
+    /// 1-based number and byte length of the longest line.
+    pub longest_line: (usize, usize),
+
+    /// How many times each identifier occurs.
+    pub identifiers: HashMap<String, usize>,
+
+    /// Time spent lexing, excluding statistics bookkeeping.
+    pub lex_time: Duration,
+
+    /// Heap allocations made by the lexer, excluding statistics
+    /// bookkeeping. Zero unless
+    /// [`CountingAllocator`](crate::allocstats::CountingAllocator) is the
+    /// global allocator.
+    #[cfg(feature = "alloc-stats")]
+    pub allocations: usize,
+
+    /// Bytes requested by the lexer's heap allocations.
+    #[cfg(feature = "alloc-stats")]
+    pub bytes_allocated: usize,
+
+    /// Most heap bytes the lexer held at once, including its copy of the
+    /// source.
+    #[cfg(feature = "alloc-stats")]
+    pub peak_live_bytes: usize,
+}
+
+impl LexStats {
+    /// Lex `source` and gather statistics about it.
+    ///
+    /// Lexing runs in error-recovery mode so malformed regions are counted
+    /// under the `"error"` category instead of aborting the scan.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::stats::LexStats;
+    /// let stats = LexStats::collect("var x = x + 1; // bump\n");
+    /// assert_eq!(stats.tokens, 7);
+    /// assert_eq!(stats.by_category["identifier"], 2);
+    /// assert_eq!(stats.identifiers["x"], 2);
+    /// assert_eq!(stats.comments, 1);
+    /// ```
+    pub fn collect(source: &str) -> Self {
+        let bytes = source.as_bytes();
+        let mut stats = LexStats {
+            bytes: bytes.len(),
+            ..LexStats::default()
+        };
+
+        for (i, line) in source.lines().enumerate() {
+            stats.lines += 1;
+            if line.len() > stats.longest_line.1 {
+                stats.longest_line = (i + 1, line.len());
+            }
+        }
+
+        #[cfg(feature = "alloc-stats")]
+        let mut tally = LexTally::start();
+        let Ok(stream) = CharStream::from_bytes(bytes) else {
+            return stats;
+        };
+        let config = LexerConfig {
+            error_recovery: true,
+            ..LexerConfig::default()
+        };
+        let mut lexer = Lexer::with_config(stream, config);
+
+        let mut previous_end = 0;
+        loop {
+            #[cfg(feature = "alloc-stats")]
+            tally.resume();
+            let started = Instant::now();
+            let next = lexer.next_token();
+            stats.lex_time += started.elapsed();
+            #[cfg(feature = "alloc-stats")]
+            tally.pause();
+            let Ok(token) = next else {
+                break;
+            };
+
+            for comment in comment_ranges(bytes, previous_end..token.span.start) {
+                stats.comments += 1;
+                stats.comment_bytes += comment.len();
+            }
+            previous_end = token.span.end;
+
+            if token.is_eof() {
+                break;
+            }
+            stats.tokens += 1;
+            *stats.by_category.entry(category(&token.kind)).or_default() += 1;
+            if let TokenKind::Identifier(symbol) = token.kind {
+                let name = lexer.interner().resolve(symbol);
+                *stats.identifiers.entry(name.to_string()).or_default() += 1;
+            }
+        }
+
+        #[cfg(feature = "alloc-stats")]
+        {
+            stats.allocations = tally.allocations;
+            stats.bytes_allocated = tally.bytes_allocated;
+            stats.peak_live_bytes = tally.peak_live_bytes;
+        }
+        stats
+    }
+
+    /// Fraction of source bytes that are inside comments, from 0.0 to 1.0.
+    pub fn comment_density(&self) -> f64 {
+        if self.bytes == 0 {
+            0.0
+        } else {
+            self.comment_bytes as f64 / self.bytes as f64
+        }
+    }
+
+    /// The `n` most frequent identifiers, most frequent first. Ties are
+    /// broken alphabetically.
+    pub fn top_identifiers(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut top: Vec<(&str, usize)> = self
+            .identifiers
+            .iter()
+            .map(|(name, &count)| (name.as_str(), count))
+            .collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        top.truncate(n);
+        top
+    }
+}
+
+/// Category name under which a token is counted; type keywords are counted
+/// apart from other keywords.
+fn category(kind: &TokenKind) -> &'static str {
+    if kind.is_type_keyword() {
+        "type"
+    } else {
+        kind.category().as_str()
+    }
+}
@@ -0,0 +1,316 @@
+//! TextMate scope mapping for editor syntax highlighting.
+//!
+//! [`scope`] assigns every [`TokenKind`] a TextMate scope name, and
+//! [`tm_language_json`] turns the keyword and punctuation tables from
+//! [`crate::grammar`] into a `.tmLanguage.json` skeleton. Regenerating the
+//! grammar file after a lexer change keeps editor highlighting (e.g. a VS Code
+//! extension) mechanically in sync with what the lexer actually accepts.
+
+use std::fmt::Write;
+
+use crate::grammar::{self, json_string};
+use crate::intern::Interner;
+use crate::token::delimiters::Delimiters;
+use crate::token::keywords::{Keywords, TypeKind};
+use crate::token::literals::Literals;
+use crate::token::operators::assignment::AssignmentOps;
+use crate::token::operators::SpecialOps;
+use crate::token::tokenkind::TokenKind;
+
+/// Root scope of Hummingbird source files.
+pub const SOURCE_SCOPE: &str = "source.hm";
+
+/// Return the TextMate scope name used to highlight tokens of `kind`.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::textmate::scope;
+/// # use hm_lexer::token::keywords::Keywords;
+/// # use hm_lexer::token::tokenkind::TokenKind;
+/// assert_eq!(scope(&TokenKind::Keyword(Keywords::If)), "keyword.control.hm");
+/// ```
+pub fn scope(kind: &TokenKind) -> &'static str {
+    match kind {
+        TokenKind::Keyword(kw) => match kw {
+            Keywords::Return
+            | Keywords::If
+            | Keywords::Else
+            | Keywords::Elif
+            | Keywords::Loop
+            | Keywords::Switch
+            | Keywords::Case => "keyword.control.hm",
+            Keywords::Import => "keyword.control.import.hm",
+            Keywords::Null => "constant.language.null.hm",
+            Keywords::Func => "storage.type.function.hm",
+            Keywords::Var | Keywords::Const | Keywords::Final => "storage.modifier.hm",
+            Keywords::Type(TypeKind::Struct) => "storage.type.struct.hm",
+            Keywords::Type(_) => "storage.type.primitive.hm",
+        },
+        TokenKind::Identifier(_) => "variable.other.hm",
+        TokenKind::Literal(literal) => match literal {
+            Literals::StringLiteral(_) | Literals::InterpolatedString(_) => {
+                "string.quoted.double.hm"
+            }
+            Literals::MultilineStringLiteral { .. } => "string.quoted.triple.hm",
+            Literals::CharacterLiteral(_) | Literals::ByteLiteral(_) => "string.quoted.single.hm",
+            Literals::IntLiteral(_) | Literals::UnsignedIntLiteral(_) => {
+                "constant.numeric.integer.hm"
+            }
+            Literals::FloatLiteral(_) => "constant.numeric.float.hm",
+            Literals::NumericLiteral { suffix, .. } if suffix.is_float() => {
+                "constant.numeric.float.hm"
+            }
+            Literals::NumericLiteral { .. } => "constant.numeric.integer.hm",
+            Literals::BoolLiteral(_) => "constant.language.boolean.hm",
+        },
+        TokenKind::Delimiter(d) => match d {
+            Delimiters::LeftParen | Delimiters::RightParen => "punctuation.section.parens.hm",
+            Delimiters::LeftBrace | Delimiters::RightBrace => "punctuation.section.block.hm",
+            Delimiters::LeftBracket | Delimiters::RightBracket => "punctuation.section.brackets.hm",
+            Delimiters::Colon => "punctuation.separator.colon.hm",
+            Delimiters::Semicolon => "punctuation.terminator.statement.hm",
+            Delimiters::Comma => "punctuation.separator.comma.hm",
+            Delimiters::Dot => "punctuation.accessor.hm",
+            Delimiters::QuestionMark => "keyword.operator.ternary.hm",
+        },
+        TokenKind::ArithmeticOperator(_) => "keyword.operator.arithmetic.hm",
+        TokenKind::RelationalOperator(_) => "keyword.operator.comparison.hm",
+        TokenKind::LogicalOperator(_) => "keyword.operator.logical.hm",
+        TokenKind::AssignmentOperator(AssignmentOps::Assign) => "keyword.operator.assignment.hm",
+        TokenKind::AssignmentOperator(_) => "keyword.operator.assignment.compound.hm",
+        TokenKind::BitwiseOperator(_) => "keyword.operator.bitwise.hm",
+        TokenKind::SpecialOperator(SpecialOps::ScopingOperator) => {
+            "punctuation.separator.namespace.hm"
+        }
+        TokenKind::SpecialOperator(SpecialOps::PointerAccess) => "punctuation.accessor.arrow.hm",
+        TokenKind::SpecialOperator(SpecialOps::Ellipsis) => "keyword.operator.variadic.hm",
+        TokenKind::SpecialOperator(SpecialOps::NullCoalescing) => {
+            "keyword.operator.null-coalescing.hm"
+        }
+        TokenKind::Error { .. } => "invalid.illegal.hm",
+        TokenKind::Eof => SOURCE_SCOPE,
+    }
+}
+
+/// Generate a `.tmLanguage.json` grammar skeleton for Hummingbird.
+///
+/// Keywords and punctuation become alternation regexes named by their
+/// [`scope`], ordered longest spelling first across all scopes so a prefix
+/// such as `<` never shadows a longer operator such as `<<=`, even when the
+/// two are highlighted differently. Comments, string/char literals with their
+/// escapes, and numeric literals get hand-written patterns whose scope names
+/// still come from [`scope`].
+///
+/// # Example
+///
+/// ```
+/// let json = hm_lexer::textmate::tm_language_json();
+/// assert!(json.contains("\"scopeName\": \"source.hm\""));
+/// assert!(json.contains("elif"));
+/// ```
+pub fn tm_language_json() -> String {
+    let string_scope = scope(&TokenKind::Literal(Literals::StringLiteral(String::new())));
+    let multiline_scope = scope(&TokenKind::Literal(Literals::MultilineStringLiteral {
+        raw: String::new(),
+        value: String::new(),
+    }));
+    let char_scope = scope(&TokenKind::Literal(Literals::CharacterLiteral(' ')));
+    let int_scope = scope(&TokenKind::Literal(Literals::IntLiteral(0)));
+    let float_scope = scope(&TokenKind::Literal(Literals::FloatLiteral(0.0)));
+    let bool_scope = scope(&TokenKind::Literal(Literals::BoolLiteral(true)));
+    let ident_scope = scope(&TokenKind::Identifier(Interner::new().intern("_")));
+
+    let keywords: Vec<_> = grammar::keywords()
+        .into_iter()
+        .map(|(spelling, kind)| (spelling, scope(&kind)))
+        .collect();
+    let punctuation: Vec<_> = grammar::punctuators()
+        .into_iter()
+        .map(|(spelling, kind)| (spelling, scope(&kind)))
+        .collect();
+
+    let mut json = String::new();
+    json.push_str("{\n");
+    json.push_str(
+        "  \"$schema\": \"https://raw.githubusercontent.com/martinring/tmlanguage/master/tmlanguage.json\",\n",
+    );
+    json.push_str("  \"name\": \"Hummingbird\",\n");
+    let _ = writeln!(json, "  \"scopeName\": {},", json_string(SOURCE_SCOPE));
+    json.push_str("  \"fileTypes\": [\"hm\"],\n");
+    json.push_str("  \"patterns\": [\n");
+    let sections = [
+        "comments",
+        "strings",
+        "characters",
+        "numbers",
+        "keywords",
+        "punctuation",
+        "identifiers",
+    ];
+    for (i, section) in sections.iter().enumerate() {
+        let comma = if i + 1 < sections.len() { "," } else { "" };
+        let _ = writeln!(json, "    {{ \"include\": \"#{section}\" }}{comma}");
+    }
+    json.push_str("  ],\n");
+    json.push_str("  \"repository\": {\n");
+
+    json.push_str("    \"comments\": { \"patterns\": [\n");
+    push_match(&mut json, "comment.line.double-slash.hm", "//.*$", true);
+    push_match(&mut json, "comment.line.shebang.hm", "\\A#!.*$", true);
+    json.push_str("      { \"include\": \"#block_comment\" }\n");
+    json.push_str("    ] },\n");
+
+    // Block comments nest, so the rule includes itself.
+    let _ = writeln!(
+        json,
+        "    \"block_comment\": {{ \"name\": \"comment.block.hm\", \"begin\": {}, \"end\": {}, \
+         \"patterns\": [{{ \"include\": \"#block_comment\" }}] }},",
+        json_string("/\\*"),
+        json_string("\\*/")
+    );
+
+    json.push_str("    \"strings\": { \"patterns\": [\n");
+    push_begin_end(
+        &mut json,
+        multiline_scope,
+        "\"\"\"",
+        "\"\"\"",
+        Some("\\\\(?:[ntr0\\\\\"$]|u\\{[0-9a-fA-F]{1,6}\\})"),
+        true,
+    );
+    push_begin_end(
+        &mut json,
+        string_scope,
+        "\"",
+        "\"",
+        Some("\\\\(?:[ntr0\\\\\"$]|u\\{[0-9a-fA-F]{1,6}\\})"),
+        false,
+    );
+    json.push_str("    ] },\n");
+
+    json.push_str("    \"characters\": { \"patterns\": [\n");
+    push_begin_end(
+        &mut json,
+        char_scope,
+        "\\bb?'",
+        "'",
+        Some("\\\\(?:[ntr0\\\\'$]|u\\{[0-9a-fA-F]{1,6}\\})"),
+        false,
+    );
+    json.push_str("    ] },\n");
+
+    json.push_str("    \"numbers\": { \"patterns\": [\n");
+    push_match(
+        &mut json,
+        float_scope,
+        "\\b[0-9][0-9_]*\\.[0-9][0-9_]*(?:f32|f64)?\\b",
+        true,
+    );
+    push_match(
+        &mut json,
+        int_scope,
+        "\\b(?:0b[01_]+|0o[0-7_]+|[0-9][0-9_]*)(?:[iu](?:8|16|32|64)|u)?\\b",
+        false,
+    );
+    json.push_str("    ] },\n");
+
+    json.push_str("    \"keywords\": { \"patterns\": [\n");
+    push_match(&mut json, bool_scope, "\\b(?:true|false)\\b", true);
+    push_alternations(&mut json, &keywords, true);
+    json.push_str("    ] },\n");
+
+    json.push_str("    \"punctuation\": { \"patterns\": [\n");
+    push_alternations(&mut json, &punctuation, false);
+    json.push_str("    ] },\n");
+
+    json.push_str("    \"identifiers\": { \"patterns\": [\n");
+    push_match(
+        &mut json,
+        ident_scope,
+        "\\b[A-Za-z_][A-Za-z0-9_]*\\b",
+        false,
+    );
+    json.push_str("    ] }\n");
+
+    json.push_str("  }\n");
+    json.push_str("}\n");
+    json
+}
+
+/// Append `{ "name", "match" }` rules matching every `(spelling, scope)`
+/// pair. TextMate tries rules in order, so spellings are sorted longest
+/// first across all scopes; each run of spellings sharing a scope becomes
+/// one alternation. Word boundaries are added around keyword alternations.
+fn push_alternations(json: &mut String, spellings: &[(&str, &str)], words: bool) {
+    let mut spellings = spellings.to_vec();
+    spellings.sort_by(|(a, a_scope), (b, b_scope)| {
+        b.len()
+            .cmp(&a.len())
+            .then(a_scope.cmp(b_scope))
+            .then(a.cmp(b))
+    });
+    let runs: Vec<_> = spellings
+        .chunk_by(|(_, a_scope), (_, b_scope)| a_scope == b_scope)
+        .collect();
+    for (i, run) in runs.iter().enumerate() {
+        let alternation = run
+            .iter()
+            .map(|(s, _)| regex_escape(s))
+            .collect::<Vec<_>>()
+            .join("|");
+        let pattern = if words {
+            format!("\\b(?:{alternation})\\b")
+        } else {
+            format!("(?:{alternation})")
+        };
+        push_match(json, run[0].1, &pattern, i + 1 < runs.len());
+    }
+}
+
+fn push_match(json: &mut String, name: &str, pattern: &str, comma: bool) {
+    let _ = writeln!(
+        json,
+        "      {{ \"name\": {}, \"match\": {} }}{}",
+        json_string(name),
+        json_string(pattern),
+        if comma { "," } else { "" }
+    );
+}
+
+fn push_begin_end(
+    json: &mut String,
+    name: &str,
+    begin: &str,
+    end: &str,
+    escape: Option<&str>,
+    comma: bool,
+) {
+    let _ = write!(
+        json,
+        "      {{ \"name\": {}, \"begin\": {}, \"end\": {}",
+        json_string(name),
+        json_string(begin),
+        json_string(end)
+    );
+    if let Some(escape) = escape {
+        let _ = write!(
+            json,
+            ", \"patterns\": [{{ \"name\": \"constant.character.escape.hm\", \"match\": {} }}]",
+            json_string(escape)
+        );
+    }
+    let _ = writeln!(json, " }}{}", if comma { "," } else { "" });
+}
+
+/// Escape regex metacharacters in a literal spelling.
+fn regex_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.^$|?*+()[]{}/".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
@@ -1,14 +1,55 @@
 //! Token representation and related types.
 //!
 //! This module contains the core token structure and its constituent parts:
-//! token kinds, source spans, and lexeme strings.
+//! token kinds, source spans, and lexeme strings, plus the trivia that
+//! lossless lexing attaches to tokens.
 
+pub mod builder;
 pub mod span;
 pub mod tokenkind;
 pub mod operators;
 pub mod keywords;
 pub mod literals;
 pub mod delimiters;
+pub mod trivia;
+
+/// Storage for lexeme and identifier text.
+///
+/// By default this is a `Cow<'static, str>`: text with a fixed spelling
+/// (keywords) borrows a static string and never allocates, while
+/// identifiers and literals own their text. With the `smol_str` feature
+/// enabled it is [`smol_str::SmolStr`], which stores short strings inline
+/// and clones in O(1); most Hummingbird tokens are only a few bytes long.
+#[cfg(not(feature = "smol_str"))]
+pub type Lexeme = std::borrow::Cow<'static, str>;
+
+/// Storage for lexeme and identifier text, backed by [`smol_str::SmolStr`].
+#[cfg(feature = "smol_str")]
+pub type Lexeme = smol_str::SmolStr;
+
+/// Build a [`Lexeme`] for text with a fixed spelling, without allocating.
+#[cfg(not(feature = "smol_str"))]
+pub(crate) fn static_lexeme(text: &'static str) -> Lexeme {
+    std::borrow::Cow::Borrowed(text)
+}
+
+/// Build a [`Lexeme`] for text with a fixed spelling, without allocating.
+#[cfg(feature = "smol_str")]
+pub(crate) fn static_lexeme(text: &'static str) -> Lexeme {
+    smol_str::SmolStr::new_static(text)
+}
+
+/// Build a [`Lexeme`] by copying text out of the source.
+#[cfg(not(feature = "smol_str"))]
+pub(crate) fn owned_lexeme(text: &str) -> Lexeme {
+    std::borrow::Cow::Owned(text.to_owned())
+}
+
+/// Build a [`Lexeme`] by copying text out of the source.
+#[cfg(feature = "smol_str")]
+pub(crate) fn owned_lexeme(text: &str) -> Lexeme {
+    smol_str::SmolStr::new(text)
+}
 
 /// A single token produced by the lexer.
 ///
@@ -25,10 +66,10 @@ pub mod delimiters;
 /// # Example
 ///
 /// ```no_run
-/// # use hm_lexer::token::{Token, tokenkind::TokenKind, span::Span};
+/// # use hm_lexer::token::{Token, tokenkind::TokenKind, span::Span, literals::Literals};
 /// # fn example_token() {
 /// let token = Token {
-///     kind: TokenKind::IntLiteral(42),
+///     kind: TokenKind::Literal(Literals::IntLiteral(42)),
 ///     span: Span {
 ///         start: 0,
 ///         end: 2,
@@ -36,19 +77,21 @@ pub mod delimiters;
 ///         column_start: 1,
 ///         line_end: 1,
 ///         column_end: 3,
+///         char_offsets: None,
 ///     },
-///     lexeme: "42".to_string(),
+///     lexeme: "42".into(),
 /// };
 /// # }
 /// ```
-#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Token {
     /// The type and classification of this token.
     pub kind: tokenkind::TokenKind,
     /// The source location (byte offset and line/column) of this token.
     pub span: span::Span,
     /// The original lexeme (text) from the source code.
-    pub lexeme: String,
+    pub lexeme: Lexeme,
 }
 
 impl Token {
@@ -60,4 +103,131 @@ impl Token {
     pub fn is_eof(&self) -> bool {
         matches!(self.kind, tokenkind::TokenKind::Eof)
     }
+
+    /// The source text of this token, sliced from the source it was lexed
+    /// from.
+    ///
+    /// Equal to [`lexeme`](Self::lexeme) unless the lexer was configured
+    /// with [`LexerConfig::lazy_lexemes`](crate::config::LexerConfig::lazy_lexemes),
+    /// which leaves lexemes empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the span is out of bounds for `source` or does not fall
+    /// on character boundaries, as when `source` is not the text the token
+    /// was lexed from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::tokenize;
+    /// let source = "var name = \"hi\";";
+    /// let (tokens, _) = tokenize(source).unwrap();
+    /// assert_eq!(tokens[3].text(source), "\"hi\"");
+    /// assert_eq!(tokens[3].text(source), tokens[3].lexeme);
+    /// ```
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.span.start..self.span.end]
+    }
+
+    /// Move the token, and the tokens of any interpolations inside it, down
+    /// by `lines` lines and forward by `bytes` bytes; see [`Span::shift`].
+    pub(crate) fn shift(&mut self, bytes: usize, lines: usize) {
+        self.span.shift(bytes, lines);
+        if let tokenkind::TokenKind::Literal(literals::Literals::InterpolatedString(parts)) =
+            &mut self.kind
+        {
+            for part in parts {
+                if let literals::StringPart::Expr(expr) = part {
+                    for token in expr {
+                        token.shift(bytes, lines);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Move the symbols of the token, and of the tokens of any
+    /// interpolations inside it, from the interner `names` they were lexed
+    /// with into `into`.
+    ///
+    /// Reinterning tokens in source order gives `into` the same symbols
+    /// lexing them all with one lexer would have.
+    pub(crate) fn reintern(
+        &mut self,
+        names: &crate::intern::Interner,
+        into: &mut crate::intern::Interner,
+    ) {
+        match &mut self.kind {
+            tokenkind::TokenKind::Identifier(symbol) => {
+                *symbol = into.intern(names.resolve(*symbol));
+            }
+            tokenkind::TokenKind::Literal(literals::Literals::InterpolatedString(parts)) => {
+                for part in parts {
+                    if let literals::StringPart::Expr(expr) = part {
+                        for token in expr {
+                            token.reintern(names, into);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A token whose lexeme borrows the source it was lexed from.
+///
+/// Produced by [`tokenize_borrowed`](crate::tokenize_borrowed). The lexeme
+/// is always `&source[span.start..span.end]`, so a token list holds no
+/// copies of the source text; convert to an owned [`Token`] with
+/// [`into_owned`](Self::into_owned) or `Token::from` when a token has to
+/// outlive the source.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::token::Token;
+/// # use hm_lexer::tokenize_borrowed;
+/// let source = String::from("var answer = 42;");
+/// let (tokens, _) = tokenize_borrowed(&source).unwrap();
+/// assert_eq!(tokens[1].lexeme, "answer");
+///
+/// let owned: Token = tokens[1].clone().into_owned();
+/// drop(tokens);
+/// drop(source);
+/// assert_eq!(owned.lexeme, "answer");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorrowedToken<'src> {
+    /// The type and classification of this token.
+    pub kind: tokenkind::TokenKind,
+    /// The source location (byte offset and line/column) of this token.
+    pub span: span::Span,
+    /// The original lexeme, borrowed from the source.
+    pub lexeme: &'src str,
+}
+
+impl<'src> BorrowedToken<'src> {
+    /// Checks if this token is the end-of-file (EOF) token.
+    pub fn is_eof(&self) -> bool {
+        matches!(self.kind, tokenkind::TokenKind::Eof)
+    }
+
+    /// Copy the lexeme out of the source, producing a [`Token`] that does
+    /// not borrow it.
+    pub fn into_owned(self) -> Token {
+        Token {
+            kind: self.kind,
+            span: self.span,
+            lexeme: owned_lexeme(self.lexeme),
+        }
+    }
+}
+
+impl From<BorrowedToken<'_>> for Token {
+    fn from(token: BorrowedToken<'_>) -> Token {
+        token.into_owned()
+    }
 }
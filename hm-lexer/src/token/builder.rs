@@ -0,0 +1,196 @@
+//! Programmatic token construction.
+//!
+//! Refactoring tools and code generators often need to fabricate tokens that
+//! never came out of the lexer. [`TokenBuilder`] fills in a lexeme that
+//! spells the kind and a span whose end is computed from that lexeme, so
+//! callers only state what differs from the defaults. Identifiers are built
+//! with [`Token::identifier_builder`], which interns their name.
+
+use crate::intern::Interner;
+use crate::token::literals::{Literals, NumericValue};
+use crate::token::span::Span;
+use crate::token::tokenkind::TokenKind;
+use crate::token::{owned_lexeme, static_lexeme, Lexeme, Token};
+
+/// Builder for [`Token`]s created outside the lexer.
+///
+/// Created with [`Token::builder`]. Defaults:
+///
+/// - **lexeme**: the kind's source spelling, e.g. `->` for the pointer-access
+///   operator or a re-escaped string literal, or the name given to
+///   [`Token::identifier_builder`]
+/// - **span**: starts at byte 0, line 1, column 1 and ends after the lexeme,
+///   counting byte columns and `\n` line breaks
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::token::Token;
+/// # use hm_lexer::token::keywords::Keywords;
+/// # use hm_lexer::token::tokenkind::TokenKind;
+/// let token = Token::builder(TokenKind::Keyword(Keywords::Return))
+///     .at(10, 2, 5)
+///     .build();
+/// assert_eq!(token.lexeme, "return");
+/// assert_eq!((token.span.start, token.span.end), (10, 16));
+/// assert_eq!((token.span.line_end, token.span.column_end), (2, 11));
+/// ```
+pub struct TokenBuilder {
+    kind: TokenKind,
+    lexeme: Option<Lexeme>,
+    start: (usize, usize, usize),
+    span: Option<Span>,
+}
+
+impl Token {
+    /// Start building a token of the given kind.
+    ///
+    /// See [`TokenBuilder`] for the defaults applied to unset fields.
+    pub fn builder(kind: TokenKind) -> TokenBuilder {
+        TokenBuilder {
+            kind,
+            lexeme: None,
+            start: (0, 1, 1),
+            span: None,
+        }
+    }
+
+    /// Start building an identifier token for `name`, interning it in
+    /// `names`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::intern::Interner;
+    /// # use hm_lexer::token::Token;
+    /// # use hm_lexer::token::tokenkind::TokenKind;
+    /// let mut names = Interner::new();
+    /// let token = Token::identifier_builder("count", &mut names).build();
+    /// assert_eq!(token.lexeme, "count");
+    /// assert_eq!(token.kind, TokenKind::Identifier(names.get("count").unwrap()));
+    /// ```
+    pub fn identifier_builder(name: &str, names: &mut Interner) -> TokenBuilder {
+        Token::builder(TokenKind::Identifier(names.intern(name))).lexeme(owned_lexeme(name))
+    }
+}
+
+impl TokenBuilder {
+    /// Use `lexeme` as the token's source text instead of the default spelling.
+    pub fn lexeme(mut self, lexeme: impl Into<Lexeme>) -> Self {
+        self.lexeme = Some(lexeme.into());
+        self
+    }
+
+    /// Place the token's start at byte `offset`, 1-based `line` and `column`.
+    ///
+    /// The end of the span is computed from the lexeme. Ignored if an
+    /// explicit [`span`](Self::span) is set.
+    pub fn at(mut self, offset: usize, line: usize, column: usize) -> Self {
+        self.start = (offset, line, column);
+        self
+    }
+
+    /// Use `span` verbatim instead of computing it from the lexeme.
+    pub fn span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Finish building the token.
+    pub fn build(self) -> Token {
+        let lexeme = self.lexeme.unwrap_or_else(|| default_lexeme(&self.kind));
+        let span = self.span.unwrap_or_else(|| {
+            let (start, line_start, column_start) = self.start;
+            let mut line_end = line_start;
+            let mut column_end = column_start;
+            for b in lexeme.bytes() {
+                if b == b'\n' {
+                    line_end += 1;
+                    column_end = 1;
+                } else {
+                    column_end += 1;
+                }
+            }
+            Span {
+                start,
+                end: start + lexeme.len(),
+                line_start,
+                column_start,
+                line_end,
+                column_end,
+                char_offsets: None,
+            }
+        });
+        Token {
+            kind: self.kind,
+            span,
+            lexeme,
+        }
+    }
+}
+
+/// Source text that the lexer would turn back into `kind`.
+fn default_lexeme(kind: &TokenKind) -> Lexeme {
+    if let Some(text) = kind.fixed_spelling() {
+        return static_lexeme(text);
+    }
+    match kind {
+        TokenKind::Literal(Literals::IntLiteral(v)) => owned_lexeme(&v.to_string()),
+        TokenKind::Literal(Literals::UnsignedIntLiteral(v)) => owned_lexeme(&format!("{v}u")),
+        TokenKind::Literal(Literals::FloatLiteral(v)) => owned_lexeme(&float_text(*v)),
+        TokenKind::Literal(Literals::NumericLiteral { value, suffix }) => {
+            let text = match value {
+                NumericValue::Int(v) => v.to_string(),
+                NumericValue::Unsigned(v) | NumericValue::Magnitude(v) => v.to_string(),
+                NumericValue::Float(v) => float_text(*v),
+            };
+            owned_lexeme(&format!("{text}{}", suffix.as_str()))
+        }
+        TokenKind::Literal(Literals::StringLiteral(s)) => owned_lexeme(&quote(s, '"')),
+        TokenKind::Literal(Literals::MultilineStringLiteral { raw, .. }) => {
+            owned_lexeme(&format!("\"\"\"{raw}\"\"\""))
+        }
+        TokenKind::Literal(Literals::CharacterLiteral(c)) => {
+            owned_lexeme(&quote(&c.to_string(), '\''))
+        }
+        TokenKind::Literal(Literals::ByteLiteral(b)) => {
+            owned_lexeme(&format!("b{}", quote(&char::from(*b).to_string(), '\'')))
+        }
+        TokenKind::Literal(Literals::BoolLiteral(b)) => {
+            static_lexeme(if *b { "true" } else { "false" })
+        }
+        _ => Lexeme::default(),
+    }
+}
+
+/// `value` as the lexer would read it back as a float.
+fn float_text(value: f64) -> String {
+    // Whole numbers print without a fraction, which the lexer requires.
+    let mut text = value.to_string();
+    if !text.contains('.') {
+        text.push_str(".0");
+    }
+    text
+}
+
+/// Wrap `text` in `delimiter`, escaping it the way the lexer decodes it.
+fn quote(text: &str, delimiter: char) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push(delimiter);
+    for c in text.chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            '\0' => out.push_str("\\0"),
+            '\\' => out.push_str("\\\\"),
+            c if c == delimiter => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push(delimiter);
+    out
+}
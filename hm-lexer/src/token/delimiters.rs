@@ -3,7 +3,8 @@
 /// Represents all delimiter and punctuation tokens.
 ///
 /// Used to group expressions, separate statements, and mark boundaries in code.
-#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Delimiters {
     /// Left parenthesis `(`
     LeftParen,
@@ -28,4 +29,38 @@ pub enum Delimiters {
 
     /// Question mark `?`
     QuestionMark,
-}
\ No newline at end of file
+}
+
+impl Delimiters {
+    /// Every delimiter, in declaration order.
+    pub const ALL: [Delimiters; 11] = [
+        Delimiters::LeftParen,
+        Delimiters::RightParen,
+        Delimiters::LeftBrace,
+        Delimiters::RightBrace,
+        Delimiters::LeftBracket,
+        Delimiters::RightBracket,
+        Delimiters::Colon,
+        Delimiters::Semicolon,
+        Delimiters::Comma,
+        Delimiters::Dot,
+        Delimiters::QuestionMark,
+    ];
+
+    /// Return the source spelling of this delimiter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Delimiters::LeftParen => "(",
+            Delimiters::RightParen => ")",
+            Delimiters::LeftBrace => "{",
+            Delimiters::RightBrace => "}",
+            Delimiters::LeftBracket => "[",
+            Delimiters::RightBracket => "]",
+            Delimiters::Colon => ":",
+            Delimiters::Semicolon => ";",
+            Delimiters::Comma => ",",
+            Delimiters::Dot => ".",
+            Delimiters::QuestionMark => "?",
+        }
+    }
+}
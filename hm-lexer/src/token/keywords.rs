@@ -2,7 +2,8 @@
 ///
 /// This enum is used by the lexer and parser to classify tokens
 /// that have special syntactic meaning.
-#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Keywords {
     /// Keywords that affect control flow (branching, looping, returning)
     /// Declares a function
@@ -35,11 +36,15 @@ pub enum Keywords {
 
     /// Module Import
     Import,
+
+    /// The absent value of an optional
+    Null,
 }
 
 /// Represents built-in data types in the language.
 /// This enum is used to classify type keywords.
-#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TypeKind {
     /// 8-bit signed integer
     Int8,
@@ -74,4 +79,105 @@ pub enum TypeKind {
 
     /// Boolean type
     Bool,
-}
\ No newline at end of file
+}
+
+impl Keywords {
+    /// Every keyword, including type keywords, in declaration order.
+    pub const ALL: [Keywords; 27] = [
+        Keywords::Func,
+        Keywords::Return,
+        Keywords::If,
+        Keywords::Else,
+        Keywords::Elif,
+        Keywords::Loop,
+        Keywords::Switch,
+        Keywords::Case,
+        Keywords::Var,
+        Keywords::Const,
+        Keywords::Final,
+        Keywords::Import,
+        Keywords::Null,
+        Keywords::Type(TypeKind::Int8),
+        Keywords::Type(TypeKind::Int16),
+        Keywords::Type(TypeKind::Int32),
+        Keywords::Type(TypeKind::Int64),
+        Keywords::Type(TypeKind::Unsigned8),
+        Keywords::Type(TypeKind::Unsigned16),
+        Keywords::Type(TypeKind::Unsigned32),
+        Keywords::Type(TypeKind::Unsigned64),
+        Keywords::Type(TypeKind::Float32),
+        Keywords::Type(TypeKind::Float64),
+        Keywords::Type(TypeKind::String),
+        Keywords::Type(TypeKind::Char),
+        Keywords::Type(TypeKind::Struct),
+        Keywords::Type(TypeKind::Bool),
+    ];
+
+    /// Return the source spelling of this keyword.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::token::keywords::{Keywords, TypeKind};
+    /// assert_eq!(Keywords::Func.as_str(), "func");
+    /// assert_eq!(Keywords::Type(TypeKind::Int32).as_str(), "i32");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Keywords::Func => "func",
+            Keywords::Return => "return",
+            Keywords::If => "if",
+            Keywords::Else => "else",
+            Keywords::Elif => "elif",
+            Keywords::Loop => "loop",
+            Keywords::Switch => "switch",
+            Keywords::Case => "case",
+            Keywords::Var => "var",
+            Keywords::Const => "const",
+            Keywords::Final => "final",
+            Keywords::Type(ty) => ty.as_str(),
+            Keywords::Import => "import",
+            Keywords::Null => "null",
+        }
+    }
+}
+
+impl TypeKind {
+    /// Every type keyword, in declaration order.
+    pub const ALL: [TypeKind; 14] = [
+        TypeKind::Int8,
+        TypeKind::Int16,
+        TypeKind::Int32,
+        TypeKind::Int64,
+        TypeKind::Unsigned8,
+        TypeKind::Unsigned16,
+        TypeKind::Unsigned32,
+        TypeKind::Unsigned64,
+        TypeKind::Float32,
+        TypeKind::Float64,
+        TypeKind::String,
+        TypeKind::Char,
+        TypeKind::Struct,
+        TypeKind::Bool,
+    ];
+
+    /// Return the source spelling of this type keyword.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TypeKind::Int8 => "i8",
+            TypeKind::Int16 => "i16",
+            TypeKind::Int32 => "i32",
+            TypeKind::Int64 => "i64",
+            TypeKind::Unsigned8 => "u8",
+            TypeKind::Unsigned16 => "u16",
+            TypeKind::Unsigned32 => "u32",
+            TypeKind::Unsigned64 => "u64",
+            TypeKind::Float32 => "f32",
+            TypeKind::Float64 => "f64",
+            TypeKind::String => "string",
+            TypeKind::Char => "char",
+            TypeKind::Struct => "struct",
+            TypeKind::Bool => "bool",
+        }
+    }
+}
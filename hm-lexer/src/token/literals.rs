@@ -1,7 +1,17 @@
 //! Literal token types for the Hummingbird language.
 //!
 //! `Literals` enumerates all possible literal values that can appear in source code,
-//! including strings, characters, integers, and floating-point numbers.
+//! including strings, characters, integers, floating-point numbers, and
+//! booleans.
+//! Numbers written with a type suffix, such as `42i8` or `3.14f32`, carry
+//! their [`NumericSuffix`] alongside the value, and strings with `${...}`
+//! interpolations are split into [`StringPart`]s.
+
+use std::hash::{Hash, Hasher};
+use std::mem;
+
+use crate::token::keywords::TypeKind;
+use crate::token::Token;
 
 /// Represents all literal value types recognized by the lexer.
 ///
@@ -11,10 +21,15 @@
 /// # Variants
 ///
 /// - `StringLiteral(String)`: A double-quoted string literal
+/// - `MultilineStringLiteral { raw, value }`: A triple-quoted string literal
 /// - `CharacterLiteral(char)`: A single-quoted character literal
+/// - `ByteLiteral(u8)`: A `b`-prefixed ASCII character literal
 /// - `IntLiteral(i64)`: A signed integer literal
 /// - `UnsignedIntLiteral(u64)`: An unsigned integer literal
 /// - `FloatLiteral(f64)`: A floating-point literal
+/// - `NumericLiteral { value, suffix }`: A number with a type suffix
+/// - `InterpolatedString(Vec<StringPart>)`: A string with `${...}` expressions
+/// - `BoolLiteral(bool)`: `true` or `false`
 ///
 /// # Example
 ///
@@ -24,16 +39,344 @@
 /// let int_lit = Literals::IntLiteral(42);
 /// let float_lit = Literals::FloatLiteral(3.14);
 /// ```
-#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Literals {
     /// String literal value (e.g., `"hello"`)
     StringLiteral(String),
+    /// Triple-quoted string literal (e.g., `"""line one\nline two"""`)
+    MultilineStringLiteral {
+        /// The source text between the delimiters, exactly as written
+        raw: String,
+        /// The processed value: common indentation stripped (when
+        /// [`LexerConfig::trim_indent`](crate::config::LexerConfig::trim_indent)
+        /// is enabled), then escape sequences decoded (unless
+        /// [`LexerConfig::keep_escapes`](crate::config::LexerConfig::keep_escapes)
+        /// is enabled)
+        value: String,
+    },
     /// Character literal value (e.g., `'a'`)
     CharacterLiteral(char),
+    /// Byte literal value (e.g., `b'A'`); always ASCII
+    ByteLiteral(u8),
     /// Signed integer literal value
     IntLiteral(i64),
     /// Unsigned integer literal value
     UnsignedIntLiteral(u64),
     /// Floating point literal value (e.g., `3.14`, `0.5`, `-2.0`)
     FloatLiteral(f64),
-}
\ No newline at end of file
+    /// Number literal with a type suffix (e.g., `42i8`, `100u32`, `3.14f32`)
+    NumericLiteral {
+        /// The value, already checked to fit the suffix's type, or the
+        /// [`NumericValue::Magnitude`] of its minimum after a `-`
+        value: NumericValue,
+        /// The type named by the suffix
+        suffix: NumericSuffix,
+    },
+    /// Double-quoted string containing `${...}` interpolations (e.g.,
+    /// `"hello ${name}"`), as its text and expression parts in source order
+    InterpolatedString(Vec<StringPart>),
+    /// Boolean literal value (`true` or `false`)
+    BoolLiteral(bool),
+}
+
+/// Floats compare by [`float_key`], so every literal equals itself and
+/// literals can be hashed.
+impl PartialEq for Literals {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Literals::StringLiteral(a), Literals::StringLiteral(b)) => a == b,
+            (
+                Literals::MultilineStringLiteral { raw, value },
+                Literals::MultilineStringLiteral {
+                    raw: other_raw,
+                    value: other_value,
+                },
+            ) => raw == other_raw && value == other_value,
+            (Literals::CharacterLiteral(a), Literals::CharacterLiteral(b)) => a == b,
+            (Literals::ByteLiteral(a), Literals::ByteLiteral(b)) => a == b,
+            (Literals::IntLiteral(a), Literals::IntLiteral(b)) => a == b,
+            (Literals::UnsignedIntLiteral(a), Literals::UnsignedIntLiteral(b)) => a == b,
+            (Literals::FloatLiteral(a), Literals::FloatLiteral(b)) => {
+                float_key(*a) == float_key(*b)
+            }
+            (
+                Literals::NumericLiteral { value, suffix },
+                Literals::NumericLiteral {
+                    value: other_value,
+                    suffix: other_suffix,
+                },
+            ) => value == other_value && suffix == other_suffix,
+            (Literals::InterpolatedString(a), Literals::InterpolatedString(b)) => a == b,
+            (Literals::BoolLiteral(a), Literals::BoolLiteral(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Literals {}
+
+impl Hash for Literals {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            Literals::StringLiteral(text) => text.hash(state),
+            Literals::MultilineStringLiteral { raw, value } => {
+                raw.hash(state);
+                value.hash(state);
+            }
+            Literals::CharacterLiteral(c) => c.hash(state),
+            Literals::ByteLiteral(b) => b.hash(state),
+            Literals::IntLiteral(v) => v.hash(state),
+            Literals::UnsignedIntLiteral(v) => v.hash(state),
+            Literals::FloatLiteral(v) => float_key(*v).hash(state),
+            Literals::NumericLiteral { value, suffix } => {
+                value.hash(state);
+                suffix.hash(state);
+            }
+            Literals::InterpolatedString(parts) => parts.hash(state),
+            Literals::BoolLiteral(b) => b.hash(state),
+        }
+    }
+}
+
+/// One piece of a [`Literals::InterpolatedString`].
+///
+/// Consecutive characters between interpolations form a single `Text` part,
+/// and empty text is left out, so `"${a}${b}"` has just two `Expr` parts.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::token::literals::{Literals, StringPart};
+/// # use hm_lexer::token::tokenkind::TokenKind;
+/// let (tokens, _) = hm_lexer::tokenize(r#""hello ${name}!""#).unwrap();
+/// let TokenKind::Literal(Literals::InterpolatedString(parts)) = &tokens[0].kind else {
+///     panic!("expected an interpolated string");
+/// };
+/// assert!(matches!(&parts[0], StringPart::Text(text) if text == "hello "));
+/// let StringPart::Expr(expr) = &parts[1] else {
+///     panic!("expected an expression");
+/// };
+/// assert_eq!(expr[0].lexeme, "name");
+/// assert_eq!((expr[0].span.start, expr[0].span.end), (9, 13));
+/// assert!(matches!(&parts[2], StringPart::Text(text) if text == "!"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StringPart {
+    /// Literal text, escapes decoded
+    Text(String),
+    /// The tokens between `${` and the matching `}`, with spans into the
+    /// enclosing source; there is no `Eof` token
+    Expr(Vec<Token>),
+}
+
+/// The value of a [`Literals::NumericLiteral`], held in the widest type of
+/// its suffix's family.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NumericValue {
+    /// Value of an `i8` to `i64` literal
+    Int(i64),
+    /// Value of a `u8` to `u64` literal
+    Unsigned(u64),
+    /// Value of an `f32` or `f64` literal
+    Float(f64),
+    /// Magnitude of a signed type's `MIN`, one more than its `MAX`, as the
+    /// `128` of `-128i8`. Only produced for a literal written directly
+    /// after a `-`; the parser negates it and checks that the result fits.
+    Magnitude(u64),
+}
+
+/// Floats compare by [`float_key`], as in [`Literals`].
+impl PartialEq for NumericValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (*self, *other) {
+            (NumericValue::Int(a), NumericValue::Int(b)) => a == b,
+            (NumericValue::Unsigned(a), NumericValue::Unsigned(b)) => a == b,
+            (NumericValue::Float(a), NumericValue::Float(b)) => float_key(a) == float_key(b),
+            (NumericValue::Magnitude(a), NumericValue::Magnitude(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for NumericValue {}
+
+impl Hash for NumericValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match *self {
+            NumericValue::Int(v) => v.hash(state),
+            NumericValue::Unsigned(v) => v.hash(state),
+            NumericValue::Float(v) => float_key(v).hash(state),
+            NumericValue::Magnitude(v) => v.hash(state),
+        }
+    }
+}
+
+/// The identity of a float for comparing and hashing literals: its bits,
+/// except that `-0.0` is the same as `0.0`. Unlike `==`, this makes a NaN
+/// equal to itself, so literal equality is reflexive.
+fn float_key(value: f64) -> u64 {
+    if value == 0.0 {
+        0
+    } else {
+        value.to_bits()
+    }
+}
+
+/// A type suffix on a number literal.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::token::literals::{Literals, NumericSuffix, NumericValue};
+/// # use hm_lexer::token::tokenkind::TokenKind;
+/// let (tokens, _) = hm_lexer::tokenize("100u32").unwrap();
+/// let TokenKind::Literal(Literals::NumericLiteral { value, suffix }) = &tokens[0].kind else {
+///     panic!("expected a suffixed literal");
+/// };
+/// assert_eq!(*value, NumericValue::Unsigned(100));
+/// assert_eq!(*suffix, NumericSuffix::Unsigned32);
+/// assert_eq!(suffix.as_str(), "u32");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NumericSuffix {
+    /// `i8`
+    Int8,
+    /// `i16`
+    Int16,
+    /// `i32`
+    Int32,
+    /// `i64`
+    Int64,
+    /// `u8`
+    Unsigned8,
+    /// `u16`
+    Unsigned16,
+    /// `u32`
+    Unsigned32,
+    /// `u64`
+    Unsigned64,
+    /// `f32`
+    Float32,
+    /// `f64`
+    Float64,
+}
+
+impl NumericSuffix {
+    /// Every suffix, in declaration order.
+    pub const ALL: [NumericSuffix; 10] = [
+        NumericSuffix::Int8,
+        NumericSuffix::Int16,
+        NumericSuffix::Int32,
+        NumericSuffix::Int64,
+        NumericSuffix::Unsigned8,
+        NumericSuffix::Unsigned16,
+        NumericSuffix::Unsigned32,
+        NumericSuffix::Unsigned64,
+        NumericSuffix::Float32,
+        NumericSuffix::Float64,
+    ];
+
+    /// The suffix with the given spelling, such as `"i8"`.
+    pub fn from_spelling(text: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|suffix| suffix.as_str() == text)
+    }
+
+    /// Return the source spelling of this suffix.
+    pub fn as_str(self) -> &'static str {
+        self.type_kind().as_str()
+    }
+
+    /// The type keyword this suffix names.
+    pub fn type_kind(self) -> TypeKind {
+        match self {
+            NumericSuffix::Int8 => TypeKind::Int8,
+            NumericSuffix::Int16 => TypeKind::Int16,
+            NumericSuffix::Int32 => TypeKind::Int32,
+            NumericSuffix::Int64 => TypeKind::Int64,
+            NumericSuffix::Unsigned8 => TypeKind::Unsigned8,
+            NumericSuffix::Unsigned16 => TypeKind::Unsigned16,
+            NumericSuffix::Unsigned32 => TypeKind::Unsigned32,
+            NumericSuffix::Unsigned64 => TypeKind::Unsigned64,
+            NumericSuffix::Float32 => TypeKind::Float32,
+            NumericSuffix::Float64 => TypeKind::Float64,
+        }
+    }
+
+    /// Whether this suffix names an unsigned integer type.
+    pub fn is_unsigned(self) -> bool {
+        matches!(
+            self,
+            NumericSuffix::Unsigned8
+                | NumericSuffix::Unsigned16
+                | NumericSuffix::Unsigned32
+                | NumericSuffix::Unsigned64
+        )
+    }
+
+    /// Whether this suffix names a floating point type.
+    pub fn is_float(self) -> bool {
+        matches!(self, NumericSuffix::Float32 | NumericSuffix::Float64)
+    }
+
+    /// Parse `digits`, with separators already removed, in `base` and check
+    /// that the value fits this suffix's type.
+    ///
+    /// Returns `None` if the digits do not parse or the value is out of
+    /// range, as in `300u8` or `128i8`. Float suffixes only accept base 10.
+    ///
+    /// Literals carry no sign, so `-128i8` lexes as `-` and `128i8`. When
+    /// `negated`, the literal directly follows a `-` and a signed type also
+    /// accepts `MAX + 1`, the magnitude of its minimum, as a
+    /// [`NumericValue::Magnitude`].
+    pub(crate) fn parse_value(
+        self,
+        digits: &str,
+        base: u32,
+        negated: bool,
+    ) -> Option<NumericValue> {
+        let signed = |max: i64| {
+            let v = u64::from_str_radix(digits, base).ok()?;
+            let max = max.unsigned_abs();
+            if v <= max {
+                i64::try_from(v).ok().map(NumericValue::Int)
+            } else if negated && v == max + 1 {
+                Some(NumericValue::Magnitude(v))
+            } else {
+                None
+            }
+        };
+        let unsigned = |max: u64| {
+            u64::from_str_radix(digits, base)
+                .ok()
+                .filter(|&v| v <= max)
+                .map(NumericValue::Unsigned)
+        };
+        match self {
+            NumericSuffix::Int8 => signed(i8::MAX.into()),
+            NumericSuffix::Int16 => signed(i16::MAX.into()),
+            NumericSuffix::Int32 => signed(i32::MAX.into()),
+            NumericSuffix::Int64 => signed(i64::MAX),
+            NumericSuffix::Unsigned8 => unsigned(u8::MAX.into()),
+            NumericSuffix::Unsigned16 => unsigned(u16::MAX.into()),
+            NumericSuffix::Unsigned32 => unsigned(u32::MAX.into()),
+            NumericSuffix::Unsigned64 => unsigned(u64::MAX),
+            NumericSuffix::Float32 | NumericSuffix::Float64 if base != 10 => None,
+            NumericSuffix::Float32 => digits
+                .parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .map(|v| NumericValue::Float(v.into())),
+            NumericSuffix::Float64 => digits
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .map(NumericValue::Float),
+        }
+    }
+}
@@ -14,13 +14,41 @@ pub mod assignment;
 pub mod bitwise;
 
 /// Special operators not covered by other categories.
-////
-/// This enum includes operators like pointer access and scope resolution.
-#[cfg_attr(debug_assertions, derive(Debug))]
+///
+/// This enum includes operators like pointer access, scope resolution,
+/// null coalescing, and the variadic ellipsis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SpecialOps {
     /// Pointer access operator `->`
     PointerAccess,
 
     /// Scope resolution operator `::`
     ScopingOperator,
-}
\ No newline at end of file
+
+    /// Variadic parameter marker `...`
+    Ellipsis,
+
+    /// Null-coalescing operator `??`
+    NullCoalescing,
+}
+
+impl SpecialOps {
+    /// Every special operator, in declaration order.
+    pub const ALL: [SpecialOps; 4] = [
+        SpecialOps::PointerAccess,
+        SpecialOps::ScopingOperator,
+        SpecialOps::Ellipsis,
+        SpecialOps::NullCoalescing,
+    ];
+
+    /// Return the source spelling of this operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpecialOps::PointerAccess => "->",
+            SpecialOps::ScopingOperator => "::",
+            SpecialOps::Ellipsis => "...",
+            SpecialOps::NullCoalescing => "??",
+        }
+    }
+}
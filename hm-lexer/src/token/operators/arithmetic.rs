@@ -12,8 +12,9 @@
 /// - `Asterisk`: Multiplication operator (`*`)
 /// - `Slash`: Division operator (`/`)
 /// - `Modulo`: Modulus/remainder operator (`%`)
-/// - `Exponent`: Exponentiation operator (`**`)
-#[cfg_attr(debug_assertions, derive(Debug, Clone, PartialEq, Eq))]
+/// - `Exponent`: Exponentiation operator (`**`, feature `experimental-ops`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ArithmeticOps {
     /// Addition operator (`+`)
     Plus,
@@ -26,5 +27,32 @@ pub enum ArithmeticOps {
     /// Modulus/remainder operator (`%`)
     Modulo,
     /// Exponentiation operator (`**`)
+    #[cfg(feature = "experimental-ops")]
     Exponent,
-}
\ No newline at end of file
+}
+
+impl ArithmeticOps {
+    /// Every arithmetic operator, in declaration order.
+    pub const ALL: [ArithmeticOps; 5 + cfg!(feature = "experimental-ops") as usize] = [
+        ArithmeticOps::Plus,
+        ArithmeticOps::Minus,
+        ArithmeticOps::Asterisk,
+        ArithmeticOps::Slash,
+        ArithmeticOps::Modulo,
+        #[cfg(feature = "experimental-ops")]
+        ArithmeticOps::Exponent,
+    ];
+
+    /// Return the source spelling of this operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArithmeticOps::Plus => "+",
+            ArithmeticOps::Minus => "-",
+            ArithmeticOps::Asterisk => "*",
+            ArithmeticOps::Slash => "/",
+            ArithmeticOps::Modulo => "%",
+            #[cfg(feature = "experimental-ops")]
+            ArithmeticOps::Exponent => "**",
+        }
+    }
+}
@@ -13,7 +13,10 @@
 /// - `MultiplyAssign`: Multiplication assignment (`*=`)
 /// - `DivideAssign`: Division assignment (`/=`)
 /// - `ModuloAssign`: Modulo assignment (`%=`)
-#[cfg_attr(debug_assertions, derive(Debug))]
+/// - `ExponentAssign`: Exponentiation assignment (`**=`, feature `experimental-ops`)
+/// - `NullCoalescingAssign`: Null-coalescing assignment (`??=`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AssignmentOps {
     /// Simple assignment operator (`=`)
     Assign,
@@ -27,4 +30,39 @@ pub enum AssignmentOps {
     DivideAssign,
     /// Modulo assignment operator (`%=`)
     ModuloAssign,
-}
\ No newline at end of file
+    /// Exponentiation assignment operator (`**=`)
+    #[cfg(feature = "experimental-ops")]
+    ExponentAssign,
+    /// Null-coalescing assignment operator (`??=`)
+    NullCoalescingAssign,
+}
+
+impl AssignmentOps {
+    /// Every assignment operator, in declaration order.
+    pub const ALL: [AssignmentOps; 7 + cfg!(feature = "experimental-ops") as usize] = [
+        AssignmentOps::Assign,
+        AssignmentOps::AddAssign,
+        AssignmentOps::SubtractAssign,
+        AssignmentOps::MultiplyAssign,
+        AssignmentOps::DivideAssign,
+        AssignmentOps::ModuloAssign,
+        #[cfg(feature = "experimental-ops")]
+        AssignmentOps::ExponentAssign,
+        AssignmentOps::NullCoalescingAssign,
+    ];
+
+    /// Return the source spelling of this operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AssignmentOps::Assign => "=",
+            AssignmentOps::AddAssign => "+=",
+            AssignmentOps::SubtractAssign => "-=",
+            AssignmentOps::MultiplyAssign => "*=",
+            AssignmentOps::DivideAssign => "/=",
+            AssignmentOps::ModuloAssign => "%=",
+            #[cfg(feature = "experimental-ops")]
+            AssignmentOps::ExponentAssign => "**=",
+            AssignmentOps::NullCoalescingAssign => "??=",
+        }
+    }
+}
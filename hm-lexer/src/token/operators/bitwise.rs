@@ -13,7 +13,8 @@
 /// - `Not`: Bitwise NOT (`~`) - inverts all bits
 /// - `LeftShift`: Left shift (`<<`) - shifts bits left, filling with zeros
 /// - `RightShift`: Right shift (`>>`) - shifts bits right
-#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BitwiseOps {
     /// Bitwise AND operator (`&`)
     And,
@@ -27,4 +28,28 @@ pub enum BitwiseOps {
     LeftShift,
     /// Right shift operator (`>>`)
     RightShift,
-}
\ No newline at end of file
+}
+
+impl BitwiseOps {
+    /// Every bitwise operator, in declaration order.
+    pub const ALL: [BitwiseOps; 6] = [
+        BitwiseOps::And,
+        BitwiseOps::Or,
+        BitwiseOps::Xor,
+        BitwiseOps::Not,
+        BitwiseOps::LeftShift,
+        BitwiseOps::RightShift,
+    ];
+
+    /// Return the source spelling of this operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BitwiseOps::And => "&",
+            BitwiseOps::Or => "|",
+            BitwiseOps::Xor => "^",
+            BitwiseOps::Not => "~",
+            BitwiseOps::LeftShift => "<<",
+            BitwiseOps::RightShift => ">>",
+        }
+    }
+}
@@ -10,7 +10,8 @@
 /// - `And`: Logical AND operator (`&&`) - true if both operands are true
 /// - `Or`: Logical OR operator (`||`) - true if at least one operand is true
 /// - `Not`: Logical NOT operator (`!`) - inverts a boolean value
-#[cfg_attr(debug_assertions, derive(Debug, Clone, PartialEq, Eq))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LogicalOps {
     /// Logical AND operator (`&&`)
     And,
@@ -18,4 +19,22 @@ pub enum LogicalOps {
     Or,
     /// Logical NOT operator (`!`)
     Not,
-}
\ No newline at end of file
+}
+
+impl LogicalOps {
+    /// Every logical operator, in declaration order.
+    pub const ALL: [LogicalOps; 3] = [
+        LogicalOps::And,
+        LogicalOps::Or,
+        LogicalOps::Not,
+    ];
+
+    /// Return the source spelling of this operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogicalOps::And => "&&",
+            LogicalOps::Or => "||",
+            LogicalOps::Not => "!",
+        }
+    }
+}
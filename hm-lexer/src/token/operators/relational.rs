@@ -13,7 +13,8 @@
 /// - `GreaterThanOrEqual`: Greater than or equal comparison (`>=`)
 /// - `Equal`: Equality comparison (`==`)
 /// - `NotEqual`: Inequality comparison (`!=`)
-#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RelationalOps {
     /// Less than operator (`<`)
     LessThan,
@@ -27,4 +28,28 @@ pub enum RelationalOps {
     Equal,
     /// Inequality operator (`!=`)
     NotEqual,
-}
\ No newline at end of file
+}
+
+impl RelationalOps {
+    /// Every relational operator, in declaration order.
+    pub const ALL: [RelationalOps; 6] = [
+        RelationalOps::LessThan,
+        RelationalOps::GreaterThan,
+        RelationalOps::LessThanOrEqual,
+        RelationalOps::GreaterThanOrEqual,
+        RelationalOps::Equal,
+        RelationalOps::NotEqual,
+    ];
+
+    /// Return the source spelling of this operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelationalOps::LessThan => "<",
+            RelationalOps::GreaterThan => ">",
+            RelationalOps::LessThanOrEqual => "<=",
+            RelationalOps::GreaterThanOrEqual => ">=",
+            RelationalOps::Equal => "==",
+            RelationalOps::NotEqual => "!=",
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Source location tracking types.
 
+use std::ops::Range;
+
 /// A contiguous region (span) of source text represented as byte offsets
 /// and human-readable line/column positions.
 ///
@@ -11,6 +13,8 @@
 ///
 /// - `start <= end` and `end` is exclusive (one past the last byte in the span)
 /// - Line and column numbers are 1-based (first line is 1, first column is 1)
+/// - When present, `char_offsets` follow the same half-open convention as
+///   `start` and `end`
 ///
 /// # Example
 ///
@@ -23,11 +27,32 @@
 ///     column_start: 1,
 ///     line_end: 1,
 ///     column_end: 6,
+///     char_offsets: None,
 /// };
 /// // Represents "hello" at line 1, columns 1-5
 /// ```
-#[cfg_attr(debug_assertions, derive(Debug))]
+///
+/// # Ordering
+///
+/// Spans order by `start`, then `end`, so sorting diagnostics or tokens by
+/// span puts them in source order. Spans with equal offsets are compared by
+/// their remaining fields in declaration order, which keeps `Ord` consistent
+/// with `Eq`. `Span` is also `Hash`, so it can key a `HashMap` or be stored
+/// in a `BTreeSet` directly.
+///
+/// ```
+/// # use std::collections::BTreeSet;
+/// # use hm_lexer::tokenize;
+/// let (tokens, _) = tokenize("a + b").unwrap();
+/// let spans: BTreeSet<_> = tokens.iter().rev().map(|t| t.span).collect();
+/// let starts: Vec<_> = spans.iter().map(|s| s.start).collect();
+/// assert_eq!(starts, [0, 2, 4, 5]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Span {
+    // Field order matters: the derived `Ord` compares fields top to bottom.
+
     /// Byte offset of the first byte in the span (inclusive).
     pub start: usize,
 
@@ -45,4 +70,150 @@ pub struct Span {
 
     /// 1-based column number within `line_end` where the span ends.
     pub column_end: usize,
+
+    /// Start and end offsets counted in Unicode scalar values (`char`s)
+    /// instead of bytes. Only filled in when
+    /// [`LexerConfig::char_offsets`](crate::config::LexerConfig::char_offsets)
+    /// is enabled; `None` otherwise.
+    pub char_offsets: Option<(usize, usize)>,
+}
+
+impl Span {
+    /// Move the span down by `lines` lines and forward by `bytes` bytes, for
+    /// a span lexed from a slice of a larger text that starts on a line of
+    /// its own.
+    pub(crate) fn shift(&mut self, bytes: usize, lines: usize) {
+        self.start += bytes;
+        self.end += bytes;
+        self.line_start += lines;
+        self.line_end += lines;
+    }
+
+    /// Smallest span covering both `self` and `other`.
+    ///
+    /// The start position (offset, line, column) comes from whichever span
+    /// starts first and the end position from whichever ends last, so the
+    /// result is correct even when the two spans are on different lines or
+    /// given out of order. `char_offsets` are merged the same way when both
+    /// spans carry them and dropped otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The span to combine with this one
+    ///
+    /// # Returns
+    ///
+    /// A span from the earlier start to the later end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::tokenize;
+    /// let (tokens, _) = tokenize("let x =\n    42").unwrap();
+    /// let node = tokens[0].span.merge(tokens[3].span);
+    /// assert_eq!((node.start, node.end), (0, 14));
+    /// assert_eq!((node.line_start, node.column_start), (1, 1));
+    /// assert_eq!((node.line_end, node.column_end), (2, 7));
+    /// assert_eq!(tokens[3].span.merge(tokens[0].span), node);
+    /// ```
+    #[must_use]
+    pub fn merge(self, other: Span) -> Span {
+        let first = if other.start < self.start {
+            other
+        } else {
+            self
+        };
+        let last = if other.end > self.end { other } else { self };
+        let char_offsets = match (self.char_offsets, other.char_offsets) {
+            (Some(a), Some(b)) => Some((a.0.min(b.0), a.1.max(b.1))),
+            _ => None,
+        };
+        Span {
+            start: first.start,
+            end: last.end,
+            line_start: first.line_start,
+            column_start: first.column_start,
+            line_end: last.line_end,
+            column_end: last.column_end,
+            char_offsets,
+        }
+    }
+
+    /// Check whether a byte offset lies inside the span.
+    ///
+    /// Spans are half-open, so `end` itself is not contained and an empty
+    /// span contains no offsets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::tokenize;
+    /// let span = tokenize("abc").unwrap().0[0].span;
+    /// assert!(span.contains(0) && span.contains(2));
+    /// assert!(!span.contains(3));
+    /// ```
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
+
+    /// Length of the span in bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::tokenize;
+    /// let (tokens, _) = tokenize("\"é\"").unwrap();
+    /// assert_eq!(tokens[0].span.len(), 4);
+    /// assert!(tokens[1].span.is_empty()); // Eof
+    /// ```
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Check whether the span covers no bytes, as for the `Eof` token.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Borrow the bytes of `source` covered by the span.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source the span was produced from
+    ///
+    /// # Returns
+    ///
+    /// The bytes from `start` to `end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the span is out of bounds for `source`, e.g. when it was
+    /// produced from a different source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::tokenize;
+    /// let source = "let total = 42";
+    /// let (tokens, _) = tokenize(source).unwrap();
+    /// assert_eq!(tokens[1].span.slice(source.as_bytes()), b"total");
+    /// ```
+    pub fn slice<'a>(&self, source: &'a [u8]) -> &'a [u8] {
+        &source[Range::from(*self)]
+    }
+}
+
+impl From<Span> for Range<usize> {
+    /// Convert a span into its byte range, e.g. to index a `str` with it.
+    ///
+    /// ```
+    /// # use std::ops::Range;
+    /// # use hm_lexer::tokenize;
+    /// let source = "a + b";
+    /// let span = tokenize(source).unwrap().0[2].span;
+    /// assert_eq!(&source[Range::from(span)], "b");
+    /// ```
+    fn from(span: Span) -> Range<usize> {
+        span.start..span.end
+    }
 }
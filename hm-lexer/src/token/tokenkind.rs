@@ -3,9 +3,11 @@
 //! `TokenKind` enumerates all possible token types the lexer can produce,
 //! including keywords, identifiers, literals, delimiters, and operators.
 
+use crate::intern::Symbol;
+use crate::keywordtable::KeywordTable;
+use crate::lexerror::ErrorCode;
 use crate::token::delimiters::Delimiters;
 use crate::token::keywords::Keywords;
-use crate::token::keywords::TypeKind;
 use crate::token::literals::Literals;
 use crate::token::operators::arithmetic::ArithmeticOps;
 use crate::token::operators::assignment::AssignmentOps;
@@ -28,7 +30,7 @@ use crate::token::operators::SpecialOps;
 /// - Types: `Int8`, `Int16`, `Int32`, `Int64`, `Unsigned8`, `Unsigned16`, `Unsigned32`, `Unsigned64`, `Float`, `Double`, `String`, `Character`, `Struct`
 ///
 /// ## Identifiers and Literals
-/// - `Identifier(String)`: User-defined names
+/// - `Identifier(Symbol)`: User-defined names, interned by the lexer
 /// - `StringLiteral(String)`: Double-quoted strings
 /// - `CharacterLiteral(char)`: Single-quoted characters
 /// - `IntLiteral(i64)`: Signed integer constants
@@ -44,16 +46,19 @@ use crate::token::operators::SpecialOps;
 /// - `Colon`, `Semicolon`, `Comma`, `Dot`
 ///
 /// ## Special
+/// - `Error { message_code }`: Malformed input skipped in recovery mode
 /// - `Eof`: End of file marker
-#[cfg_attr(debug_assertions, derive(Debug))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TokenKind {
     // Keywords
     /// Reserved keyword in the HM language
     Keyword(Keywords),
 
     // Identifiers and Literals
-    /// User-defined identifier (variable, function name, etc.)
-    Identifier(String),
+    /// User-defined identifier (variable, function name, etc.), as the
+    /// symbol of its name in the lexer's [`Interner`](crate::intern::Interner)
+    Identifier(Symbol),
 
     /// All literal types
     Literal(Literals),
@@ -81,10 +86,17 @@ pub enum TokenKind {
     /// Bitwise operator (`&`, `|`, `^`, `~`, `<<`, `>>`)
     BitwiseOperator(BitwiseOps),
 
-    /// Special operators (`::`, `->`)
+    /// Special operators (`::`, `->`, `...`, `??`)
     SpecialOperator(SpecialOps),
 
     // Special
+    /// Malformed input skipped in error-recovery mode. The token's span and
+    /// lexeme cover the skipped region.
+    Error {
+        /// Code identifying the error that was recovered from
+        message_code: ErrorCode,
+    },
+
     /// End of file marker
     Eof,
 }
@@ -92,9 +104,11 @@ pub enum TokenKind {
 impl TokenKind {
     /// Attempt to parse a string as a reserved keyword.
     ///
-    /// This method checks if the provided string exactly matches a reserved
+    /// This method checks if the provided string exactly matches a built-in
     /// keyword and returns the corresponding token kind if found. It is
-    /// case-sensitive and matches the exact keyword names.
+    /// case-sensitive and matches the exact keyword names. A lexer may be
+    /// configured with extra keywords; see
+    /// [`KeywordTable`](crate::keywordtable::KeywordTable).
     ///
     /// # Arguments
     ///
@@ -111,52 +125,148 @@ impl TokenKind {
     /// # use hm_lexer::token::tokenkind::TokenKind;
     /// // Returns Some(TokenKind) for keywords
     /// assert!(TokenKind::keyword("if").is_some());
-    /// assert!(TokenKind::keyword("int32").is_some());
+    /// assert!(TokenKind::keyword("i32").is_some());
     /// // Returns None for non-keywords
     /// assert!(TokenKind::keyword("myVar").is_none());
     /// ```
     pub fn keyword(s: &str) -> Option<Self> {
-        let kw = match s {
-            // Control Flow
-            "func" => Some(Keywords::Func),
-            "return" => Some(Keywords::Return),
-            "if" => Some(Keywords::If),
-            "else" => Some(Keywords::Else),
-            "elif" => Some(Keywords::Elif),
-            "loop" => Some(Keywords::Loop),
-            "switch" => Some(Keywords::Switch),
-            "case" => Some(Keywords::Case),
-
-            // Variable/Binding
-            "var" => Some(Keywords::Var),
-            "const" => Some(Keywords::Const),
-            "final" => Some(Keywords::Final),
-
-            // Integer Types
-            "i8" => Some(Keywords::Type(TypeKind::Int8)),
-            "i16" => Some(Keywords::Type(TypeKind::Int16)),
-            "i32" => Some(Keywords::Type(TypeKind::Int32)),
-            "i64" => Some(Keywords::Type(TypeKind::Int64)),
-            "u8" => Some(Keywords::Type(TypeKind::Unsigned8)),
-            "u16" => Some(Keywords::Type(TypeKind::Unsigned16)),
-            "u32" => Some(Keywords::Type(TypeKind::Unsigned32)),
-            "u64" => Some(Keywords::Type(TypeKind::Unsigned64)),
-
-            // Floating Point Types
-            "f32" => Some(Keywords::Type(TypeKind::Float32)),
-            "f64" => Some(Keywords::Type(TypeKind::Float64)),
-
-            // Other Types
-            "string" => Some(Keywords::Type(TypeKind::String)),
-            "char" => Some(Keywords::Type(TypeKind::Char)),
-            "struct" => Some(Keywords::Type(TypeKind::Struct)),
-            "bool" => Some(Keywords::Type(TypeKind::Bool)),
-
-            "import" => Some(Keywords::Import),
-
-            _ => None,
+        KeywordTable::builtin(s).map(TokenKind::Keyword)
+    }
+
+    /// The fixed source spelling of this kind, if it has one.
+    ///
+    /// Keywords, delimiters, and operators always have the same spelling;
+    /// `Eof` is spelled as the empty string. Identifiers, literals, and error
+    /// tokens have no fixed spelling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::token::tokenkind::TokenKind;
+    /// # use hm_lexer::token::operators::SpecialOps;
+    /// let arrow = TokenKind::SpecialOperator(SpecialOps::PointerAccess);
+    /// assert_eq!(arrow.fixed_spelling(), Some("->"));
+    /// ```
+    pub fn fixed_spelling(&self) -> Option<&'static str> {
+        let text = match self {
+            TokenKind::Keyword(kw) => kw.as_str(),
+            TokenKind::Delimiter(d) => d.as_str(),
+            TokenKind::ArithmeticOperator(op) => op.as_str(),
+            TokenKind::RelationalOperator(op) => op.as_str(),
+            TokenKind::LogicalOperator(op) => op.as_str(),
+            TokenKind::AssignmentOperator(op) => op.as_str(),
+            TokenKind::BitwiseOperator(op) => op.as_str(),
+            TokenKind::SpecialOperator(op) => op.as_str(),
+            TokenKind::Eof => "",
+            TokenKind::Identifier(_) | TokenKind::Literal(_) | TokenKind::Error { .. } => {
+                return None;
+            }
         };
+        Some(text)
+    }
+
+    /// The coarse class of this kind, for code that treats, say, all
+    /// operators alike.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::token::tokenkind::TokenCategory;
+    /// let (tokens, _) = hm_lexer::tokenize("x -> y").unwrap();
+    /// let categories: Vec<_> = tokens.iter().map(|t| t.kind.category()).collect();
+    /// assert_eq!(
+    ///     categories,
+    ///     [
+    ///         TokenCategory::Identifier,
+    ///         TokenCategory::Operator,
+    ///         TokenCategory::Identifier,
+    ///         TokenCategory::Eof
+    ///     ]
+    /// );
+    /// ```
+    pub fn category(&self) -> TokenCategory {
+        match self {
+            TokenKind::Keyword(_) => TokenCategory::Keyword,
+            TokenKind::Identifier(_) => TokenCategory::Identifier,
+            TokenKind::Literal(_) => TokenCategory::Literal,
+            TokenKind::Delimiter(_) => TokenCategory::Delimiter,
+            TokenKind::ArithmeticOperator(_)
+            | TokenKind::RelationalOperator(_)
+            | TokenKind::LogicalOperator(_)
+            | TokenKind::AssignmentOperator(_)
+            | TokenKind::BitwiseOperator(_)
+            | TokenKind::SpecialOperator(_) => TokenCategory::Operator,
+            TokenKind::Error { .. } => TokenCategory::Error,
+            TokenKind::Eof => TokenCategory::Eof,
+        }
+    }
+
+    /// Whether this is a keyword, including type keywords such as `i32`.
+    pub fn is_keyword(&self) -> bool {
+        self.category() == TokenCategory::Keyword
+    }
+
+    /// Whether this is a type keyword such as `i32` or `string`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::token::tokenkind::TokenKind;
+    /// assert!(TokenKind::keyword("u8").unwrap().is_type_keyword());
+    /// assert!(!TokenKind::keyword("var").unwrap().is_type_keyword());
+    /// ```
+    pub fn is_type_keyword(&self) -> bool {
+        matches!(self, TokenKind::Keyword(Keywords::Type(_)))
+    }
+
+    /// Whether this is a literal; `true` and `false` are literals, `null` is
+    /// a keyword.
+    pub fn is_literal(&self) -> bool {
+        self.category() == TokenCategory::Literal
+    }
+
+    /// Whether this is an operator of any family, including `::` and `->`.
+    pub fn is_operator(&self) -> bool {
+        self.category() == TokenCategory::Operator
+    }
+
+    /// Whether this is a delimiter such as `(`, `;`, or `.`.
+    pub fn is_delimiter(&self) -> bool {
+        self.category() == TokenCategory::Delimiter
+    }
+}
+
+/// The coarse class of a [`TokenKind`], returned by [`TokenKind::category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TokenCategory {
+    /// A [`TokenKind::Keyword`]
+    Keyword,
+    /// A [`TokenKind::Identifier`]
+    Identifier,
+    /// A [`TokenKind::Literal`]
+    Literal,
+    /// A [`TokenKind::Delimiter`]
+    Delimiter,
+    /// Any of the operator kinds
+    Operator,
+    /// A [`TokenKind::Error`]
+    Error,
+    /// [`TokenKind::Eof`]
+    Eof,
+}
 
-        kw.map(TokenKind::Keyword)
+impl TokenCategory {
+    /// The category's name in lower case, such as `"operator"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TokenCategory::Keyword => "keyword",
+            TokenCategory::Identifier => "identifier",
+            TokenCategory::Literal => "literal",
+            TokenCategory::Delimiter => "delimiter",
+            TokenCategory::Operator => "operator",
+            TokenCategory::Error => "error",
+            TokenCategory::Eof => "eof",
+        }
     }
 }
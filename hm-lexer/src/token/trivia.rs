@@ -0,0 +1,87 @@
+//! Whitespace and comments attached to tokens.
+//!
+//! The lexer normally discards trivia. Formatters and other tools that must
+//! reproduce the source exactly can instead lex with
+//! [`Lexer::next_token_with_trivia`](crate::lexer::Lexer::next_token_with_trivia)
+//! or [`lex_with_trivia`](crate::lex_with_trivia), which return each token
+//! with the trivia around it split into [`Trivia`] pieces.
+//!
+//! A token's trailing trivia runs up to and including the first newline
+//! after it; everything from there to the next token is that token's
+//! leading trivia. Trivia at the end of the input leads the `Eof` token.
+
+use crate::token::span::Span;
+use crate::token::{Lexeme, Token};
+
+/// The kind of a [`Trivia`] piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TriviaKind {
+    /// A run of spaces, tabs, and carriage returns not followed by `\n`
+    Whitespace,
+    /// A single line break, `\n` or `\r\n`
+    Newline,
+    /// A `//` comment, without the newline ending it
+    LineComment,
+    /// A `/* ... */` comment
+    BlockComment,
+    /// A `#!` interpreter line at the very start of the input, without the
+    /// newline ending it
+    Shebang,
+}
+
+/// One piece of whitespace or one comment, with its location and text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trivia {
+    /// What the piece is
+    pub kind: TriviaKind,
+    /// Where the piece is in the source
+    pub span: Span,
+    /// The piece's source text
+    pub text: Lexeme,
+}
+
+/// A token together with the trivia around it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TriviaToken {
+    /// Trivia between the previous token's trailing trivia and this token
+    pub leading: Vec<Trivia>,
+    /// The token itself
+    pub token: Token,
+    /// Trivia after the token, up to and including the first newline
+    pub trailing: Vec<Trivia>,
+}
+
+impl TriviaToken {
+    /// Append the source text of the leading trivia, the token, and the
+    /// trailing trivia to `out`.
+    pub fn write_source(&self, out: &mut String) {
+        for trivia in &self.leading {
+            out.push_str(&trivia.text);
+        }
+        out.push_str(&self.token.lexeme);
+        for trivia in &self.trailing {
+            out.push_str(&trivia.text);
+        }
+    }
+}
+
+/// Rebuild the source text that `tokens` were lexed from.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::token::trivia::reconstruct;
+/// let source = "var x = 1; // one\n\n/* two */ x += 2;\n";
+/// let (tokens, _) = hm_lexer::lex_with_trivia(source).unwrap();
+/// assert_eq!(reconstruct(&tokens), source);
+/// ```
+pub fn reconstruct(tokens: &[TriviaToken]) -> String {
+    let mut out = String::new();
+    for token in tokens {
+        token.write_source(&mut out);
+    }
+    out
+}
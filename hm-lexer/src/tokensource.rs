@@ -0,0 +1,109 @@
+//! A common interface for feeding tokens to a parser.
+//!
+//! Parsers need the current token, a few tokens of lookahead, and a way to
+//! consume tokens, regardless of whether they come from a
+//! [`Lexer`](crate::lexer::Lexer) lexing on demand or from a
+//! [`TokenStream`](crate::tokenstream::TokenStream) holding tokens lexed up
+//! front (or built by hand in tests). [`TokenSource`] captures exactly that,
+//! so a parser can be written once against the trait.
+
+use std::fmt;
+
+use crate::lexerror::LexResult;
+use crate::token::span::Span;
+use crate::token::{Lexeme, Token};
+
+/// A cursor over tokens with unbounded lookahead.
+///
+/// Past the end of input every method yields `Eof` tokens, so parsers never
+/// need to special-case running off the end.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::tokensource::TokenSource;
+/// # use hm_lexer::tokenstream::TokenStream;
+/// fn parse_assignment(tokens: &mut impl TokenSource) -> Result<String, String> {
+///     let name = tokens.bump().map_err(|e| e.to_string())?;
+///     if tokens.current().map_err(|e| e.to_string())?.lexeme != "=" {
+///         let err = tokens.unexpected(&["`=`"]).map_err(|e| e.to_string())?;
+///         return Err(err.to_string());
+///     }
+///     Ok(name.lexeme.to_string())
+/// }
+///
+/// let mut tokens = TokenStream::from_source("x = 1;").unwrap();
+/// assert_eq!(parse_assignment(&mut tokens), Ok("x".to_string()));
+///
+/// let mut tokens = TokenStream::from_source("x + 1;").unwrap();
+/// assert_eq!(
+///     parse_assignment(&mut tokens),
+///     Err("Expected `=`, found '+' at line 1, column 3".to_string())
+/// );
+/// ```
+pub trait TokenSource {
+    /// The token `n` positions past the cursor; `lookahead(0)` is the
+    /// current token.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&Token)`, which is `Eof` for positions past the end of input
+    /// - `Err(LexError)` if lexing up to that position fails
+    fn lookahead(&mut self, n: usize) -> LexResult<&Token>;
+
+    /// Consume the current token and return it.
+    ///
+    /// At the end of input this keeps returning `Eof`.
+    fn bump(&mut self) -> LexResult<Token>;
+
+    /// The token at the cursor, without consuming it.
+    fn current(&mut self) -> LexResult<&Token> {
+        self.lookahead(0)
+    }
+
+    /// Build the error a parser reports when the current token is not one
+    /// of `expected`.
+    ///
+    /// The default implementation describes the current token; sources can
+    /// override it to record or enrich the expected set.
+    fn unexpected(&mut self, expected: &[&'static str]) -> LexResult<UnexpectedToken> {
+        let token = self.current()?;
+        Ok(UnexpectedToken {
+            found: token.lexeme.clone(),
+            span: token.span,
+            expected: expected.to_vec(),
+        })
+    }
+}
+
+/// A token that does not fit what the parser expected at its position.
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct UnexpectedToken {
+    /// Text of the unexpected token; empty for `Eof`
+    pub found: Lexeme,
+    /// Location of the unexpected token
+    pub span: Span,
+    /// Descriptions of what would have been accepted, e.g. `"identifier"`
+    pub expected: Vec<&'static str>,
+}
+
+impl fmt::Display for UnexpectedToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Expected ")?;
+        match self.expected.as_slice() {
+            [] => f.write_str("something else")?,
+            [only] => f.write_str(only)?,
+            [init @ .., last] => write!(f, "{} or {last}", init.join(", "))?,
+        }
+        if self.found.is_empty() {
+            f.write_str(", found end of input")?;
+        } else {
+            write!(f, ", found '{}'", self.found)?;
+        }
+        write!(
+            f,
+            " at line {}, column {}",
+            self.span.line_start, self.span.column_start
+        )
+    }
+}
@@ -0,0 +1,411 @@
+//! Tokens lexed up front and consumed in order.
+//!
+//! A [`TokenStream`] is the eager counterpart of [`Lexer`]: the whole input
+//! is lexed before parsing starts, so lookahead is a plain index and tests
+//! can feed a parser tokens built by hand with
+//! [`Token::builder`](crate::token::Token::builder).
+//!
+//! [`TokenStream::validate`] checks the span invariants every consumer
+//! relies on. Streams built by [`TokenStream::from_source`] are validated
+//! automatically in debug builds; tests and fuzzers can call it directly on
+//! any stream.
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::charstream::CharStream;
+use crate::config::{is_continuation, ColumnCounter, ColumnUnit, LineEndings};
+use crate::intern::Interner;
+use crate::lexer::Lexer;
+use crate::lexerror::LexResult;
+use crate::token::span::Span;
+use crate::token::tokenkind::TokenKind;
+use crate::token::{Lexeme, Token};
+use crate::tokensource::TokenSource;
+
+/// A queue of already-lexed tokens that always ends with `Eof`.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::tokensource::TokenSource;
+/// # use hm_lexer::tokenstream::TokenStream;
+/// let mut tokens = TokenStream::from_source("a + b").unwrap();
+/// assert_eq!(tokens.lookahead(2).unwrap().lexeme, "b");
+/// assert_eq!(tokens.bump().unwrap().lexeme, "a");
+/// assert_eq!(tokens.current().unwrap().lexeme, "+");
+/// assert!(tokens.lookahead(10).unwrap().is_eof());
+/// ```
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct TokenStream {
+    /// Tokens not yet consumed, excluding `Eof`.
+    tokens: VecDeque<Token>,
+    /// The end-of-input token returned once `tokens` runs out.
+    eof: Token,
+    /// How the token columns were counted, used by [`Self::validate`].
+    columns: ColumnCounter,
+    /// Names of the identifier symbols in the tokens.
+    names: Interner,
+}
+
+impl TokenStream {
+    /// Wrap a list of tokens.
+    ///
+    /// The stream ends at the first `Eof` in `tokens`; anything after it is
+    /// dropped. If there is no `Eof`, one is added at the end of the last
+    /// token.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokens` - Tokens in source order, with or without a trailing `Eof`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::intern::Interner;
+    /// # use hm_lexer::token::Token;
+    /// # use hm_lexer::tokenstream::TokenStream;
+    /// let mut names = Interner::new();
+    /// let ident = Token::identifier_builder("x", &mut names).build();
+    /// let mut tokens = TokenStream::new(vec![ident]).with_interner(names);
+    /// assert_eq!(tokens.advance().lexeme, "x");
+    /// assert!(tokens.is_at_end());
+    /// assert!(tokens.advance().is_eof());
+    /// ```
+    pub fn new(tokens: Vec<Token>) -> Self {
+        let mut tokens = VecDeque::from(tokens);
+        let eof = match tokens.iter().position(Token::is_eof) {
+            Some(index) => {
+                tokens.truncate(index + 1);
+                tokens.pop_back()
+            }
+            None => None,
+        };
+        let eof = eof.unwrap_or_else(|| match tokens.back() {
+            Some(last) => eof_token(end_of(last.span)),
+            None => Token::builder(TokenKind::Eof).build(),
+        });
+        Self {
+            tokens,
+            eof,
+            columns: ColumnCounter::default(),
+            names: Interner::new(),
+        }
+    }
+
+    /// Attach the interner holding the names of the tokens' identifier
+    /// symbols. Streams built from a lexer take the lexer's.
+    pub fn with_interner(mut self, names: Interner) -> Self {
+        self.names = names;
+        self
+    }
+
+    /// Declare the unit the token columns were counted in, for
+    /// [`Self::validate`]. Streams built from a lexer take it from the
+    /// lexer's configuration; hand-built streams default to bytes.
+    pub fn with_column_unit(mut self, unit: ColumnUnit) -> Self {
+        self.columns.unit = unit;
+        self
+    }
+
+    /// Declare how the token positions count `\r\n` line endings, for
+    /// [`Self::validate`]; like [`Self::with_column_unit`], streams built
+    /// from a lexer take it from the lexer's configuration.
+    pub fn with_line_endings(mut self, line_endings: LineEndings) -> Self {
+        self.columns.line_endings = line_endings;
+        self
+    }
+
+    /// Declare the tab width the token columns were counted with, for
+    /// [`Self::validate`]; like [`Self::with_column_unit`], streams built
+    /// from a lexer take it from the lexer's configuration.
+    pub fn with_tab_width(mut self, width: usize) -> Self {
+        self.columns.tab_width = width;
+        self
+    }
+
+    /// Lex all of `source` into a stream.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(TokenStream)` holding every token of `source`
+    /// - `Err(LexError)` if the input contains invalid tokens
+    pub fn from_source(source: &str) -> LexResult<Self> {
+        let stream = Self::from_lexer(Lexer::new(CharStream::from_str(source)?))?;
+        #[cfg(debug_assertions)]
+        if let Err(violation) = stream.validate(source) {
+            panic!("lexer produced an invalid token stream: {violation}");
+        }
+        Ok(stream)
+    }
+
+    /// Drain `lexer` into a stream, up to and including its `Eof`.
+    pub fn from_lexer(mut lexer: Lexer) -> LexResult<Self> {
+        let column_unit = lexer.config().column_unit;
+        let line_endings = lexer.config().line_endings;
+        let tab_width = lexer.config().tab_width;
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_token()?;
+            let is_eof = token.is_eof();
+            tokens.push(token);
+            if is_eof {
+                break;
+            }
+        }
+        Ok(Self::new(tokens)
+            .with_column_unit(column_unit)
+            .with_line_endings(line_endings)
+            .with_tab_width(tab_width)
+            .with_interner(lexer.into_interner()))
+    }
+
+    /// The token `n` positions past the cursor, or `Eof` if the stream is
+    /// shorter than that.
+    pub fn peek(&self, n: usize) -> &Token {
+        self.tokens.get(n).unwrap_or(&self.eof)
+    }
+
+    /// Remove and return the token at the cursor.
+    ///
+    /// Once the stream is exhausted each call returns a fresh `Eof` token.
+    pub fn advance(&mut self) -> Token {
+        self.tokens
+            .pop_front()
+            .unwrap_or_else(|| eof_token(self.eof.span))
+    }
+
+    /// The names the tokens' identifier symbols stand for.
+    pub fn interner(&self) -> &Interner {
+        &self.names
+    }
+
+    /// Whether every token before `Eof` has been consumed.
+    pub fn is_at_end(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// The tokens not yet consumed, ending with `Eof`.
+    pub fn iter(&self) -> impl Iterator<Item = &Token> {
+        self.tokens.iter().chain(std::iter::once(&self.eof))
+    }
+
+    /// Check the span invariants of the remaining tokens against `source`,
+    /// the text they were lexed from.
+    ///
+    /// Spans must lie within `source`, start no earlier than the previous
+    /// span ends, and carry line, column, and (when present) character
+    /// offsets that match their byte offsets. The stream always ends with
+    /// `Eof`, and that token is checked like any other.
+    ///
+    /// For a partly consumed stream, positions are still measured from the
+    /// start of `source`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if every token is consistent
+    /// - `Err(StreamViolation)` describing the first inconsistency
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hm_lexer::intern::Interner;
+    /// # use hm_lexer::token::Token;
+    /// # use hm_lexer::tokenstream::{StreamViolation, TokenStream};
+    /// let source = "a b";
+    /// assert!(TokenStream::from_source(source).unwrap().validate(source).is_ok());
+    ///
+    /// let mut names = Interner::new();
+    /// let a = Token::identifier_builder("a", &mut names).at(1, 1, 2).build();
+    /// let b = Token::identifier_builder("b", &mut names).build();
+    /// let err = TokenStream::new(vec![a, b]).validate(source).unwrap_err();
+    /// assert!(matches!(err, StreamViolation::Overlap { index: 1, .. }));
+    /// ```
+    pub fn validate(&self, source: &str) -> Result<(), StreamViolation> {
+        let bytes = source.as_bytes();
+        let mut position = Position::default();
+        let mut previous_end = 0;
+        for (index, token) in self.iter().enumerate() {
+            let span = &token.span;
+            if span.start > span.end || span.end > bytes.len() {
+                return Err(StreamViolation::OutOfBounds {
+                    index,
+                    start: span.start,
+                    end: span.end,
+                    len: bytes.len(),
+                });
+            }
+            if span.start < previous_end {
+                return Err(StreamViolation::Overlap {
+                    index,
+                    start: span.start,
+                    previous_end,
+                });
+            }
+            previous_end = span.end;
+
+            position.advance_to(bytes, span.start, &self.columns);
+            let start = position;
+            position.advance_to(bytes, span.end, &self.columns);
+            for (offset, at, line, column) in [
+                (span.start, start, span.line_start, span.column_start),
+                (span.end, position, span.line_end, span.column_end),
+            ] {
+                if (line, column) != (at.line, at.column) {
+                    return Err(StreamViolation::Position {
+                        index,
+                        offset,
+                        expected: (at.line, at.column),
+                        found: (line, column),
+                    });
+                }
+            }
+            if let Some(found) = span.char_offsets {
+                let expected = (start.chars, position.chars);
+                if found != expected {
+                    return Err(StreamViolation::CharOffsets {
+                        index,
+                        expected,
+                        found,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A broken invariant found by [`TokenStream::validate`].
+///
+/// `index` counts from the current position of the stream.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum StreamViolation {
+    /// A span is reversed or extends past the end of the source.
+    #[error("Token {index} has span {start}..{end} outside a {len}-byte source")]
+    OutOfBounds {
+        /// Position of the token in the stream
+        index: usize,
+        /// Start of the span
+        start: usize,
+        /// End of the span
+        end: usize,
+        /// Length of the source
+        len: usize,
+    },
+
+    /// A span starts before the previous one ends.
+    #[error(
+        "Token {index} starts at byte {start}, before the previous token ends at {previous_end}"
+    )]
+    Overlap {
+        /// Position of the token in the stream
+        index: usize,
+        /// Start of the span
+        start: usize,
+        /// End of the previous span
+        previous_end: usize,
+    },
+
+    /// A span's line or column does not match its byte offset.
+    #[error(
+        "Token {index} has line {}, column {} at byte {offset}, expected line {}, column {}",
+        .found.0, .found.1, .expected.0, .expected.1
+    )]
+    Position {
+        /// Position of the token in the stream
+        index: usize,
+        /// The byte offset whose line and column are wrong
+        offset: usize,
+        /// Line and column computed from the source
+        expected: (usize, usize),
+        /// Line and column recorded in the span
+        found: (usize, usize),
+    },
+
+    /// A span's character offsets do not match its byte offsets.
+    #[error(
+        "Token {index} has character offsets {}..{}, expected {}..{}",
+        .found.0, .found.1, .expected.0, .expected.1
+    )]
+    CharOffsets {
+        /// Position of the token in the stream
+        index: usize,
+        /// Character offsets computed from the source
+        expected: (usize, usize),
+        /// Character offsets recorded in the span
+        found: (usize, usize),
+    },
+}
+
+/// Line, column, and character count at a byte offset, advanced the same
+/// way [`CharStream`] counts them.
+#[derive(Clone, Copy)]
+struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+    chars: usize,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            line: 1,
+            column: 1,
+            chars: 0,
+        }
+    }
+}
+
+impl Position {
+    /// Move forward to `offset`, which must not be behind the current one.
+    fn advance_to(&mut self, bytes: &[u8], offset: usize, columns: &ColumnCounter) {
+        for i in self.offset..offset {
+            let b = bytes[i];
+            if b == b'\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column = columns.advance(self.column, bytes, i);
+            }
+            self.chars += usize::from(!is_continuation(b));
+        }
+        self.offset = offset;
+    }
+}
+
+impl TokenSource for TokenStream {
+    fn lookahead(&mut self, n: usize) -> LexResult<&Token> {
+        Ok(self.peek(n))
+    }
+
+    fn bump(&mut self) -> LexResult<Token> {
+        Ok(self.advance())
+    }
+}
+
+/// An empty span where `span` ends.
+fn end_of(span: Span) -> Span {
+    Span {
+        start: span.end,
+        end: span.end,
+        line_start: span.line_end,
+        column_start: span.column_end,
+        line_end: span.line_end,
+        column_end: span.column_end,
+        char_offsets: span.char_offsets.map(|(_, end)| (end, end)),
+    }
+}
+
+/// An `Eof` token at `span`.
+fn eof_token(span: Span) -> Token {
+    Token {
+        kind: TokenKind::Eof,
+        span,
+        lexeme: Lexeme::default(),
+    }
+}
@@ -0,0 +1,157 @@
+//! Structured traversal of lexed tokens.
+//!
+//! Lint passes and metric collectors usually care about a few token
+//! categories, such as keywords, literals, or comments, and would otherwise
+//! each repeat the same `match` over [`TokenKind`]. [`TokenVisitor`] has one
+//! method per category, all defaulting to doing nothing, and [`walk`] or
+//! [`walk_with_trivia`] call them in source order.
+
+use std::ops::Range;
+
+use crate::highlight::comment_ranges;
+use crate::intern::Symbol;
+use crate::lexerror::ErrorCode;
+use crate::token::delimiters::Delimiters;
+use crate::token::keywords::Keywords;
+use crate::token::literals::Literals;
+use crate::token::tokenkind::TokenKind;
+use crate::token::Token;
+
+/// Callbacks for each category of token.
+///
+/// Implement only the methods you need; the rest ignore their tokens. Every
+/// method receives the whole [`Token`] alongside the decoded kind, so spans
+/// and lexemes are always at hand.
+///
+/// # Example
+///
+/// ```
+/// # use hm_lexer::token::Token;
+/// # use hm_lexer::token::keywords::Keywords;
+/// # use hm_lexer::visitor::{walk, TokenVisitor};
+/// #[derive(Default)]
+/// struct ReturnCounter(usize);
+///
+/// impl TokenVisitor for ReturnCounter {
+///     fn visit_keyword(&mut self, keyword: &Keywords, _token: &Token) {
+///         if matches!(keyword, Keywords::Return) {
+///             self.0 += 1;
+///         }
+///     }
+/// }
+///
+/// let (tokens, _) = hm_lexer::tokenize("if a { return 1; } return 2;").unwrap();
+/// let mut counter = ReturnCounter::default();
+/// walk(&tokens, &mut counter);
+/// assert_eq!(counter.0, 2);
+/// ```
+pub trait TokenVisitor {
+    /// A keyword, including type names such as `int32`.
+    fn visit_keyword(&mut self, _keyword: &Keywords, _token: &Token) {}
+
+    /// A user-defined name, as its symbol in the interner the tokens were
+    /// lexed with.
+    fn visit_identifier(&mut self, _symbol: Symbol, _token: &Token) {}
+
+    /// A string, character, or numeric literal.
+    fn visit_literal(&mut self, _literal: &Literals, _token: &Token) {}
+
+    /// A bracket or punctuation delimiter.
+    fn visit_delimiter(&mut self, _delimiter: &Delimiters, _token: &Token) {}
+
+    /// Any arithmetic, relational, logical, assignment, bitwise, or special
+    /// operator; match on `token.kind` to tell them apart.
+    fn visit_operator(&mut self, _token: &Token) {}
+
+    /// Malformed input reported by a lexer in error-recovery mode.
+    fn visit_error(&mut self, _code: ErrorCode, _token: &Token) {}
+
+    /// A run of whitespace and comments between two tokens.
+    ///
+    /// Only called by [`walk_with_trivia`].
+    fn visit_trivia(&mut self, _text: &str, _range: Range<usize>) {}
+
+    /// A single comment, reported after the trivia run containing it.
+    ///
+    /// Only called by [`walk_with_trivia`].
+    fn visit_comment(&mut self, _text: &str, _range: Range<usize>) {}
+
+    /// The end-of-file token.
+    fn visit_eof(&mut self, _token: &Token) {}
+}
+
+/// Call the visitor method matching `token`'s kind.
+pub fn walk_token(token: &Token, visitor: &mut impl TokenVisitor) {
+    match &token.kind {
+        TokenKind::Keyword(keyword) => visitor.visit_keyword(keyword, token),
+        TokenKind::Identifier(symbol) => visitor.visit_identifier(*symbol, token),
+        TokenKind::Literal(literal) => visitor.visit_literal(literal, token),
+        TokenKind::Delimiter(delimiter) => visitor.visit_delimiter(delimiter, token),
+        TokenKind::ArithmeticOperator(_)
+        | TokenKind::RelationalOperator(_)
+        | TokenKind::LogicalOperator(_)
+        | TokenKind::AssignmentOperator(_)
+        | TokenKind::BitwiseOperator(_)
+        | TokenKind::SpecialOperator(_) => visitor.visit_operator(token),
+        TokenKind::Error { message_code } => visitor.visit_error(*message_code, token),
+        TokenKind::Eof => visitor.visit_eof(token),
+    }
+}
+
+/// Visit every token in order.
+///
+/// Trivia is not part of the token list, so
+/// [`TokenVisitor::visit_trivia`] and [`TokenVisitor::visit_comment`] are
+/// never called; use [`walk_with_trivia`] for those.
+pub fn walk(tokens: &[Token], visitor: &mut impl TokenVisitor) {
+    for token in tokens {
+        walk_token(token, visitor);
+    }
+}
+
+/// Visit every token in order, along with the trivia between them.
+///
+/// The gaps between consecutive token spans are read back from `source`,
+/// which must be the text `tokens` were lexed from. Trivia before the first
+/// token and before `Eof` is included.
+///
+/// # Arguments
+///
+/// * `source` - The source text the tokens came from
+/// * `tokens` - Tokens in source order, as produced by the lexer
+/// * `visitor` - The visitor to call
+///
+/// # Example
+///
+/// ```
+/// # use std::ops::Range;
+/// # use hm_lexer::visitor::{walk_with_trivia, TokenVisitor};
+/// #[derive(Default)]
+/// struct Comments(Vec<String>);
+///
+/// impl TokenVisitor for Comments {
+///     fn visit_comment(&mut self, text: &str, _range: Range<usize>) {
+///         self.0.push(text.to_string());
+///     }
+/// }
+///
+/// let source = "// header\nvar x = 1; /* note */";
+/// let (tokens, _) = hm_lexer::tokenize(source).unwrap();
+/// let mut comments = Comments::default();
+/// walk_with_trivia(source, &tokens, &mut comments);
+/// assert_eq!(comments.0, ["// header", "/* note */"]);
+/// ```
+pub fn walk_with_trivia(source: &str, tokens: &[Token], visitor: &mut impl TokenVisitor) {
+    let mut previous_end = 0;
+    for token in tokens {
+        let gap = previous_end..token.span.start;
+        if let Some(text) = source.get(gap.clone()).filter(|text| !text.is_empty()) {
+            visitor.visit_trivia(text, gap.clone());
+            for comment in comment_ranges(source.as_bytes(), gap) {
+                visitor.visit_comment(&source[comment.clone()], comment);
+            }
+        }
+        previous_end = token.span.end;
+        walk_token(token, visitor);
+    }
+}
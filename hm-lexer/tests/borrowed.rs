@@ -0,0 +1,33 @@
+//! Borrowed tokens agree with owned ones and point into the source.
+
+use hm_lexer::token::Token;
+use hm_lexer::{tokenize, tokenize_borrowed};
+
+#[test]
+fn borrowed_tokens_convert_to_the_owned_tokens() {
+    let sources = [
+        "func add(a: int32, b: int32): int32 { return a + b; }",
+        "var s = \"caf\u{e9} \\u{1F600}\"; // trailing comment",
+        "const doc = \"\"\"\nline one\n  line two\n\"\"\";",
+        "x += 0o17u8 * 2.5f32 /* nested /* block */ */ - 'c'",
+    ];
+    for source in sources {
+        let (owned, _) = tokenize(source).unwrap();
+        let borrowed: Vec<Token> = tokenize_borrowed(source)
+            .unwrap()
+            .0
+            .into_iter()
+            .map(Token::from)
+            .collect();
+        assert_eq!(borrowed, owned, "{source:?}");
+    }
+}
+
+#[test]
+fn borrowed_lexemes_are_slices_of_the_source() {
+    let source = "var name = \"value\";";
+    let range = source.as_bytes().as_ptr_range();
+    for token in tokenize_borrowed(source).unwrap().0 {
+        assert!(range.contains(&token.lexeme.as_ptr()) || token.is_eof());
+    }
+}
@@ -0,0 +1,90 @@
+//! Diagnostics for malformed character literals, in particular the empty
+//! literal `''`, and byte literals `b'x'`.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::LexerConfig;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::{CharLiteralReason, ErrorCode, LexError};
+use hm_lexer::token::literals::Literals;
+use hm_lexer::token::tokenkind::TokenKind;
+
+#[test]
+fn empty_char_literal_points_at_both_quotes() {
+    let err = hm_lexer::tokenize("c = '';").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid character literal at line 1, column 5: empty character literal"
+    );
+    let LexError::InvalidCharLiteral { reason, span, .. } = err else {
+        panic!("expected an invalid char literal");
+    };
+    assert!(reason == CharLiteralReason::Empty);
+    assert_eq!((span.start, span.end), (4, 6));
+    assert_eq!((span.line_start, span.column_start), (1, 5));
+    assert_eq!((span.line_end, span.column_end), (1, 7));
+}
+
+#[test]
+fn lexing_continues_after_empty_char_literal() {
+    let mut lexer = Lexer::new(CharStream::from_bytes(b"c = ''; '';d").unwrap());
+    assert_eq!(lexer.next_token().unwrap().lexeme, "c");
+    assert_eq!(lexer.next_token().unwrap().lexeme, "=");
+    assert!(lexer.next_token().is_err());
+    assert_eq!(lexer.next_token().unwrap().lexeme, ";");
+    assert!(lexer.next_token().is_err());
+    assert_eq!(lexer.next_token().unwrap().lexeme, ";");
+    assert_eq!(lexer.next_token().unwrap().lexeme, "d");
+}
+
+#[test]
+fn recovery_reports_empty_char_literal_as_one_error_token() {
+    let config = LexerConfig {
+        error_recovery: true,
+        ..LexerConfig::default()
+    };
+    let mut lexer = Lexer::with_config(CharStream::from_bytes(b"f('')").unwrap(), config);
+    assert_eq!(lexer.next_token().unwrap().lexeme, "f");
+    assert_eq!(lexer.next_token().unwrap().lexeme, "(");
+
+    let error = lexer.next_token().unwrap();
+    assert!(matches!(
+        error.kind,
+        TokenKind::Error {
+            message_code: ErrorCode::InvalidCharLiteral
+        }
+    ));
+    assert_eq!(error.lexeme, "''");
+
+    assert_eq!(lexer.next_token().unwrap().lexeme, ")");
+    assert!(lexer.next_token().unwrap().is_eof());
+}
+
+#[test]
+fn byte_literal_holds_ascii_value() {
+    let (tokens, _) = hm_lexer::tokenize("x = b'A' + b'\\n';").unwrap();
+    assert!(matches!(
+        tokens[2].kind,
+        TokenKind::Literal(Literals::ByteLiteral(b'A'))
+    ));
+    assert_eq!(tokens[2].lexeme, "b'A'");
+    assert_eq!((tokens[2].span.start, tokens[2].span.end), (4, 8));
+    assert!(matches!(
+        tokens[4].kind,
+        TokenKind::Literal(Literals::ByteLiteral(b'\n'))
+    ));
+}
+
+#[test]
+fn byte_literal_rejects_non_ascii() {
+    let mut lexer = Lexer::new(CharStream::from_bytes("b'é' z".as_bytes()).unwrap());
+    let err = lexer.next_token().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid character literal at line 1, column 1: byte literal must be ASCII"
+    );
+    let LexError::InvalidCharLiteral { span, .. } = err else {
+        panic!("expected an invalid char literal");
+    };
+    assert_eq!((span.start, span.end), (0, 5));
+    assert_eq!(lexer.next_token().unwrap().lexeme, "z");
+}
@@ -0,0 +1,190 @@
+//! Round-trip tests for the binary token-stream codec.
+
+use std::collections::HashSet;
+
+use hm_lexer::codec::{self, DecodeError};
+use hm_lexer::intern::Interner;
+use hm_lexer::token::literals::Literals;
+use hm_lexer::token::Token;
+use hm_lexer::tokenize;
+
+const CORPUS: &str = r#"
+import std::io;
+
+/* block comment */
+func fib(n: u64): u64 {
+    if n <= 1u { return n; }
+    var a: u64 = 0u;
+    var b = 1u;
+    loop {
+        final next = a + b;
+        a = b; b = next;
+        n -= 1u;
+    }
+    return b ** 2 >> 1 | 0;
+}
+
+func main(): i32 {
+    const greeting: string = "hello,\tworld\n";
+    var c: char = '\'';
+    var pi: f64 = 3.14159;
+    var neg = -42;
+    var big = 18446744073709551615u;
+    var padded = 007 + 1u + 0010u;
+    var typed = 42i8 + 0o777u16 + 0b1_0001i64;
+    var ratio = 0.5f32 * 2.0f64;
+    var done: bool = true && !false;
+    var maybe = null;
+    var multi = """spans
+two lines""";
+    ptr->field = !(a && b) || (c != d) ^ ~e;
+    x %= 3; y /= 2; z *= 4; w += greeting;
+    return 0;
+}
+"#;
+
+fn debug(tokens: &[hm_lexer::token::Token]) -> Vec<String> {
+    tokens.iter().map(|t| format!("{t:?}")).collect()
+}
+
+#[test]
+fn corpus_round_trips() {
+    let (tokens, names) = tokenize(CORPUS).unwrap();
+    let bytes = codec::encode(&tokens, &names);
+    let (decoded, decoded_names) = codec::decode(&bytes).unwrap();
+    assert_eq!(debug(&tokens), debug(&decoded));
+    assert_eq!(decoded_names, names);
+}
+
+#[test]
+fn decoded_tokens_compare_and_hash_equal() {
+    let (tokens, names) = tokenize(CORPUS).unwrap();
+    let (decoded, _) = codec::decode(&codec::encode(&tokens, &names)).unwrap();
+    assert_eq!(decoded, tokens);
+
+    // Every token has its own span, so no two are equal.
+    let unique: HashSet<Token> = tokens.iter().chain(&decoded).cloned().collect();
+    assert_eq!(unique.len(), tokens.len());
+    assert!(unique.contains(&tokens[3]));
+
+    let zero = Literals::FloatLiteral(0.0);
+    assert_eq!(zero, Literals::FloatLiteral(-0.0));
+    assert_eq!(
+        Literals::FloatLiteral(f64::NAN),
+        Literals::FloatLiteral(f64::NAN)
+    );
+    assert_ne!(zero, Literals::IntLiteral(0));
+}
+
+#[test]
+fn encoding_is_smaller_than_source() {
+    let source = CORPUS.repeat(50);
+    let (tokens, names) = tokenize(&source).unwrap();
+    let bytes = codec::encode(&tokens, &names);
+    assert!(
+        bytes.len() < source.len(),
+        "{} >= {}",
+        bytes.len(),
+        source.len()
+    );
+}
+
+#[test]
+fn empty_stream_round_trips() {
+    let bytes = codec::encode(&[], &Interner::new());
+    let (tokens, names) = codec::decode(&bytes).unwrap();
+    assert!(tokens.is_empty() && names.is_empty());
+}
+
+#[test]
+fn foreign_data_is_rejected() {
+    assert!(matches!(
+        codec::decode(b"not a token stream"),
+        Err(DecodeError::BadMagic)
+    ));
+}
+
+#[test]
+fn version_mismatches_are_rejected() {
+    let (tokens, names) = tokenize("var x = 42;").unwrap();
+    let bytes = codec::encode(&tokens, &names);
+
+    // Header: 4 magic bytes, format version varint, lexer version string.
+    let mut newer_format = bytes.clone();
+    newer_format[4] += 1;
+    assert!(matches!(
+        codec::decode(&newer_format),
+        Err(DecodeError::UnsupportedVersion { .. })
+    ));
+
+    let mut other_lexer = bytes;
+    other_lexer[6] = b'9';
+    assert!(matches!(
+        codec::decode(&other_lexer),
+        Err(DecodeError::LexerVersionMismatch { .. })
+    ));
+}
+
+#[test]
+fn truncated_input_is_rejected() {
+    let (tokens, names) = tokenize("var x = 42;").unwrap();
+    let bytes = codec::encode(&tokens, &names);
+    for len in 0..bytes.len() {
+        assert!(matches!(
+            codec::decode(&bytes[..len]),
+            Err(DecodeError::UnexpectedEnd { .. })
+        ));
+    }
+}
+
+#[test]
+fn error_tokens_round_trip() {
+    use hm_lexer::charstream::CharStream;
+    use hm_lexer::config::LexerConfig;
+    use hm_lexer::lexer::Lexer;
+
+    let config = LexerConfig {
+        error_recovery: true,
+        ..LexerConfig::default()
+    };
+    let mut lexer = Lexer::with_config(CharStream::from_bytes(b"var @x = 1; $").unwrap(), config);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        let is_eof = token.is_eof();
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+
+    let (decoded, _) = codec::decode(&codec::encode(&tokens, lexer.interner())).unwrap();
+    assert_eq!(debug(&tokens), debug(&decoded));
+}
+
+#[test]
+fn char_offsets_round_trip() {
+    use hm_lexer::charstream::CharStream;
+    use hm_lexer::config::LexerConfig;
+    use hm_lexer::lexer::Lexer;
+
+    let config = LexerConfig {
+        char_offsets: true,
+        ..LexerConfig::default()
+    };
+    let stream = CharStream::from_bytes(CORPUS.as_bytes()).unwrap();
+    let mut lexer = Lexer::with_config(stream, config);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        let is_eof = token.is_eof();
+        tokens.push(token);
+        if is_eof {
+            break;
+        }
+    }
+    assert!(tokens.iter().all(|t| t.span.char_offsets.is_some()));
+
+    let (decoded, _) = codec::decode(&codec::encode(&tokens, lexer.interner())).unwrap();
+    assert_eq!(debug(&tokens), debug(&decoded));
+}
@@ -0,0 +1,169 @@
+//! Differential tests: the production lexer must agree with the naive
+//! reference lexer in `tests/reference` on every input, token for token and
+//! error for error.
+
+mod reference;
+
+use hm_lexer::tokenize;
+
+const CORPUS: &[&str] = &[
+    "var x = 42;",
+    "func main(): i32 { return 0; }",
+    "import std::io;\nptr->field = !(a && b) || (c != d) ^ ~e;",
+    "x += 1; y -= 2; z *= 3; w /= 4; v %= 5; p ** 2 << 1 >> 3 <= 4 >= 5 == 6",
+    "const s: string = \"tab\\there\\nnew \\\"quoted\\\" \\\\ \\0\";",
+    "var c: char = '\\''; var d = 'x'; var e = '\\n';",
+    "3.14159 42u 18446744073709551615u 9223372036854775807 007 1.5u",
+    "9223372036854775808",
+    "18446744073709551616u",
+    "42.foo 42. .5 a.b",
+    "123abc",
+    "x = 10q;",
+    "y = 3.0u;",
+    "version = 1.2.3;",
+    "1.2..3 1.2.x",
+    "func f(args: i32...) { g(args...); } a..b 1...2 ....",
+    "x **= 2; x ** = 2; x ***= 2; x **== 2;",
+    "var n = a ?? b; n ??= c; a ? b : c; a???b ??== x?.y",
+    "1.2.3.4u",
+    "x = 7u_x + 1;",
+    "0x1F 1e5 3.0uz 2.5_f",
+    "mask = 0b1010_0001 | 0b1u;",
+    "0b; 0b_ 0b",
+    "0b102 0b1a 0b12u",
+    "0b1111111111111111111111111111111111111111111111111111111111111111 0b0u",
+    "0b1111111111111111111111111111111111111111111111111111111111111111u",
+    "mode = 0o755; max = 0o1777777777777777777777u; 0o0_7",
+    "0o", "0o_;", "0o8", "0o759", "0o7x", "0o1777777777777777777777",
+    "million = 1_000_000; 1_0u 3_1.4_1 0b_1010_1 0o7_7u 1_2.3_4.5",
+    "1__0", "1_", "1_u", "1_.5", "1.5_", "1.5__0", "0b1__0", "0b1_", "0o__7", "0b_",
+    "9_223_372_036_854_775_808",
+    "a = 42i8; b = 100u32; c = 3.14f32; d = 2.5f64; e = 0b1111_1111u8; f = 0o777i16",
+    "127i8 255u8 32767i16 65535u16 2147483647i32 4294967295u32 9223372036854775807i64",
+    "18446744073709551615u64 1_000i32 0.1f32 340282356779733661637539395458142568448.0f32",
+    "128i8", "256u8", "0b1_0000_0000u8", "18446744073709551616u64", "42f32", "3.0i32",
+    "1i", "2u7", "0o7f32", "340282356779733661637539395458142568449.0f32", "4.5f", "1_i8",
+    "9i8x",
+    "-128i8 -32768i16 -0b1000_0000i8 -9223372036854775808i64 -129i8 9223372036854775809i64",
+    "x-128i8 - 128i8", "-127i8 128i8",
+    "s = \"open\nclose\";",
+    "s = \"a\\nb\"; m = \"\"\"one\ntwo\"\"\";",
+    "greet = \"hello ${name}, you are ${age + 1} years\";",
+    "\"${a}${b}\" \"${ \"inner ${x} \" }\" \"${ f({ }) }\" \"\\${not} $x\"",
+    "\"${ /* c */ 1 }\"", "\"${\"", "\"${ x \"", "\"${ @ }\"", "\"a ${}",
+    "// line comment\n/* block\ncomment */ final y = 1;",
+    "/* unterminated block comment",
+    "#!/usr/bin/env hm\nvar x = 1;", "#!", "#!hm\r\n#!not_first", " #!/bin/hm", "# !x",
+    "a /* x /* y */ z */ b /**/ c /*/ d */ e /* /* */ */ f",
+    "/* outer /* inner */ still open", "/* // */ g /* \"*/\" */ h", "*/ */",
+    "x = 1;\n  /* never closed\n", "a /* b */ c /* d /* e */", "\"${ /* open }\"", "/*",
+    "\"unterminated string",
+    "'ab'",
+    "''",
+    "'\\q'",
+    "c = b'A' + b'\\n' + b'\\'';",
+    "b'\u{e9}' b'ab' b'' b'",
+    "ab'c' b 'd'",
+    "\"bad \\q escape\"",
+    "done = true; ok = !false && truex || false_ || True;",
+    "x = null; y = nullable ?? null_; z = Null;",
+    "s = \"smile \\u{1F600} \\u{e9}\"; c = '\\u{41}'; m = \"\"\"\\u{10FFFF}\\u{0}\"\"\";",
+    "\"\\u{D800}\"", "\"\\u{110000}\"", "\"\\u{}\"", "\"\\u{1234567}\"", "\"\\u{12g}\"",
+    "\"\\u41\"", "\"\\u{41\"", "'\\u{41'", "\"\"\"\\u{dfff}\"\"\"", "\"\"\"a\\u{}b\"\"\"",
+    "\"trailing backslash \\",
+    "var @ = 1;",
+    "  \r\n\t  ",
+    "\"multi\nline\" after",
+    "\"caf\u{e9}\" '\u{e9}'",
+    "s = \"\u{65e5}\u{672c} \u{1F600}\"; c = '\u{1F600}'; d = '\u{65e5}'; m = \"\"\"\u{e9}\"\"\";",
+    "'\u{65e5}\u{672c}'", "'\u{e9}", "\"\\\u{e9}\"",
+    "\"\"\"\n    two \\\"\"\"\n    lines\\t\n    \"\"\" x",
+    "\"\"\"unterminated \"\" triple",
+    "\"\"\"bad \\q\"\"\" \"\"\"\\\u{e9}\"\"\"",
+    "s = \u{201C}hi\u{201D};", "x\u{a0}= 1;", "a \u{2014} b", "\"\u{2018}ok\u{2019}\u{a0}\"",
+    "",
+];
+
+fn assert_agree(source: &str) {
+    let actual = tokenize(source);
+    let expected = reference::tokenize(source);
+    if let (Ok((_, actual)), Ok((_, expected))) = (&actual, &expected) {
+        assert_eq!(actual, expected, "interned names disagree on {source:?}");
+    }
+    let actual = format!("{:?}", actual.map(|(tokens, _)| tokens));
+    let expected = format!("{:?}", expected.map(|(tokens, _)| tokens));
+    assert_eq!(actual, expected, "lexers disagree on {source:?}");
+}
+
+#[test]
+fn corpus_agrees_with_reference() {
+    for source in CORPUS {
+        assert_agree(source);
+    }
+}
+
+/// Minimal xorshift generator so the fuzz inputs are reproducible without
+/// pulling in a dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Source fragments that exercise every token family and their boundaries.
+const FRAGMENTS: &[&str] = &[
+    "var", "func", "i32", "u64", "string", "import", "x", "_tmp", "a1", "0", "42", "7u", "3.25",
+    "1.", ".", "...", "u", "99999999999999999999", "\"s\"", "\"\\n\\t\"", "\"\"\"", "'c'", "'\\''",
+    "'", "\"", "\\", "=", "==", "!", "!=", "<", "<<", "<=", ">", ">>", ">=", "+", "+=", "-", "-=",
+    "->", "*", "**", "*=", "**=", "/", "/=", "%", "%=", "&", "&&", "|", "||", "^", "~", ":", "::",
+    "(", ")", "{", "}", "[", "]", ";", ",", "?", "??", "??=", "//", "/*", "*/", " ", " ", "\n",
+    "\t", "\r", "@", "#", "$", "`", "\u{e9}", "\u{201C}", "\u{a0}", "b", "b'A'", "0b", "0b1_0", "1",
+    "0o", "0o7_5", "8", "_", "1_0", "__", "i8", "u32", "f32", "f64", "300", "1.5", "\"${", "$",
+    "\\u{", "1F600}", "D800}",
+];
+
+fn fragment_input(rng: &mut Rng) -> String {
+    let len = 1 + rng.below(24);
+    (0..len)
+        .map(|_| FRAGMENTS[rng.below(FRAGMENTS.len())])
+        .collect()
+}
+
+fn byte_soup_input(rng: &mut Rng) -> String {
+    let len = 1 + rng.below(32);
+    (0..len)
+        .map(|_| {
+            // Printable ASCII plus the whitespace the lexer treats as trivia.
+            match rng.below(100) {
+                0..=4 => '\n',
+                5..=9 => ' ',
+                _ => (b' ' + rng.below(95) as u8) as char,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn fuzzed_fragments_agree_with_reference() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    for _ in 0..5_000 {
+        assert_agree(&fragment_input(&mut rng));
+    }
+}
+
+#[test]
+fn fuzzed_bytes_agree_with_reference() {
+    let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+    for _ in 0..5_000 {
+        assert_agree(&byte_soup_input(&mut rng));
+    }
+}
@@ -0,0 +1,78 @@
+//! Rejecting token forms newer than the configured edition.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::Edition;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::{ErrorCode, LexError};
+use hm_lexer::token::tokenkind::TokenKind;
+
+const SOURCE: &str = "var a = 0b101; var b = 0o17; var c = b'x'; var d = 0;";
+
+fn lexer(source: &str, edition: Edition) -> Lexer {
+    let stream = CharStream::from_bytes(source.as_bytes()).unwrap();
+    Lexer::builder()
+        .edition(edition)
+        .error_recovery(true)
+        .build(stream)
+}
+
+#[test]
+fn latest_edition_accepts_every_form() {
+    let mut lexer = lexer(SOURCE, Edition::LATEST);
+    lexer.by_ref().for_each(|token| drop(token.unwrap()));
+    assert!(lexer.errors().is_empty());
+}
+
+#[test]
+fn older_edition_rejects_each_newer_literal_as_a_whole() {
+    let mut lexer = lexer(SOURCE, Edition::V1);
+    let errors: Vec<_> = lexer
+        .by_ref()
+        .map(Result::unwrap)
+        .filter(|token| matches!(token.kind, TokenKind::Error { .. }))
+        .map(|token| token.lexeme.to_string())
+        .collect();
+    assert_eq!(errors, ["0b101", "0o17", "b'x'"]);
+
+    let features: Vec<_> = lexer
+        .errors()
+        .iter()
+        .map(|err| match err {
+            LexError::RequiresEdition {
+                feature, required, ..
+            } => (feature.as_str(), *required),
+            other => panic!("unexpected error {other}"),
+        })
+        .collect();
+    assert_eq!(
+        features,
+        [
+            ("Binary literals", Edition::V2),
+            ("Octal literals", Edition::V2),
+            ("Byte literals", Edition::V2),
+        ]
+    );
+    assert_eq!(lexer.errors()[0].code(), ErrorCode::RequiresEdition);
+    assert_eq!(ErrorCode::RequiresEdition.as_str(), "L0014");
+}
+
+#[test]
+fn malformed_literals_report_their_own_error_first() {
+    let stream = CharStream::from_bytes(b"0b102").unwrap();
+    let err = Lexer::builder()
+        .edition(Edition::V1)
+        .build(stream)
+        .next_token()
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InvalidNumber);
+}
+
+#[test]
+fn editions_round_trip_through_their_numbers() {
+    for number in 1..=Edition::LATEST.number() {
+        let edition = Edition::from_number(number).unwrap();
+        assert_eq!(edition.number(), number);
+        assert_eq!(edition.to_string(), number.to_string());
+    }
+    assert_eq!(Edition::from_number(Edition::LATEST.number() + 1), None);
+}
@@ -0,0 +1,33 @@
+//! Empty input lexes to a lone `Eof` token instead of failing.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::{lex_with_trivia, tokenize, tokenize_bytes};
+
+#[test]
+fn empty_source_is_a_single_eof_at_the_start() {
+    for (tokens, _) in [tokenize("").unwrap(), tokenize_bytes(b"").unwrap()] {
+        let [eof] = tokens.as_slice() else {
+            panic!("expected only Eof, got {tokens:?}");
+        };
+        assert!(eof.is_eof());
+        assert_eq!((eof.span.start, eof.span.end), (0, 0));
+        assert_eq!((eof.span.line_start, eof.span.column_start), (1, 1));
+        assert_eq!((eof.span.line_end, eof.span.column_end), (1, 1));
+    }
+}
+
+#[test]
+fn empty_streams_keep_returning_eof() {
+    let mut lexer = Lexer::new(CharStream::from_reader(std::io::empty()).unwrap());
+    assert!(lexer.next_token().unwrap().is_eof());
+    assert!(lexer.next_token().unwrap().is_eof());
+    assert!(lexer.errors().is_empty());
+}
+
+#[test]
+fn empty_source_has_no_trivia() {
+    let (tokens, _) = lex_with_trivia("").unwrap();
+    assert_eq!(tokens.len(), 1);
+    assert!(tokens[0].leading.is_empty() && tokens[0].trailing.is_empty());
+}
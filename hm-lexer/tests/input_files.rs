@@ -0,0 +1,54 @@
+//! Lexing input read from files on disk.
+
+use std::fs;
+use std::path::PathBuf;
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::LexError;
+
+/// A path in the temp directory unique to this test binary and `name`.
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("hm-lexer-{}-{name}", std::process::id()))
+}
+
+#[test]
+fn files_are_read_and_lexed() {
+    let path = temp_path("main.hm");
+    fs::write(&path, "var x = 1;\n").unwrap();
+
+    let stream = CharStream::from_file(&path).unwrap();
+    let lexemes: Vec<_> = Lexer::new(stream).map(|t| t.unwrap().lexeme).collect();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(lexemes, ["var", "x", "=", "1", ";", ""]);
+}
+
+#[test]
+fn directories_are_reported_as_io_errors() {
+    let dir = std::env::temp_dir();
+    let err = CharStream::from_file(&dir).err().unwrap();
+    let LexError::Io { path, .. } = &err else {
+        panic!("expected an I/O error, got {err:?}");
+    };
+    assert_eq!(path, &dir.display().to_string());
+    assert_eq!(err.code().as_str(), "L0013");
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn mapped_files_lex_like_read_files() {
+    let path = temp_path("mapped.hm");
+    let source = "func f(a: int32) {\n    return a * 2;\n}\n";
+    fs::write(&path, source).unwrap();
+
+    let file = fs::File::open(&path).unwrap();
+    // SAFETY: the file is private to this test and not modified while mapped.
+    let map = unsafe { memmap2::Mmap::map(&file) }.unwrap();
+    let mapped: Vec<_> = Lexer::new(CharStream::from_mmap(map).unwrap())
+        .map(Result::unwrap)
+        .collect();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(mapped, hm_lexer::tokenize(source).unwrap().0);
+}
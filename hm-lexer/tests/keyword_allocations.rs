@@ -0,0 +1,126 @@
+//! Verifies that lexing tokens with a fixed spelling (keywords, boolean
+//! literals, operators, and delimiters) performs no heap allocation, and
+//! that lazy lexemes and borrowed tokens skip the copies other tokens make.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::{tokenize, tokenize_borrowed};
+
+/// Global allocator that counts every allocation request.
+struct CountingAllocator;
+
+thread_local! {
+    /// Allocations made by the current thread, so tests running in parallel
+    /// do not see each other's.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Lex `source` to the end, returning the number of tokens before `Eof`
+/// and the allocations made while lexing them.
+fn count_allocations(source: &str, lazy_lexemes: bool) -> (usize, usize) {
+    let stream = CharStream::from_bytes(source.as_bytes()).unwrap();
+    let mut lexer = Lexer::builder().lazy_lexemes(lazy_lexemes).build(stream);
+
+    let before = ALLOCATIONS.get();
+    let mut tokens = 0;
+    loop {
+        let token = lexer.next_token().unwrap();
+        if token.is_eof() {
+            break;
+        }
+        if !lazy_lexemes {
+            assert_eq!(token.lexeme.len(), token.span.end - token.span.start);
+        }
+        tokens += 1;
+    }
+    (tokens, ALLOCATIONS.get() - before)
+}
+
+#[test]
+fn keyword_tokens_do_not_allocate() {
+    let source =
+        "func return if else elif loop switch case var const final import i32 u64 string bool \
+         null true false";
+    let (keywords, allocations) = count_allocations(source, false);
+
+    assert_eq!(keywords, 19);
+    assert_eq!(allocations, 0, "lexing keywords allocated");
+}
+
+#[test]
+fn operator_and_delimiter_tokens_do_not_allocate() {
+    let source = "( ) { } [ ] , . ; : :: ? + - * / % ++ -- += -= *= /= %= = == != < <= > >= \
+                  && || ! & | ^ ~ << >> &= |= ^= <<= >>= -> ** =>";
+    let (tokens, allocations) = count_allocations(source, false);
+
+    assert!(tokens > 40);
+    assert_eq!(allocations, 0, "lexing punctuation allocated");
+}
+
+// `SmolStr` keeps short lexemes inline, so neither mode allocates for them
+#[cfg(not(feature = "smol_str"))]
+#[test]
+fn lazy_literals_do_not_allocate() {
+    let source = "42 7u 3.25 0b1010 0o17 100_000 12i8 2.5f32 'c' b'x' \"text\"";
+    let (literals, eager) = count_allocations(source, false);
+    let (_, lazy) = count_allocations(source, true);
+
+    assert_eq!(literals, 11);
+    // Only the decoded string value and the digits of `100_000` without
+    // their separator are left
+    assert_eq!(lazy, 2, "lazy literals allocated");
+    assert_eq!(eager, lazy + literals);
+}
+
+// `SmolStr` keeps short lexemes inline, so neither mode allocates for them
+#[cfg(not(feature = "smol_str"))]
+#[test]
+fn lazy_identifiers_allocate_only_new_names() {
+    let source = "alpha beta gamma";
+    let repeated = [source; 20].join(" ");
+    let (_, once) = count_allocations(source, true);
+    let (identifiers, lazy) = count_allocations(&repeated, true);
+    let (_, eager) = count_allocations(&repeated, false);
+
+    // Names seen before are already interned
+    assert_eq!(lazy, once);
+    assert_eq!(eager, lazy + identifiers);
+}
+
+#[test]
+fn borrowed_tokens_do_not_copy_lexemes() {
+    let source = "alpha = 42 + beta * 7; ".repeat(100);
+
+    let before = ALLOCATIONS.get();
+    let (borrowed, _) = tokenize_borrowed(&source).unwrap();
+    let borrowing = ALLOCATIONS.get() - before;
+
+    let before = ALLOCATIONS.get();
+    let (owned, _) = tokenize(&source).unwrap();
+    let owning = ALLOCATIONS.get() - before;
+
+    assert_eq!(borrowed.len(), owned.len());
+    // Two interned names plus a handful of buffers; no per-token lexemes
+    assert!(borrowing <= 8, "{borrowing} allocations");
+    // One per identifier and per literal lexeme, two of each per statement
+    #[cfg(not(feature = "smol_str"))]
+    assert!(owning >= 400, "{owning} allocations");
+    assert!(borrowing < owning || cfg!(feature = "smol_str"));
+}
@@ -0,0 +1,70 @@
+//! Lexing with a customized keyword table.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::keywordtable::{KeywordEntry, KeywordTable};
+use hm_lexer::lexer::Lexer;
+use hm_lexer::token::keywords::Keywords;
+use hm_lexer::token::literals::Literals;
+use hm_lexer::token::tokenkind::TokenKind;
+use hm_lexer::token::Token;
+
+fn lex(source: &str, table: KeywordTable) -> Vec<Token> {
+    let stream = CharStream::from_bytes(source.as_bytes()).unwrap();
+    Lexer::builder()
+        .keywords(table)
+        .build(stream)
+        .map(Result::unwrap)
+        .filter(|token| !token.is_eof())
+        .collect()
+}
+
+#[test]
+fn default_table_matches_builtin_keywords() {
+    for keyword in Keywords::ALL {
+        assert!(KeywordTable::new().get(keyword.as_str()) == Some(KeywordEntry::Reserved(keyword)));
+        assert_eq!(
+            TokenKind::keyword(keyword.as_str()),
+            Some(TokenKind::Keyword(keyword))
+        );
+    }
+}
+
+#[test]
+fn empty_table_lexes_every_word_as_an_identifier() {
+    let tokens = lex("func if i32 null", KeywordTable::empty());
+    assert_eq!(tokens.len(), 4);
+    assert!(tokens
+        .iter()
+        .all(|token| matches!(token.kind, TokenKind::Identifier(_))));
+}
+
+#[test]
+fn empty_table_can_be_filled_from_scratch() {
+    let table = KeywordTable::empty().define("def", KeywordEntry::Reserved(Keywords::Func));
+    let tokens = lex("def func", table);
+    assert_eq!(tokens[0].kind, TokenKind::Keyword(Keywords::Func));
+    assert!(matches!(tokens[1].kind, TokenKind::Identifier(_)));
+}
+
+#[test]
+fn removing_then_redefining_a_builtin_restores_it() {
+    let removed = KeywordTable::new().remove("var");
+    assert!(removed.get("var").is_none());
+
+    let restored = removed.define("var", KeywordEntry::Reserved(Keywords::Var));
+    assert_eq!(
+        lex("var", restored)[0].kind,
+        TokenKind::Keyword(Keywords::Var)
+    );
+}
+
+#[test]
+fn defined_spellings_take_precedence_over_bool_literals() {
+    let table = KeywordTable::new().define("true", KeywordEntry::Contextual(7));
+    let tokens = lex("true false", table);
+    assert!(matches!(tokens[0].kind, TokenKind::Identifier(_)));
+    assert_eq!(
+        tokens[1].kind,
+        TokenKind::Literal(Literals::BoolLiteral(false))
+    );
+}
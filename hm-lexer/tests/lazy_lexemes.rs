@@ -0,0 +1,67 @@
+//! Lexing without copying lexeme text out of the source.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::intern::Interner;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::token::literals::{Literals, StringPart};
+use hm_lexer::token::tokenkind::TokenKind;
+use hm_lexer::token::Token;
+use hm_lexer::tokenize;
+
+const SOURCE: &str =
+    "func add(a: i32) -> i32 {\n    return a + 31; // sum\n}\nvar s = \"hi ${name}\";\n";
+
+fn lex_lazy(source: &str) -> (Vec<Token>, Interner) {
+    let stream = CharStream::from_bytes(source.as_bytes()).unwrap();
+    let mut lexer = Lexer::builder().lazy_lexemes(true).build(stream);
+    let tokens = lexer.by_ref().map(Result::unwrap).collect();
+    (tokens, lexer.into_interner())
+}
+
+#[test]
+fn lazy_tokens_keep_only_static_spellings() {
+    for token in lex_lazy(SOURCE).0 {
+        assert_eq!(token.lexeme, token.kind.fixed_spelling().unwrap_or(""));
+    }
+}
+
+#[test]
+fn text_recovers_the_eager_lexemes() {
+    let (eager, _) = tokenize(SOURCE).unwrap();
+    let (lazy, _) = lex_lazy(SOURCE);
+    assert_eq!(eager.len(), lazy.len());
+    for (eager, lazy) in eager.iter().zip(&lazy) {
+        assert_eq!(lazy.span, eager.span);
+        assert_eq!(lazy.text(SOURCE), eager.lexeme);
+    }
+}
+
+#[test]
+fn identifiers_keep_their_names() {
+    let (tokens, interner) = lex_lazy(SOURCE);
+    let names: Vec<_> = tokens
+        .into_iter()
+        .filter_map(|token| match token.kind {
+            TokenKind::Identifier(symbol) => Some(interner.resolve(symbol)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, ["add", "a", "a", "s"]);
+}
+
+#[test]
+fn interpolated_tokens_are_lazy_too() {
+    let (tokens, _) = lex_lazy(SOURCE);
+    let parts = tokens
+        .iter()
+        .find_map(|token| match &token.kind {
+            TokenKind::Literal(Literals::InterpolatedString(parts)) => Some(parts),
+            _ => None,
+        })
+        .unwrap();
+    let StringPart::Expr(nested) = &parts[1] else {
+        panic!("expected an expression part");
+    };
+    assert!(nested[0].lexeme.is_empty());
+    assert_eq!(nested[0].text(SOURCE), "name");
+}
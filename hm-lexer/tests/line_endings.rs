@@ -0,0 +1,57 @@
+//! Position tracking on sources with `\r\n` line endings.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::{LexerConfig, LineEndings};
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::LexError;
+use hm_lexer::lineindex::LineIndex;
+use hm_lexer::tokenstream::TokenStream;
+
+const SOURCE: &str = "var a = 1;\r\n/* one\r\n two */ var s = \"\"\"\r\nx\r\n\"\"\";\r\n";
+
+fn lexer(source: &str, line_endings: LineEndings, offsets_only: bool) -> Lexer {
+    let config = LexerConfig {
+        line_endings,
+        offsets_only,
+        ..LexerConfig::default()
+    };
+    Lexer::with_config(CharStream::from_bytes(source.as_bytes()).unwrap(), config)
+}
+
+#[test]
+fn crlf_spans_end_where_editors_put_them() {
+    let tokens = TokenStream::from_lexer(lexer(SOURCE, LineEndings::Crlf, false)).unwrap();
+    tokens.validate(SOURCE).unwrap();
+
+    let semicolon = tokens.peek(4);
+    assert_eq!(
+        (semicolon.span.line_end, semicolon.span.column_end),
+        (1, 11)
+    );
+    let string = tokens.peek(8);
+    assert_eq!((string.span.line_start, string.span.column_start), (3, 17));
+    assert_eq!((string.span.line_end, string.span.column_end), (5, 4));
+}
+
+#[test]
+fn line_index_agrees_with_the_lexer() {
+    let tracked: Vec<_> = lexer(SOURCE, LineEndings::Crlf, false)
+        .map(|t| t.unwrap().span)
+        .collect();
+    let index = LineIndex::new(SOURCE).with_line_endings(LineEndings::Crlf);
+    let resolved: Vec<_> = lexer(SOURCE, LineEndings::Crlf, true)
+        .map(|t| index.resolve(t.unwrap().span))
+        .collect();
+    assert_eq!(resolved, tracked);
+}
+
+#[test]
+fn errors_at_a_crlf_are_reported_before_the_carriage_return() {
+    for offsets_only in [false, true] {
+        let mut lexer = lexer("s = \"open\r\n", LineEndings::Crlf, offsets_only);
+        let err = lexer.find_map(Result::err).unwrap();
+        assert!(matches!(err, LexError::UnterminatedString { .. }));
+        let span = err.span().unwrap();
+        assert_eq!((span.line_end, span.column_end), (1, 10));
+    }
+}
@@ -0,0 +1,70 @@
+//! Non-ASCII characters pasted in place of ASCII punctuation.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::LexerConfig;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::{ErrorCode, LexError};
+
+#[test]
+fn smart_quotes_suggest_a_straight_quote() {
+    let err = hm_lexer::tokenize("var s = \u{201C}hi\u{201D};").unwrap_err();
+    let LexError::LookalikeCharacter {
+        ch,
+        suggestion,
+        span,
+        ..
+    } = &err
+    else {
+        panic!("expected a lookalike character, got {err:?}");
+    };
+    assert_eq!((*ch, *suggestion), ('\u{201C}', '"'));
+    assert_eq!((span.start, span.end), (8, 11));
+    assert_eq!(err.code().as_str(), "L0012");
+}
+
+#[test]
+fn dashes_and_non_breaking_spaces_are_named() {
+    for (source, name, suggestion) in [
+        ("x = a \u{2014} b;", "em dash", '-'),
+        ("x = a \u{2013} b;", "en dash", '-'),
+        ("x\u{a0}= 1;", "non-breaking space", ' '),
+    ] {
+        let err = hm_lexer::tokenize(source).unwrap_err();
+        assert!(matches!(
+            err,
+            LexError::LookalikeCharacter { name: n, suggestion: s, .. } if n == name && s == suggestion
+        ));
+        assert!(err
+            .to_string()
+            .ends_with(&format!("did you mean '{suggestion}'?")));
+    }
+}
+
+#[test]
+fn lookalikes_are_fine_inside_literals() {
+    let tokens = hm_lexer::tokenize("s = \"\u{201C}quoted\u{201D}\u{a0}\"; c = '\u{2014}';");
+    assert!(tokens.is_ok());
+}
+
+#[test]
+fn other_characters_are_reported_whole() {
+    let err = hm_lexer::tokenize("x = \u{e9};").unwrap_err();
+    let LexError::UnexpectedCharacter { ch, span, .. } = err else {
+        panic!("expected an unexpected character");
+    };
+    assert_eq!(ch, '\u{e9}');
+    assert_eq!(span.end - span.start, 2);
+}
+
+#[test]
+fn recovery_skips_a_lookalike() {
+    let config = LexerConfig {
+        error_recovery: true,
+        ..LexerConfig::default()
+    };
+    let source = "a \u{2019} b";
+    let mut lexer = Lexer::with_config(CharStream::from_bytes(source.as_bytes()).unwrap(), config);
+    let lexemes: Vec<_> = lexer.by_ref().map(|token| token.unwrap().lexeme).collect();
+    assert_eq!(lexemes, ["a", "\u{2019}", "b", ""]);
+    assert!(lexer.errors()[0].code() == ErrorCode::LookalikeCharacter);
+}
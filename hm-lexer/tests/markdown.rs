@@ -0,0 +1,82 @@
+//! Extraction and position remapping of Markdown code blocks.
+
+use hm_lexer::lexerror::LexError;
+use hm_lexer::markdown::{check, code_blocks, lex_blocks};
+
+const DOC: &str = concat!(
+    "# Tutorial\n",
+    "\n",
+    "```hummingbird\n",
+    "var x = 1;\n",
+    "```\n",
+    "\n",
+    "~~~~ hm ignore\n",
+    "// ``` does not close a tilde fence\n",
+    "var c = 'ab';\n",
+    "~~~~\n",
+    "\n",
+    "```rust\n",
+    "fn main() {}\n",
+    "```\n",
+    "\n",
+    "  ```hummingbird\n",
+    "  var y = x;\n",
+    "  ```\n",
+    "\n",
+    "```hm\n",
+    "```\n",
+);
+
+#[test]
+fn finds_only_hummingbird_blocks() {
+    let blocks = code_blocks(DOC);
+    let infos: Vec<&str> = blocks.iter().map(|b| b.info).collect();
+    assert_eq!(infos, ["hummingbird", "hm ignore", "hummingbird", "hm"]);
+    assert_eq!(
+        blocks[1].source,
+        "// ``` does not close a tilde fence\nvar c = 'ab';\n"
+    );
+    assert_eq!(blocks[3].source, "");
+    for block in &blocks {
+        assert_eq!(
+            &DOC[block.offset..block.offset + block.source.len()],
+            block.source
+        );
+        let line = DOC[..block.offset].matches('\n').count() + 1;
+        assert_eq!(block.line, line);
+    }
+}
+
+#[test]
+fn token_spans_point_into_the_document() {
+    let lexed = lex_blocks(DOC);
+    let (tokens, _) = lexed[2].result.as_ref().unwrap();
+    let y = &tokens[1];
+    assert_eq!(&DOC[y.span.start..y.span.end], "y");
+    assert_eq!((y.span.line_start, y.span.column_start), (17, 7));
+
+    let (empty, _) = lexed[3].result.as_ref().unwrap();
+    assert_eq!(empty.len(), 1);
+    assert!(empty[0].is_eof());
+    assert_eq!(empty[0].span.line_start, 21);
+}
+
+#[test]
+fn errors_are_reported_at_document_positions() {
+    let errors = check(DOC);
+    assert_eq!(errors.len(), 1);
+    let LexError::InvalidCharLiteral { span, .. } = &errors[0] else {
+        panic!("expected an invalid char literal, got {}", errors[0]);
+    };
+    assert_eq!(&DOC[span.start..span.end], "'ab'");
+    assert_eq!((span.line_start, span.column_start), (9, 9));
+}
+
+#[test]
+fn unclosed_block_runs_to_end_of_document() {
+    let doc = "intro\n```hummingbird\nvar z = 2;";
+    let blocks = code_blocks(doc);
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].source, "var z = 2;");
+    assert!(check(doc).is_empty());
+}
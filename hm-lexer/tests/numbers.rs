@@ -0,0 +1,123 @@
+//! Range checks on typed numeric literals.
+
+use hm_lexer::lexerror::ErrorCode;
+use hm_lexer::token::literals::{Literals, NumericSuffix, NumericValue};
+use hm_lexer::token::operators::arithmetic::ArithmeticOps;
+use hm_lexer::token::tokenkind::TokenKind;
+use hm_lexer::tokenize;
+
+/// The value of the typed literal that follows a leading `-`.
+fn negated_literal(source: &str) -> (NumericValue, NumericSuffix) {
+    let (tokens, _) = tokenize(source).unwrap();
+    assert_eq!(
+        tokens[0].kind,
+        TokenKind::ArithmeticOperator(ArithmeticOps::Minus)
+    );
+    match tokens[1].kind {
+        TokenKind::Literal(Literals::NumericLiteral { value, suffix }) => (value, suffix),
+        ref other => panic!("expected a typed literal, got {other:?}"),
+    }
+}
+
+/// Fold a unary minus into the typed literal after it, as a parser does,
+/// returning `None` if the negated value does not fit the suffix's type.
+fn fold_negation(value: NumericValue, suffix: NumericSuffix) -> Option<i64> {
+    let negated = match value {
+        NumericValue::Int(v) => -i128::from(v),
+        NumericValue::Magnitude(v) => -i128::from(v),
+        _ => return None,
+    };
+    let fits = match suffix {
+        NumericSuffix::Int8 => i8::try_from(negated).is_ok(),
+        NumericSuffix::Int16 => i16::try_from(negated).is_ok(),
+        NumericSuffix::Int32 => i32::try_from(negated).is_ok(),
+        NumericSuffix::Int64 => i64::try_from(negated).is_ok(),
+        _ => false,
+    };
+    fits.then_some(negated as i64)
+}
+
+#[test]
+fn signed_minimums_lex_as_magnitudes_after_a_minus() {
+    assert_eq!(
+        negated_literal("-128i8"),
+        (NumericValue::Magnitude(128), NumericSuffix::Int8)
+    );
+    assert_eq!(
+        negated_literal("-32768i16"),
+        (NumericValue::Magnitude(32768), NumericSuffix::Int16)
+    );
+    assert_eq!(
+        negated_literal("-0b1000_0000i8"),
+        (NumericValue::Magnitude(128), NumericSuffix::Int8)
+    );
+    assert_eq!(
+        negated_literal("-9223372036854775808i64"),
+        (
+            NumericValue::Magnitude(9_223_372_036_854_775_808),
+            NumericSuffix::Int64
+        )
+    );
+
+    // Values that fit stay plain, minus or not
+    assert_eq!(
+        negated_literal("-127i8"),
+        (NumericValue::Int(127), NumericSuffix::Int8)
+    );
+}
+
+#[test]
+fn negated_minimums_fold_to_the_type_minimum() {
+    let (value, suffix) = negated_literal("-128i8");
+    assert_eq!(fold_negation(value, suffix), Some(i8::MIN.into()));
+
+    let (value, suffix) = negated_literal("-9223372036854775808i64");
+    assert_eq!(fold_negation(value, suffix), Some(i64::MIN));
+}
+
+#[test]
+fn signed_minimum_magnitudes_without_a_minus_are_rejected() {
+    for source in [
+        "128i8",
+        "32768i16",
+        "2147483648i32",
+        "9223372036854775808i64",
+        "0b1000_0000i8",
+        "- 128i8",
+    ] {
+        let err = tokenize(source).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidNumber, "{source}");
+    }
+}
+
+#[test]
+fn signed_literals_past_the_minimum_magnitude_are_rejected() {
+    for source in [
+        "-129i8",
+        "-32769i16",
+        "-2147483649i32",
+        "-9223372036854775809i64",
+    ] {
+        let err = tokenize(source).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidNumber, "{source}");
+    }
+}
+
+#[test]
+fn minimum_magnitudes_round_trip() {
+    use hm_lexer::codec::{decode, encode};
+    use hm_lexer::snapshot;
+    use hm_lexer::token::Token;
+
+    let (tokens, names) = tokenize("-9223372036854775808i64").unwrap();
+    assert_eq!(decode(&encode(&tokens, &names)).unwrap().0, tokens);
+    assert_eq!(
+        snapshot::parse(&snapshot::serialize(&tokens, &names))
+            .unwrap()
+            .0,
+        tokens
+    );
+
+    let rebuilt = Token::builder(tokens[1].kind.clone()).build();
+    assert_eq!(rebuilt.lexeme, "9223372036854775808i64");
+}
@@ -0,0 +1,53 @@
+//! Offset-only lexing plus [`LineIndex`] reproduces the positions of a
+//! line-tracking lexer.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::{ColumnUnit, LexerConfig};
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::LexResult;
+use hm_lexer::lineindex::LineIndex;
+use hm_lexer::token::Token;
+
+const SOURCE: &str = "// é🙂 header\nfunc f(a: int32): int32 {\n\treturn a * 2;\n}\n\
+    var s = \"\"\"\n  🙂\n\"\"\"; var c = 'x';\n";
+
+fn lex(source: &str, config: LexerConfig) -> LexResult<Vec<Token>> {
+    let stream = CharStream::from_bytes(source.as_bytes()).unwrap();
+    Lexer::with_config(stream, config).collect()
+}
+
+#[test]
+fn resolved_spans_match_tracked_spans() {
+    for unit in [ColumnUnit::Bytes, ColumnUnit::Chars, ColumnUnit::Utf16] {
+        let tracked = LexerConfig {
+            column_unit: unit,
+            ..LexerConfig::default()
+        };
+        let untracked = LexerConfig {
+            offsets_only: true,
+            ..tracked.clone()
+        };
+        let expected = lex(SOURCE, tracked).unwrap();
+        let offsets = lex(SOURCE, untracked).unwrap();
+        assert_eq!(expected.len(), offsets.len());
+
+        let index = LineIndex::new(SOURCE).with_column_unit(unit);
+        for (want, got) in expected.iter().zip(&offsets) {
+            assert_eq!((got.span.line_start, got.span.column_end), (0, 0));
+            assert_eq!(want.span, index.resolve(got.span), "{want:?}");
+        }
+    }
+}
+
+#[test]
+fn errors_are_located_without_line_tracking() {
+    let config = LexerConfig {
+        offsets_only: true,
+        ..LexerConfig::default()
+    };
+    for source in ["x\n  y @", "a\n\"open", "\n\n var c = 'ab';", "1\n 10q"] {
+        let expected = lex(source, LexerConfig::default()).unwrap_err();
+        let got = lex(source, config.clone()).unwrap_err();
+        assert_eq!(expected.to_string(), got.to_string());
+    }
+}
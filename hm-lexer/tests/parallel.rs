@@ -0,0 +1,65 @@
+//! Parallel chunked lexing gives the same result as sequential lexing.
+
+use hm_lexer::parallel::MIN_CHUNK_LEN;
+use hm_lexer::{lex_parallel, tokenize};
+
+/// Lines of ordinary code, repeated to span several chunks.
+fn code(lines: usize) -> String {
+    "var total = count * 2 + \"${name}\"; // running total\n".repeat(lines)
+}
+
+fn assert_same(source: &str) {
+    assert!(source.len() > 4 * MIN_CHUNK_LEN);
+    for n_threads in [2, 3, 8] {
+        match (lex_parallel(source, n_threads), tokenize(source)) {
+            (Ok(parallel), Ok(sequential)) => assert!(parallel == sequential),
+            (Err(parallel), Err(sequential)) => {
+                assert_eq!(parallel.to_string(), sequential.to_string());
+                assert_eq!(parallel.span(), sequential.span());
+            }
+            (parallel, sequential) => panic!(
+                "results differ with {n_threads} threads: {:?} vs {:?}",
+                parallel.map(|(t, _)| t.len()),
+                sequential.map(|(t, _)| t.len())
+            ),
+        }
+    }
+}
+
+#[test]
+fn plain_code_matches_tokenize() {
+    assert_same(&code(6_000));
+}
+
+#[test]
+fn comments_and_strings_across_chunk_boundaries() {
+    let comment = format!("/* outer\n{}/* nested\n*/\n*/\n", code(2_000));
+    let string = format!("var s = \"\"\"\n{}\"\"\";\n", code(2_000));
+    let source = [code(1_000), comment, code(1_000), string, code(1_000)].concat();
+    assert_same(&source);
+}
+
+#[test]
+fn a_comment_covering_everything_but_the_end() {
+    let source = format!("/*\n{}*/\nvar last = 1;\n", code(6_000));
+    assert_same(&source);
+}
+
+#[test]
+fn first_error_matches_tokenize() {
+    let late = format!("{}var bad = @;\n{}", code(5_500), code(100));
+    assert_same(&late);
+
+    let unterminated = format!("{}/* never closed\n{}", code(2_000), code(3_500));
+    assert_same(&unterminated);
+
+    let shebangs = format!("{}#!not a shebang here\n{}", code(3_000), code(3_000));
+    assert_same(&shebangs);
+}
+
+#[test]
+fn small_inputs_are_lexed_on_the_calling_thread() {
+    let source = code(10);
+    assert!(lex_parallel(&source, 0).unwrap() == tokenize(&source).unwrap());
+    assert!(lex_parallel("", 4).unwrap() == tokenize("").unwrap());
+}
@@ -0,0 +1,118 @@
+//! Import-driven lexing of multi-file programs.
+
+use std::collections::HashMap;
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::LexerConfig;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::project::{lex_project, Project, ProjectError};
+use hm_lexer::source::SourceFile;
+use hm_lexer::sourcemap::SourceMap;
+
+/// Lex `files[0]` as the root, resolving `a::b` to the file named `a/b.hm`.
+fn lex(files: &[(&str, &str)]) -> Result<Project, ProjectError> {
+    let files: HashMap<String, String> = files
+        .iter()
+        .map(|(name, text)| (name.to_string(), text.to_string()))
+        .collect();
+    let root = SourceFile::new("main.hm", files["main.hm"].clone());
+    lex_project(root, |path, _| {
+        let name = format!("{}.hm", path.replace("::", "/"));
+        match files.get(&name) {
+            Some(text) => Ok(SourceFile::new(name, text.clone())),
+            None => Err("file not found".to_string()),
+        }
+    })
+}
+
+/// Like [`lex`], for projects that must fail to lex.
+fn lex_err(files: &[(&str, &str)]) -> ProjectError {
+    match lex(files) {
+        Ok(_) => panic!("expected the project to fail to lex"),
+        Err(err) => err,
+    }
+}
+
+#[test]
+fn shared_imports_are_lexed_once() {
+    let project = lex(&[
+        ("main.hm", "import a; import b;"),
+        ("a.hm", "import shared::util;"),
+        ("b.hm", "import shared::util; var b = 1;"),
+        ("shared/util.hm", "func help() {}"),
+    ])
+    .unwrap();
+
+    let names: Vec<&str> = project.source_map.iter().map(|(_, f)| f.name()).collect();
+    assert_eq!(names, ["main.hm", "a.hm", "shared/util.hm", "b.hm"]);
+    let a = project.root().imports[0].target;
+    let b = project.root().imports[1].target;
+    assert!(project.module(a).imports[0].target == project.module(b).imports[0].target);
+
+    let import = &project.module(b).imports[0];
+    let text = &project.source_map.get(b).source()[import.span];
+    assert_eq!(text, "import shared::util;");
+}
+
+#[test]
+fn cycles_are_reported() {
+    let err = lex_err(&[
+        ("main.hm", "import a;"),
+        ("a.hm", "import b;"),
+        ("b.hm", "import a;"),
+    ]);
+    assert_eq!(err.to_string(), "import cycle: a.hm -> b.hm -> a.hm");
+}
+
+#[test]
+fn unresolved_imports_and_lex_errors_name_the_file() {
+    let err = lex_err(&[("main.hm", "var x = 1;\n  import missing::mod;")]);
+    assert_eq!(
+        err.to_string(),
+        "main.hm:2:3: cannot resolve import 'missing::mod': file not found"
+    );
+
+    let err = lex_err(&[("main.hm", "import bad;"), ("bad.hm", "var x = @;")]);
+    assert!(matches!(err, ProjectError::Lex { ref file, .. } if file == "bad.hm"));
+}
+
+#[test]
+fn malformed_imports_are_left_to_the_parser() {
+    let project = lex(&[("main.hm", "import; import a::; import 1;")]).unwrap();
+    assert!(project.root().imports.is_empty());
+}
+
+#[test]
+fn import_spans_resolve_to_their_file() {
+    let project = lex(&[
+        ("main.hm", "var x = 1;\nimport a;"),
+        ("a.hm", "// helpers\n\n  import b;"),
+        ("b.hm", "func f() {}"),
+    ])
+    .unwrap();
+
+    let mut locations: Vec<String> = project
+        .modules
+        .iter()
+        .flat_map(|module| module.imports.iter().map(|i| module.file.span(i.span)))
+        .map(|span| project.source_map.location(span).to_string())
+        .collect();
+    locations.sort();
+    assert_eq!(locations, ["a.hm:3:3", "main.hm:2:1"]);
+}
+
+#[test]
+fn offsets_only_spans_resolve_from_the_source() {
+    let mut map = SourceMap::new();
+    let file = map.add(SourceFile::new("lib.hm", "var a;\n\tvar b;"));
+    let config = LexerConfig {
+        offsets_only: true,
+        ..LexerConfig::default()
+    };
+    let text = map.get(file).source().as_str().as_bytes();
+    let mut lexer = Lexer::with_config(CharStream::from_bytes(text).unwrap(), config);
+    let b = lexer.nth(4).unwrap().unwrap();
+
+    let location = map.location(file.span(b.span));
+    assert_eq!((location.line, location.column), (2, 6));
+}
@@ -0,0 +1,196 @@
+//! Recovery mode: malformed input becomes `Error` tokens, the errors are
+//! kept on the lexer, and lexing carries on to the end of the input, as
+//! [`Lexer::tokenize_all`] does.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::{LexerConfig, SyncStrategy};
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::{ErrorCode, LexError};
+use hm_lexer::token::tokenkind::TokenKind;
+use hm_lexer::token::Token;
+
+fn recovering(source: &str, sync_strategy: SyncStrategy) -> Lexer {
+    let config = LexerConfig {
+        error_recovery: true,
+        sync_strategy,
+        ..LexerConfig::default()
+    };
+    Lexer::with_config(CharStream::from_bytes(source.as_bytes()).unwrap(), config)
+}
+
+fn lex_to_eof(lexer: &mut Lexer) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        let eof = token.is_eof();
+        tokens.push(token);
+        if eof {
+            return tokens;
+        }
+    }
+}
+
+#[test]
+fn every_error_becomes_a_token_and_is_recorded() {
+    let source = "a = @;\nb = \"bad \\q\";\nc = 'xy';\nd = \"open\ne = 1;";
+    let mut lexer = recovering(source, SyncStrategy::NextLine);
+    let tokens = lex_to_eof(&mut lexer);
+
+    let codes: Vec<_> = tokens
+        .iter()
+        .filter_map(|token| match token.kind {
+            TokenKind::Error { message_code } => Some(message_code),
+            _ => None,
+        })
+        .collect();
+    assert!(
+        codes
+            == [
+                ErrorCode::UnexpectedCharacter,
+                ErrorCode::InvalidEscape,
+                ErrorCode::InvalidCharLiteral,
+                ErrorCode::UnterminatedString,
+            ]
+    );
+
+    let errors = lexer.errors();
+    assert_eq!(errors.len(), codes.len());
+    for (err, code) in errors.iter().zip(&codes) {
+        assert!(err.code() == *code);
+    }
+    assert!(matches!(errors[3], LexError::UnterminatedString { .. }));
+    assert_eq!((errors[3].line(), errors[3].column()), (Some(4), Some(5)));
+
+    let last: Vec<_> = tokens[tokens.len() - 5..]
+        .iter()
+        .map(|t| &*t.lexeme)
+        .collect();
+    assert_eq!(last, ["e", "=", "1", ";", ""]);
+    assert_eq!(lexer.take_errors().len(), 4);
+    assert!(lexer.errors().is_empty());
+}
+
+#[test]
+fn restore_forgets_errors_after_the_saved_position() {
+    let mut lexer = recovering("x # y # z", SyncStrategy::Whitespace);
+    let state = lexer.save();
+    lex_to_eof(&mut lexer);
+    assert_eq!(lexer.errors().len(), 2);
+
+    lexer.restore(state);
+    assert!(lexer.errors().is_empty());
+    lex_to_eof(&mut lexer);
+    assert_eq!(lexer.errors().len(), 2);
+}
+
+#[test]
+fn recovered_errors_are_located_without_line_tracking() {
+    let config = LexerConfig {
+        error_recovery: true,
+        offsets_only: true,
+        ..LexerConfig::default()
+    };
+    let stream = CharStream::from_bytes(b"x\n  @ y").unwrap();
+    let mut lexer = Lexer::with_config(stream, config);
+    lex_to_eof(&mut lexer);
+    let [LexError::UnexpectedCharacter { span, .. }] = lexer.errors() else {
+        panic!("expected one unexpected character");
+    };
+    assert_eq!((span.line_start, span.column_start), (2, 3));
+    assert_eq!((span.line_end, span.column_end), (2, 4));
+}
+
+#[test]
+fn every_recovered_error_is_located_without_line_tracking() {
+    let config = LexerConfig {
+        error_recovery: true,
+        offsets_only: true,
+        ..LexerConfig::default()
+    };
+    let stream = CharStream::from_bytes(b"@\n x #\n\n  $").unwrap();
+    let mut lexer = Lexer::with_config(stream, config);
+    lex_to_eof(&mut lexer);
+    let starts: Vec<_> = lexer
+        .errors()
+        .iter()
+        .map(|err| {
+            let span = err.span().unwrap();
+            (span.line_start, span.column_start)
+        })
+        .collect();
+    assert_eq!(starts, [(1, 1), (2, 4), (4, 3)]);
+}
+
+#[test]
+fn tokenize_all_reports_every_error() {
+    let source = "a = @;\nb = #;\nc = 1;";
+    let lexer = Lexer::new(CharStream::from_bytes(source.as_bytes()).unwrap());
+    let (tokens, errors, _) = lexer.tokenize_all();
+    let lines: Vec<_> = errors
+        .iter()
+        .map(|err| match err {
+            LexError::UnexpectedCharacter { ch, span, .. } => (*ch, span.line_start),
+            _ => panic!("expected unexpected characters"),
+        })
+        .collect();
+    assert_eq!(lines, [('@', 1), ('#', 2)]);
+    assert!(tokens.last().unwrap().is_eof());
+    assert_eq!(tokens.len(), 11);
+
+    let lexer = Lexer::new(CharStream::from_bytes(b"var ok = 1;").unwrap());
+    let (tokens, errors, _) = lexer.tokenize_all();
+    assert!(errors.is_empty());
+    assert_eq!(tokens.len(), 6);
+}
+
+#[test]
+fn iteration_continues_past_recovered_errors_and_stops_after_eof() {
+    let mut lexer = recovering("a @ b # c", SyncStrategy::Whitespace);
+    let (low, high) = lexer.size_hint();
+    assert!(low >= 1 && high >= Some(6));
+
+    let tokens: Vec<_> = lexer.by_ref().collect::<Result<_, _>>().unwrap();
+    assert_eq!(tokens.len(), 6);
+    assert!(tokens[5].is_eof());
+    assert_eq!(lexer.errors().len(), 2);
+    assert!(lexer.next().is_none());
+    assert_eq!(lexer.size_hint(), (0, Some(0)));
+}
+
+/// The lexemes up to end of input, with error tokens marked by a `!`.
+fn resumed(source: &str, sync_strategy: SyncStrategy) -> Vec<String> {
+    let mut lexer = recovering(source, sync_strategy);
+    let mut tokens = lex_to_eof(&mut lexer);
+    tokens.pop();
+    tokens
+        .iter()
+        .map(|token| match token.kind {
+            TokenKind::Error { .. } => format!("!{}", token.lexeme),
+            _ => token.lexeme.to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn whitespace_sync_resumes_at_the_next_whitespace() {
+    let strategy = SyncStrategy::Whitespace;
+    assert_eq!(resumed("x @ y; z", strategy), ["x", "!@", "y", ";", "z"]);
+    assert_eq!(resumed("x @ y\nz", strategy), ["x", "!@", "y", "z"]);
+    assert_eq!(resumed("x @y; z", strategy), ["x", "!@y;", "z"]);
+}
+
+#[test]
+fn statement_terminator_sync_resumes_at_the_semicolon() {
+    let strategy = SyncStrategy::StatementTerminator;
+    assert_eq!(resumed("x @ y; z", strategy), ["x", "!@ y", ";", "z"]);
+    // Without a `;` the rest of the input is skipped.
+    assert_eq!(resumed("x @ y\nz", strategy), ["x", "!@ y\nz"]);
+    assert_eq!(resumed("x @; z", strategy), ["x", "!@", ";", "z"]);
+}
+
+#[test]
+fn next_line_sync_resumes_after_the_newline() {
+    let strategy = SyncStrategy::NextLine;
+    assert_eq!(resumed("x @ y; z", strategy), ["x", "!@ y; z"]);
+    assert_eq!(resumed("x @ y\nz", strategy), ["x", "!@ y\n", "z"]);
+}
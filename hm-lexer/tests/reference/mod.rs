@@ -0,0 +1,754 @@
+//! A deliberately naive reference lexer used for differential testing.
+//!
+//! Everything here favours obviousness over speed: positions are recomputed
+//! by rescanning the source from the start, punctuation is matched against a
+//! flat table by trying the longest spelling first, and keywords are looked
+//! up by scanning [`Keywords::ALL`]. None of the production lexer's internals
+//! (byte-class table, token builder, escape macro) are reused, so a
+//! disagreement between the two points at a behavior change in one of them.
+
+use hm_lexer::intern::Interner;
+use hm_lexer::lexerror::CharLiteralReason::{
+    self, Empty, MissingClosingQuote, NonAscii, TooManyCharacters,
+};
+use hm_lexer::lexerror::{LexError, NumberClass};
+use hm_lexer::token::delimiters::Delimiters;
+use hm_lexer::token::keywords::Keywords;
+use hm_lexer::token::literals::{Literals, NumericSuffix, NumericValue, StringPart};
+use hm_lexer::token::operators::arithmetic::ArithmeticOps;
+use hm_lexer::token::operators::assignment::AssignmentOps;
+use hm_lexer::token::operators::bitwise::BitwiseOps;
+use hm_lexer::token::operators::logical::LogicalOps;
+use hm_lexer::token::operators::relational::RelationalOps;
+use hm_lexer::token::operators::SpecialOps;
+use hm_lexer::token::span::Span;
+use hm_lexer::token::tokenkind::TokenKind;
+use hm_lexer::token::{Lexeme, Token};
+
+/// Suffixes allowed after an integer and after a float.
+const INT_SUFFIXES: &[&str] = &["u", "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64"];
+const FLOAT_SUFFIXES: &[&str] = &["f32", "f64"];
+
+/// Non-ASCII characters reported with the ASCII character they stand in for.
+const LOOKALIKES: &[(char, &str, char)] = &[
+    ('\u{201C}', "left double quotation mark", '"'),
+    ('\u{201D}', "right double quotation mark", '"'),
+    ('\u{2018}', "left single quotation mark", '\''),
+    ('\u{2019}', "right single quotation mark", '\''),
+    ('\u{2013}', "en dash", '-'),
+    ('\u{2014}', "em dash", '-'),
+    ('\u{2212}', "minus sign", '-'),
+    ('\u{00A0}', "non-breaking space", ' '),
+    ('\u{202F}', "narrow non-breaking space", ' '),
+];
+
+/// Every punctuation spelling, longer spellings first so the first match
+/// is the longest one.
+const PUNCTUATION: &[&str] = &[
+    "...", "??=", "??",
+    #[cfg(feature = "experimental-ops")]
+    "**=",
+    "==", "!=", "<=", ">=", "<<", ">>", "&&", "||", "+=", "-=", "*=", "/=", "%=", "->", "::",
+    #[cfg(feature = "experimental-ops")]
+    "**",
+    "(", ")", "{", "}", "[", "]", ";", ",", ".", "?", ":", "=", "+", "-", "*", "/", "%", "<",
+    ">", "!", "&", "|", "^", "~",
+];
+
+/// Map a punctuation spelling to its token kind.
+fn punctuation_kind(spelling: &str) -> TokenKind {
+    match spelling {
+        "(" => TokenKind::Delimiter(Delimiters::LeftParen),
+        ")" => TokenKind::Delimiter(Delimiters::RightParen),
+        "{" => TokenKind::Delimiter(Delimiters::LeftBrace),
+        "}" => TokenKind::Delimiter(Delimiters::RightBrace),
+        "[" => TokenKind::Delimiter(Delimiters::LeftBracket),
+        "]" => TokenKind::Delimiter(Delimiters::RightBracket),
+        ";" => TokenKind::Delimiter(Delimiters::Semicolon),
+        "," => TokenKind::Delimiter(Delimiters::Comma),
+        "." => TokenKind::Delimiter(Delimiters::Dot),
+        "?" => TokenKind::Delimiter(Delimiters::QuestionMark),
+        ":" => TokenKind::Delimiter(Delimiters::Colon),
+        "::" => TokenKind::SpecialOperator(SpecialOps::ScopingOperator),
+        "->" => TokenKind::SpecialOperator(SpecialOps::PointerAccess),
+        "..." => TokenKind::SpecialOperator(SpecialOps::Ellipsis),
+        "??" => TokenKind::SpecialOperator(SpecialOps::NullCoalescing),
+        "??=" => TokenKind::AssignmentOperator(AssignmentOps::NullCoalescingAssign),
+        "+" => TokenKind::ArithmeticOperator(ArithmeticOps::Plus),
+        "-" => TokenKind::ArithmeticOperator(ArithmeticOps::Minus),
+        "*" => TokenKind::ArithmeticOperator(ArithmeticOps::Asterisk),
+        "/" => TokenKind::ArithmeticOperator(ArithmeticOps::Slash),
+        "%" => TokenKind::ArithmeticOperator(ArithmeticOps::Modulo),
+        #[cfg(feature = "experimental-ops")]
+        "**" => TokenKind::ArithmeticOperator(ArithmeticOps::Exponent),
+        #[cfg(feature = "experimental-ops")]
+        "**=" => TokenKind::AssignmentOperator(AssignmentOps::ExponentAssign),
+        "==" => TokenKind::RelationalOperator(RelationalOps::Equal),
+        "!=" => TokenKind::RelationalOperator(RelationalOps::NotEqual),
+        "<" => TokenKind::RelationalOperator(RelationalOps::LessThan),
+        ">" => TokenKind::RelationalOperator(RelationalOps::GreaterThan),
+        "<=" => TokenKind::RelationalOperator(RelationalOps::LessThanOrEqual),
+        ">=" => TokenKind::RelationalOperator(RelationalOps::GreaterThanOrEqual),
+        "!" => TokenKind::LogicalOperator(LogicalOps::Not),
+        "&&" => TokenKind::LogicalOperator(LogicalOps::And),
+        "||" => TokenKind::LogicalOperator(LogicalOps::Or),
+        "=" => TokenKind::AssignmentOperator(AssignmentOps::Assign),
+        "+=" => TokenKind::AssignmentOperator(AssignmentOps::AddAssign),
+        "-=" => TokenKind::AssignmentOperator(AssignmentOps::SubtractAssign),
+        "*=" => TokenKind::AssignmentOperator(AssignmentOps::MultiplyAssign),
+        "/=" => TokenKind::AssignmentOperator(AssignmentOps::DivideAssign),
+        "%=" => TokenKind::AssignmentOperator(AssignmentOps::ModuloAssign),
+        "&" => TokenKind::BitwiseOperator(BitwiseOps::And),
+        "|" => TokenKind::BitwiseOperator(BitwiseOps::Or),
+        "^" => TokenKind::BitwiseOperator(BitwiseOps::Xor),
+        "~" => TokenKind::BitwiseOperator(BitwiseOps::Not),
+        "<<" => TokenKind::BitwiseOperator(BitwiseOps::LeftShift),
+        ">>" => TokenKind::BitwiseOperator(BitwiseOps::RightShift),
+        _ => unreachable!("not a punctuation spelling: {spelling}"),
+    }
+}
+
+/// 1-based (line, column) of byte offset `at`, found by rescanning `src`.
+fn line_col(src: &[u8], at: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+    for &b in &src[..at] {
+        if b == b'\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
+}
+
+fn span(src: &[u8], start: usize, end: usize) -> Span {
+    let (line_start, column_start) = line_col(src, start);
+    let (line_end, column_end) = line_col(src, end);
+    Span {
+        start,
+        end,
+        line_start,
+        column_start,
+        line_end,
+        column_end,
+        char_offsets: None,
+    }
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Offset of the first byte after any whitespace and comments at `i`, or an
+/// error if a block comment there is never closed.
+fn skip_trivia(src: &[u8], mut i: usize) -> Result<usize, LexError> {
+    loop {
+        if i < src.len() && matches!(src[i], b' ' | b'\t' | b'\r' | b'\n') {
+            i += 1;
+        } else if src[i..].starts_with(b"//") || (i == 0 && src.starts_with(b"#!")) {
+            while i < src.len() && src[i] != b'\n' {
+                i += 1;
+            }
+        } else if src[i..].starts_with(b"/*") {
+            let start = i;
+            let mut depth = 0;
+            while i < src.len() {
+                if src[i..].starts_with(b"/*") {
+                    depth += 1;
+                    i += 2;
+                } else if src[i..].starts_with(b"*/") {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+            if depth > 0 {
+                return Err(LexError::UnterminatedBlockComment {
+                    span: span(src, start, src.len()),
+                    context: None,
+                });
+            }
+        } else {
+            return Ok(i);
+        }
+    }
+}
+
+/// Decode the escape whose backslash is at `i`, returning the character and
+/// the offset just past the escape.
+fn escape(src: &[u8], i: usize, quote: u8, start: usize) -> Result<(char, usize), LexError> {
+    let decoded = match src.get(i + 1) {
+        Some(b'n') => '\n',
+        Some(b't') => '\t',
+        Some(b'r') => '\r',
+        Some(b'0') => '\0',
+        Some(b'\\') => '\\',
+        Some(b'$') => '$',
+        Some(&b) if b == quote => b as char,
+        Some(b'u') => return unicode_escape(src, i, start, None),
+        other => {
+            let end = match other {
+                Some(&b) => i + 1 + char_width(b),
+                None => i + 1,
+            };
+            return Err(LexError::InvalidEscape {
+                sequence: match other {
+                    Some(&b) => format!("\\{}", b as char),
+                    None => "\\(EOF)".to_string(),
+                },
+                span: span(src, start, end),
+                context: None,
+            });
+        }
+    };
+    Ok((decoded, i + 2))
+}
+
+/// Decode the `\u{...}` escape whose backslash is at `i`. Errors span from
+/// `start` through the escape, or cover `literal` if one is given.
+fn unicode_escape(
+    src: &[u8],
+    i: usize,
+    start: usize,
+    literal: Option<Span>,
+) -> Result<(char, usize), LexError> {
+    let invalid = |sequence: &[u8], context: String| LexError::InvalidEscape {
+        sequence: String::from_utf8_lossy(sequence).into_owned(),
+        span: literal.unwrap_or_else(|| span(src, start, i + sequence.len())),
+        context: Some(context.into()),
+    };
+    if src.get(i + 2) != Some(&b'{') {
+        return Err(invalid(b"\\u", "expected `{` after `\\u`".to_string()));
+    }
+    let mut end = i + 3;
+    while end < src.len() && src[end].is_ascii_hexdigit() {
+        end += 1;
+    }
+    let digits = std::str::from_utf8(&src[i + 3..end]).unwrap();
+    match src.get(end) {
+        Some(b'}') => {}
+        Some(&b) if b.is_ascii_alphanumeric() => {
+            return Err(invalid(
+                &src[i..=end],
+                format!("`{}` is not a hex digit", b as char),
+            ));
+        }
+        _ => {
+            return Err(invalid(
+                &src[i..end],
+                "unterminated unicode escape; expected `}`".to_string(),
+            ));
+        }
+    }
+    let sequence = &src[i..=end];
+    if digits.is_empty() {
+        return Err(invalid(sequence, "empty unicode escape".to_string()));
+    }
+    if digits.len() > 6 {
+        return Err(invalid(
+            sequence,
+            "unicode escape must have at most 6 hex digits".to_string(),
+        ));
+    }
+    match u32::from_str_radix(digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+    {
+        Some(ch) => Ok((ch, end + 1)),
+        None => Err(invalid(
+            sequence,
+            format!("`{digits}` is not a Unicode scalar value"),
+        )),
+    }
+}
+
+/// Decode the UTF-8 character starting at `i`, returning it and the offset
+/// just past it.
+fn utf8_char(src: &[u8], i: usize) -> (char, usize) {
+    let width = char_width(src[i]);
+    let text = std::str::from_utf8(&src[i..i + width]).unwrap();
+    (text.chars().next().unwrap(), i + width)
+}
+
+/// Width of the UTF-8 character whose leading byte is `b`.
+fn char_width(b: u8) -> usize {
+    (b.leading_ones() as usize).max(1)
+}
+
+fn unterminated(src: &[u8], start: usize, end: usize) -> LexError {
+    LexError::UnterminatedString {
+        span: span(src, start, end),
+        context: None,
+    }
+}
+
+fn invalid_char(src: &[u8], start: usize, end: usize, reason: CharLiteralReason) -> LexError {
+    LexError::InvalidCharLiteral {
+        reason,
+        span: span(src, start, end),
+        context: None,
+    }
+}
+
+fn invalid_number(src: &[u8], start: usize, end: usize) -> LexError {
+    LexError::InvalidNumber {
+        lexeme: String::from_utf8_lossy(&src[start..end]).into_owned(),
+        span: span(src, start, end),
+        context: None,
+    }
+}
+
+/// Reject a `_` in the digit run `run_start..run_end` that is not followed
+/// by a digit.
+fn separators(src: &[u8], start: usize, run_start: usize, run_end: usize) -> Result<(), LexError> {
+    let run = &src[run_start..run_end];
+    let Some(offset) = (0..run.len())
+        .find(|&i| run[i] == b'_' && run.get(i + 1).is_none_or(|&b| b == b'_'))
+    else {
+        return Ok(());
+    };
+    let context = if offset + 1 < run.len() {
+        "digit separators `_` cannot be repeated"
+    } else {
+        "a number cannot end with a digit separator `_`"
+    };
+    let mut end = run_end;
+    while end < src.len() && is_ident_byte(src[end]) {
+        end += 1;
+    }
+    Err(LexError::InvalidNumber {
+        lexeme: String::from_utf8_lossy(&src[start..end]).into_owned(),
+        span: span(src, run_start + offset, end),
+        context: Some(context.into()),
+    })
+}
+
+/// Lex a `0b` or `0o` literal in `base` starting at `start`.
+fn radix(src: &[u8], start: usize, base: u32, name: &str) -> Result<(TokenKind, usize), LexError> {
+    let run_end = |mut i: usize, pred: &dyn Fn(u8) -> bool| {
+        while i < src.len() && pred(src[i]) {
+            i += 1;
+        }
+        i
+    };
+    let digits_end = run_end(start + 2, &|b| b == b'_' || (b as char).is_digit(base));
+    let digits: String = src[start + 2..digits_end]
+        .iter()
+        .filter(|&&b| b != b'_')
+        .map(|&b| b as char)
+        .collect();
+
+    if let Some(&digit) = src.get(digits_end).filter(|b| b.is_ascii_digit()) {
+        let end = run_end(digits_end, &is_ident_byte);
+        return Err(LexError::InvalidNumber {
+            lexeme: String::from_utf8_lossy(&src[start..end]).into_owned(),
+            span: span(src, digits_end, end),
+            context: Some(format!("invalid digit '{}' in {name} literal", digit as char).into()),
+        });
+    }
+    if digits.is_empty() {
+        let prefix = src[start + 1] as char;
+        return Err(invalid_number(src, start, digits_end)
+            .with_context(format!("expected at least one digit after `0{prefix}` prefix")));
+    }
+    separators(src, start, start + 2, digits_end)?;
+
+    let end = run_end(digits_end, &is_ident_byte);
+    let suffix = &src[digits_end..end];
+    if !suffix.is_empty() && !INT_SUFFIXES.iter().any(|s| s.as_bytes() == suffix) {
+        return Err(LexError::InvalidNumberSuffix {
+            suffix: String::from_utf8_lossy(suffix).into(),
+            span: span(src, start, end),
+            allowed: NumberClass::Int,
+            context: None,
+        });
+    }
+    if suffix.len() > 1 {
+        return typed(src, start, end, &digits, base, suffix);
+    }
+    let literal = if suffix == b"u" {
+        u64::from_str_radix(&digits, base).map(Literals::UnsignedIntLiteral).ok()
+    } else {
+        i64::from_str_radix(&digits, base).map(Literals::IntLiteral).ok()
+    };
+    match literal {
+        Some(literal) => Ok((TokenKind::Literal(literal), end)),
+        None => Err(invalid_number(src, start, end)),
+    }
+}
+
+/// Lex the tokens of an interpolation from `i`, just past its `${`, up to
+/// the balancing `}`, returning them with the offset after that `}`. The
+/// enclosing string starts at `start` and sits inside `depth - 1` other
+/// interpolations.
+fn interpolation(
+    src: &[u8],
+    mut i: usize,
+    start: usize,
+    depth: usize,
+    names: &mut Interner,
+) -> Result<(Vec<Token>, usize), LexError> {
+    let mut tokens = Vec::new();
+    let mut braces = 0;
+    loop {
+        i = skip_trivia(src, i)?;
+        if i == src.len() {
+            return Err(unterminated(src, start, src.len())
+                .with_context("unclosed `${` in string literal"));
+        }
+        let (kind, end) = lex_one(src, i, depth, names)?;
+        match kind {
+            TokenKind::Delimiter(Delimiters::RightBrace) if braces == 0 => return Ok((tokens, end)),
+            TokenKind::Delimiter(Delimiters::RightBrace) => braces -= 1,
+            TokenKind::Delimiter(Delimiters::LeftBrace) => braces += 1,
+            _ => {}
+        }
+        tokens.push(Token {
+            kind,
+            span: span(src, i, end),
+            lexeme: Lexeme::from(String::from_utf8_lossy(&src[i..end]).into_owned()),
+        });
+        i = end;
+    }
+}
+
+/// Finish the literal `start..end` whose digits, without separators, are
+/// `digits` in `base`, followed by the type `suffix`.
+fn typed(
+    src: &[u8],
+    start: usize,
+    end: usize,
+    digits: &str,
+    base: u32,
+    suffix: &[u8],
+) -> Result<(TokenKind, usize), LexError> {
+    let suffix = std::str::from_utf8(suffix).unwrap();
+    let value = match suffix {
+        "f32" => digits
+            .parse::<f32>()
+            .ok()
+            .filter(|v| v.is_finite())
+            .map(|v| NumericValue::Float(v.into())),
+        "f64" => digits.parse::<f64>().ok().filter(|v| v.is_finite()).map(NumericValue::Float),
+        _ => {
+            let bits: u32 = suffix[1..].parse().unwrap();
+            let v = i128::from_str_radix(digits, base).ok();
+            if suffix.starts_with('u') {
+                v.filter(|&v| v < 1 << bits).map(|v| NumericValue::Unsigned(v as u64))
+            } else {
+                let min = 1 << (bits - 1);
+                let negated = start > 0 && src[start - 1] == b'-';
+                match v {
+                    Some(v) if v < min => Some(NumericValue::Int(v as i64)),
+                    Some(v) if v == min && negated => Some(NumericValue::Magnitude(v as u64)),
+                    _ => None,
+                }
+            }
+        }
+    };
+    match value {
+        Some(value) => {
+            let suffix = NumericSuffix::ALL.into_iter().find(|s| s.as_str() == suffix).unwrap();
+            Ok((TokenKind::Literal(Literals::NumericLiteral { value, suffix }), end))
+        }
+        None => Err(invalid_number(src, start, end)
+            .with_context(format!("literal out of range for {suffix}"))),
+    }
+}
+
+/// Lex the single-quoted character whose opening quote is at `quote`,
+/// returning its value and the offset after the closing quote. Errors span
+/// from `start`, where the whole literal began.
+fn quoted_char(src: &[u8], quote: usize, start: usize) -> Result<(char, usize), LexError> {
+    let (ch, i) = match src.get(quote + 1) {
+        None => return Err(invalid_char(src, start, quote + 1, MissingClosingQuote)),
+        Some(b'\'') => return Err(invalid_char(src, start, quote + 2, Empty)),
+        Some(b'\\') => escape(src, quote + 1, b'\'', start)?,
+        Some(_) => utf8_char(src, quote + 1),
+    };
+    if src.get(i) != Some(&b'\'') {
+        let mut j = i;
+        while let Some(&b) = src.get(j) {
+            match b {
+                b'\'' => return Err(invalid_char(src, start, j + 1, TooManyCharacters)),
+                b'\n' => break,
+                b'\\' => j += 2,
+                _ => j += 1,
+            }
+        }
+        return Err(invalid_char(src, start, i, MissingClosingQuote));
+    }
+    Ok((ch, i + 1))
+}
+
+/// Lex one token starting at `start`, returning its kind and end offset.
+/// `depth` counts the interpolations the token is inside.
+fn lex_one(
+    src: &[u8],
+    start: usize,
+    depth: usize,
+    names: &mut Interner,
+) -> Result<(TokenKind, usize), LexError> {
+    let first = src[start];
+
+    if first == b'\'' {
+        let (ch, end) = quoted_char(src, start, start)?;
+        return Ok((TokenKind::Literal(Literals::CharacterLiteral(ch)), end));
+    }
+
+    if first == b'b' && src.get(start + 1) == Some(&b'\'') {
+        if src.get(start + 2).is_some_and(|b| !b.is_ascii()) {
+            let mut end = start + 2;
+            while src.get(end).is_some_and(|b| !b.is_ascii()) {
+                end += 1;
+            }
+            if src.get(end) == Some(&b'\'') {
+                end += 1;
+            }
+            return Err(invalid_char(src, start, end, NonAscii));
+        }
+        let (ch, end) = quoted_char(src, start + 1, start)?;
+        if !ch.is_ascii() {
+            return Err(invalid_char(src, start, end, NonAscii));
+        }
+        return Ok((TokenKind::Literal(Literals::ByteLiteral(ch as u8)), end));
+    }
+
+    if src[start..].starts_with(b"\"\"\"") {
+        let mut i = start + 3;
+        while !src.get(i..).unwrap_or_default().starts_with(b"\"\"\"") {
+            match src.get(i) {
+                None => return Err(unterminated(src, start, src.len())),
+                Some(b'\\') => i += 2,
+                Some(_) => i += 1,
+            }
+        }
+        let raw = String::from_utf8_lossy(&src[start + 3..i]).into_owned();
+        let literal = span(src, start, i + 3);
+        let mut value = String::new();
+        let mut chars = raw.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                value.push(c);
+                continue;
+            }
+            value.push(match chars.next() {
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some('r') => '\r',
+                Some('0') => '\0',
+                Some(c @ ('\\' | '"' | '$')) => c,
+                Some('u') => {
+                    let backslash = start + 3 + raw.len() - chars.as_str().len() - 2;
+                    let (ch, next) = unicode_escape(src, backslash, start, Some(literal))?;
+                    chars = raw[next - start - 3..].chars();
+                    ch
+                }
+                other => {
+                    return Err(LexError::InvalidEscape {
+                        sequence: match other {
+                            Some(c) => format!("\\{c}"),
+                            None => "\\(EOF)".to_string(),
+                        },
+                        span: literal,
+                        context: None,
+                    });
+                }
+            });
+        }
+        let kind = TokenKind::Literal(Literals::MultilineStringLiteral { raw, value });
+        return Ok((kind, i + 3));
+    }
+
+    if first == b'"' {
+        let mut decoded = String::new();
+        let mut parts = Vec::new();
+        let mut i = start + 1;
+        loop {
+            match src.get(i) {
+                None => return Err(unterminated(src, start, src.len())),
+                Some(b'"') => break,
+                Some(b'$') if src.get(i + 1) == Some(&b'{') => {
+                    if depth >= 64 {
+                        return Err(LexError::UnexpectedCharacter {
+                            ch: '$',
+                            span: span(src, i, i + 1),
+                            context: Some("string interpolation nested too deeply".into()),
+                        });
+                    }
+                    if !decoded.is_empty() {
+                        parts.push(StringPart::Text(std::mem::take(&mut decoded)));
+                    }
+                    let (expr, next) = interpolation(src, i + 2, start, depth + 1, names)?;
+                    parts.push(StringPart::Expr(expr));
+                    i = next;
+                }
+                Some(b'\n') => {
+                    return Err(unterminated(src, start, i).with_context(
+                        "newline in string literal; use a triple-quoted string to span lines",
+                    ));
+                }
+                Some(b'\\') => {
+                    let (ch, next) = escape(src, i, b'"', start)?;
+                    decoded.push(ch);
+                    i = next;
+                }
+                Some(_) => {
+                    let (ch, next) = utf8_char(src, i);
+                    decoded.push(ch);
+                    i = next;
+                }
+            }
+        }
+        if parts.is_empty() {
+            return Ok((TokenKind::Literal(Literals::StringLiteral(decoded)), i + 1));
+        }
+        if !decoded.is_empty() {
+            parts.push(StringPart::Text(decoded));
+        }
+        return Ok((TokenKind::Literal(Literals::InterpolatedString(parts)), i + 1));
+    }
+
+    if first.is_ascii_alphabetic() || first == b'_' {
+        let mut end = start;
+        while end < src.len() && is_ident_byte(src[end]) {
+            end += 1;
+        }
+        let text = std::str::from_utf8(&src[start..end]).unwrap();
+        let kind = match Keywords::ALL.into_iter().find(|kw| kw.as_str() == text) {
+            Some(kw) => TokenKind::Keyword(kw),
+            None if text == "true" || text == "false" => {
+                TokenKind::Literal(Literals::BoolLiteral(text == "true"))
+            }
+            None => TokenKind::Identifier(names.intern(text)),
+        };
+        return Ok((kind, end));
+    }
+
+    match (first, src.get(start + 1)) {
+        (b'0', Some(b'b')) => return radix(src, start, 2, "binary"),
+        (b'0', Some(b'o')) => return radix(src, start, 8, "octal"),
+        _ => {}
+    }
+
+    if first.is_ascii_digit() {
+        let digits = |mut i: usize| {
+            while i < src.len() && (src[i].is_ascii_digit() || src[i] == b'_') {
+                i += 1;
+            }
+            i
+        };
+        let mut end = digits(start);
+        separators(src, start, start, end)?;
+        let is_float = src.get(end) == Some(&b'.')
+            && src.get(end + 1).is_some_and(|b| b.is_ascii_digit());
+        if is_float {
+            let frac_start = end + 1;
+            end = digits(frac_start);
+            separators(src, start, frac_start, end)?;
+            if src.get(end) == Some(&b'.')
+                && src.get(end + 1).is_some_and(|b| b.is_ascii_digit())
+            {
+                while end < src.len()
+                    && (src[end].is_ascii_digit() || matches!(src[end], b'.' | b'_'))
+                {
+                    end += 1;
+                }
+                return Err(invalid_number(src, start, end)
+                    .with_context("a number can have only one decimal point"));
+            }
+        }
+        let mut suffix_end = end;
+        while suffix_end < src.len() && is_ident_byte(src[suffix_end]) {
+            suffix_end += 1;
+        }
+        let suffix = &src[end..suffix_end];
+        let allowed = if is_float { FLOAT_SUFFIXES } else { INT_SUFFIXES };
+        if !suffix.is_empty() && !allowed.iter().any(|s| s.as_bytes() == suffix) {
+            return Err(LexError::InvalidNumberSuffix {
+                suffix: String::from_utf8_lossy(suffix).into(),
+                span: span(src, start, suffix_end),
+                allowed: if is_float { NumberClass::Float } else { NumberClass::Int },
+                context: None,
+            });
+        }
+        let text = String::from_utf8_lossy(&src[start..end]).replace('_', "");
+        if suffix.len() > 1 {
+            return typed(src, start, suffix_end, &text, 10, suffix);
+        }
+        let is_unsigned = suffix == b"u";
+        let kind = if is_float {
+            text.parse().map(Literals::FloatLiteral).ok()
+        } else if is_unsigned {
+            text.parse().map(Literals::UnsignedIntLiteral).ok()
+        } else {
+            text.parse().map(Literals::IntLiteral).ok()
+        };
+        let end = end + usize::from(is_unsigned);
+        return match kind {
+            Some(literal) => Ok((TokenKind::Literal(literal), end)),
+            None => Err(invalid_number(src, start, end)),
+        };
+    }
+
+    for spelling in PUNCTUATION {
+        if src[start..].starts_with(spelling.as_bytes()) {
+            return Ok((punctuation_kind(spelling), start + spelling.len()));
+        }
+    }
+
+    let end = start + char_width(first);
+    let text = std::str::from_utf8(&src[start..end]).unwrap();
+    let ch = text.chars().next().unwrap();
+    let span = span(src, start, end);
+    let err = match LOOKALIKES.iter().find(|(lookalike, _, _)| *lookalike == ch) {
+        Some(&(ch, name, suggestion)) => LexError::LookalikeCharacter {
+            ch,
+            name,
+            suggestion,
+            span,
+            context: None,
+        },
+        None => LexError::UnexpectedCharacter {
+            ch,
+            span,
+            context: None,
+        },
+    };
+    Err(err)
+}
+
+/// Tokenize `source` the slow, obvious way. Mirrors [`hm_lexer::tokenize`],
+/// including the trailing `Eof` token and the interned identifier names.
+pub fn tokenize(source: &str) -> Result<(Vec<Token>, Interner), LexError> {
+    let src = source.as_bytes();
+
+    let mut names = Interner::new();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    loop {
+        i = skip_trivia(src, i)?;
+        if i == src.len() {
+            tokens.push(Token {
+                kind: TokenKind::Eof,
+                span: span(src, i, i),
+                lexeme: Lexeme::default(),
+            });
+            return Ok((tokens, names));
+        }
+
+        let (kind, end) = lex_one(src, i, 0, &mut names)?;
+        tokens.push(Token {
+            kind,
+            span: span(src, i, end),
+            lexeme: Lexeme::from(String::from_utf8_lossy(&src[i..end]).into_owned()),
+        });
+        i = end;
+    }
+}
@@ -0,0 +1,42 @@
+//! Rendering errors under the source line they point at.
+
+use hm_lexer::lexerror::{render, LexError};
+
+fn rendered(source: &str) -> String {
+    render(&hm_lexer::tokenize(source).unwrap_err(), source)
+}
+
+#[test]
+fn carets_line_up_after_tabs_and_wide_characters() {
+    let source = "\ts = \"é\" @;";
+    assert_eq!(
+        rendered(source),
+        concat!(
+            "error[L0001]: Unexpected character '@' at line 1, column 11\n",
+            "  |\n",
+            "1 | \ts = \"é\" @;\n",
+            "  | \t        ^\n",
+        )
+    );
+}
+
+#[test]
+fn an_invalid_suffix_is_underlined_on_its_own() {
+    let rendered = rendered("x = 10q;");
+    assert!(rendered.ends_with("1 | x = 10q;\n  |       ^\n"));
+}
+
+#[test]
+fn a_multiline_span_is_underlined_to_the_end_of_its_first_line() {
+    let source = "a\r\n/* open\r\nstill open";
+    assert!(rendered(source).ends_with("2 | /* open\n  | ^^^^^^^\n"));
+}
+
+#[test]
+fn errors_without_a_span_render_only_the_message() {
+    let err = LexError::EmptyInput;
+    assert_eq!(
+        render(&err, ""),
+        "error[L0007]: Cannot create CharStream from empty input\n"
+    );
+}
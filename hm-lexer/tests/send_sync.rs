@@ -0,0 +1,54 @@
+//! Lexer output can be moved across threads and shared between them.
+
+use std::sync::Arc;
+use std::thread;
+
+use hm_lexer::lexerror::LexError;
+use hm_lexer::shared::SharedTokens;
+use hm_lexer::source::{Source, SourceFile};
+use hm_lexer::token::span::Span;
+use hm_lexer::token::Token;
+use hm_lexer::tokenstream::TokenStream;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn lexer_output_is_send_and_sync() {
+    assert_send_sync::<Token>();
+    assert_send_sync::<Span>();
+    assert_send_sync::<LexError>();
+    assert_send_sync::<Source>();
+    assert_send_sync::<SourceFile>();
+    assert_send_sync::<SharedTokens>();
+    assert_send_sync::<TokenStream>();
+}
+
+#[test]
+fn threads_read_text_from_one_shared_source() {
+    let file = Arc::new(SourceFile::new("lib.hm", "func f(a: int32) { return a; }"));
+    let tokens = SharedTokens::lex(Arc::clone(&file)).unwrap();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let tokens = tokens.clone();
+            thread::spawn(move || tokens.iter().map(|(_, text)| text.len()).sum::<usize>())
+        })
+        .collect();
+    let expected: usize = tokens
+        .tokens()
+        .iter()
+        .map(|t| t.span.end - t.span.start)
+        .sum();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+
+    // The source itself was never copied: every clone points at `file`.
+    assert!(Arc::ptr_eq(tokens.file(), &file));
+    assert_eq!(Arc::strong_count(&file), 2);
+
+    // Nor was any of its text copied into the tokens
+    let (f, text) = tokens.iter().nth(1).unwrap();
+    assert!(f.lexeme.is_empty());
+    assert_eq!(text, "f");
+}
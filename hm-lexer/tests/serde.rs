@@ -0,0 +1,66 @@
+//! Tokens and errors survive a trip through serde (feature `serde`).
+#![cfg(feature = "serde")]
+
+use hm_lexer::lexerror::LexError;
+use hm_lexer::token::trivia::TriviaToken;
+use hm_lexer::token::Token;
+use hm_lexer::{lex_with_trivia, tokenize};
+
+#[test]
+fn tokens_round_trip() {
+    let source = "var s = \"a ${ b + 1.5f32 }\"; x **= 0o17u8; // done\n";
+    let (tokens, _) = tokenize(source).unwrap();
+    let json = serde_json::to_string(&tokens).unwrap();
+    let decoded: Vec<Token> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, tokens);
+
+    let (trivia, _) = lex_with_trivia(source).unwrap();
+    let json = serde_json::to_string(&trivia).unwrap();
+    assert_eq!(serde_json::from_str::<Vec<TriviaToken>>(&json).unwrap(), trivia);
+}
+
+#[test]
+fn errors_round_trip() {
+    for source in [
+        "x = 10q;",
+        "x = 1.5u8;",
+        "s = \u{201C}hi\u{201D};",
+        "s = \"open",
+    ] {
+        let err = tokenize(source).unwrap_err();
+        let json = serde_json::to_string(&err).unwrap();
+        let decoded: LexError = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.to_string(), err.to_string());
+    }
+}
+
+#[test]
+fn unknown_static_names_are_rejected() {
+    let err = tokenize("x = 10q;").unwrap_err();
+    let json = serde_json::to_string(&err)
+        .unwrap()
+        .replace("\"Int\"", "\"Decimal\"");
+    assert!(serde_json::from_str::<LexError>(&json).is_err());
+}
+
+#[test]
+fn versioned_tokens_reject_other_versions() {
+    use hm_lexer::codec::{VersionedTokens, SERDE_FORMAT_VERSION};
+
+    let (tokens, names) = tokenize("var x = 42;").unwrap();
+    let json = serde_json::to_string(&VersionedTokens::new(tokens.clone(), names.clone())).unwrap();
+    let cached: VersionedTokens = serde_json::from_str(&json).unwrap();
+    assert_eq!(cached.tokens(), tokens);
+    assert_eq!(*cached.names(), names);
+
+    let older_format = json.replace(
+        &format!("\"format_version\":{SERDE_FORMAT_VERSION}"),
+        "\"format_version\":0",
+    );
+    let err = serde_json::from_str::<VersionedTokens>(&older_format).unwrap_err();
+    assert!(err.to_string().contains("format version 0"), "{err}");
+
+    let other_lexer = json.replace(hm_lexer::lexer_version(), "0.0.1+0");
+    let err = serde_json::from_str::<VersionedTokens>(&other_lexer).unwrap_err();
+    assert!(err.to_string().contains("lexer 0.0.1+0"), "{err}");
+}
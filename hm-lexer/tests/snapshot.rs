@@ -0,0 +1,110 @@
+//! Round-trip and error tests for the `.tokens` snapshot format.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::LexerConfig;
+use hm_lexer::intern::Interner;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::snapshot::{self, SnapshotError};
+use hm_lexer::token::Token;
+
+const SOURCE: &str = "import std::io;\n\
+    func f(n: u64): u64 { return n ** 2u >> 1 | 007; }\n\
+    var s = \"tab\\there \\\"q\\\"\"; var c = '\\n'; var b = b'~';\n\
+    var m = \"\"\"\n  two\n  lines\"\"\"; var pi = 3.25;\n\
+    var t = 100u32 + 0b1i8 + 0.5f32; var ok = true || false;\n\
+    var bad = 10q; @ 'é' // trailing comment\n";
+
+fn lex(config: LexerConfig) -> (Vec<Token>, Interner) {
+    let mut lexer = Lexer::with_config(CharStream::from_bytes(SOURCE.as_bytes()).unwrap(), config);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token().unwrap();
+        let is_eof = token.is_eof();
+        tokens.push(token);
+        if is_eof {
+            return (tokens, lexer.into_interner());
+        }
+    }
+}
+
+fn debug(tokens: &[Token]) -> Vec<String> {
+    tokens.iter().map(|t| format!("{t:?}")).collect()
+}
+
+#[test]
+fn round_trips_every_token_family() {
+    for char_offsets in [false, true] {
+        let (tokens, names) = lex(LexerConfig {
+            error_recovery: true,
+            char_offsets,
+            ..LexerConfig::default()
+        });
+        let text = snapshot::serialize(&tokens, &names);
+        let (parsed, parsed_names) = snapshot::parse(&text).unwrap();
+        assert_eq!(debug(&parsed), debug(&tokens));
+        assert_eq!(parsed_names, names);
+        assert_eq!(snapshot::serialize(&parsed, &parsed_names), text);
+    }
+}
+
+#[test]
+fn ignores_comments_and_blank_lines() {
+    let text = format!(
+        "{}\n\
+         # reported in a bug: `x` lexed at the wrong column\n\
+         \n\
+         1:1-1:2  0..1  c0..1  identifier \"x\"\n\
+         1:2-1:2  1..1  c1..1  eof\n",
+        snapshot::header()
+    );
+    let (tokens, names) = snapshot::parse(&text).unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(names.get("x").map(|x| names.resolve(x)), Some("x"));
+    assert_eq!(tokens[0].lexeme, "x");
+    assert_eq!(tokens[0].span.char_offsets, Some((0, 1)));
+}
+
+#[test]
+fn reports_line_of_bad_input() {
+    let err = snapshot::parse("not a snapshot").unwrap_err();
+    assert!(matches!(err, SnapshotError::MissingHeader));
+
+    let parse = |body: &str| snapshot::parse(&format!("{}\n{body}", snapshot::header()));
+
+    let err = parse("1:1-1:3 0..2 operator <>\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unknown spelling '<>' at line 2 of token snapshot"
+    );
+
+    let err = parse("1:1-1:3 0..2 int 42\n").unwrap_err();
+    assert!(matches!(
+        err,
+        SnapshotError::Malformed {
+            line: 2,
+            expected: "a quoted lexeme"
+        }
+    ));
+
+    let err = parse("1:1-1:3 0..2 string \"open\n").unwrap_err();
+    assert!(matches!(err, SnapshotError::Malformed { line: 2, .. }));
+}
+
+#[test]
+fn rejects_other_format_and_lexer_versions() {
+    let body = "1:1-1:1  0..0  eof\n";
+
+    let err = snapshot::parse(&format!("# hm-lexer tokens v1\n{body}")).unwrap_err();
+    assert!(matches!(
+        err,
+        SnapshotError::UnsupportedVersion { found: 1, .. }
+    ));
+
+    let err = snapshot::parse(&format!("# hm-lexer tokens v2 lexer 0.0.1+0\n{body}")).unwrap_err();
+    assert!(
+        matches!(err, SnapshotError::LexerVersionMismatch { ref found, .. } if found == "0.0.1+0")
+    );
+
+    let current = format!("{}\n{body}", snapshot::header());
+    assert_eq!(snapshot::parse(&current).unwrap().0.len(), 1);
+}
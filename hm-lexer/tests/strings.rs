@@ -0,0 +1,220 @@
+//! Line handling in string literals (plain strings stop at a raw newline,
+//! triple-quoted strings span lines), `${...}` interpolation, `\u{...}`
+//! escapes, and UTF-8 content.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::LexError;
+use hm_lexer::token::literals::{Literals, StringPart};
+use hm_lexer::token::tokenkind::TokenKind;
+use hm_lexer::token::Token;
+use hm_lexer::{codec, snapshot};
+
+#[test]
+fn raw_newline_ends_a_plain_string_with_an_error() {
+    let err = hm_lexer::tokenize("var s = \"open\nclose\";").unwrap_err();
+    let LexError::UnterminatedString { span, context } = err else {
+        panic!("expected an unterminated string");
+    };
+    assert_eq!((span.line_start, span.column_start), (1, 9));
+    assert_eq!((span.start, span.end), (8, 13));
+    assert_eq!(
+        context.as_deref(),
+        Some("newline in string literal; use a triple-quoted string to span lines")
+    );
+}
+
+#[test]
+fn lexing_resumes_on_the_next_line() {
+    let mut lexer = Lexer::new(CharStream::from_bytes(b"s = \"open\nx;").unwrap());
+    assert_eq!(lexer.next_token().unwrap().lexeme, "s");
+    assert_eq!(lexer.next_token().unwrap().lexeme, "=");
+    assert!(lexer.next_token().is_err());
+    let x = lexer.next_token().unwrap();
+    assert_eq!(x.lexeme, "x");
+    assert_eq!((x.span.line_start, x.span.column_start), (2, 1));
+}
+
+#[test]
+fn escaped_newline_is_allowed_in_a_plain_string() {
+    let (tokens, _) = hm_lexer::tokenize("\"a\\nb\"").unwrap();
+    let TokenKind::Literal(Literals::StringLiteral(value)) = &tokens[0].kind else {
+        panic!("expected a string literal");
+    };
+    assert_eq!(value, "a\nb");
+}
+
+#[test]
+fn triple_quoted_string_spans_lines() {
+    let (tokens, _) = hm_lexer::tokenize("s = \"\"\"one\ntwo\n  three\"\"\";").unwrap();
+    let string = &tokens[2];
+    let TokenKind::Literal(Literals::MultilineStringLiteral { raw, .. }) = &string.kind else {
+        panic!("expected a multiline string literal");
+    };
+    assert_eq!(raw, "one\ntwo\n  three");
+    let span = string.span;
+    assert_eq!((span.start, span.end), (4, 25));
+    assert_eq!((span.line_start, span.column_start), (1, 5));
+    assert_eq!((span.line_end, span.column_end), (3, 11));
+    assert_eq!(tokens[3].span.line_start, 3);
+}
+
+fn parts(token: &Token) -> &[StringPart] {
+    let TokenKind::Literal(Literals::InterpolatedString(parts)) = &token.kind else {
+        panic!("expected an interpolated string, got {:?}", token.kind);
+    };
+    parts
+}
+
+#[test]
+fn interpolations_nest_and_balance_braces() {
+    let source = r#"s = "a ${ f({ x: "b ${y}" }) } c";"#;
+    let (tokens, _) = hm_lexer::tokenize(source).unwrap();
+    assert_eq!(tokens.len(), 5);
+    let outer = parts(&tokens[2]);
+    assert_eq!(outer.len(), 3);
+    let StringPart::Expr(expr) = &outer[1] else {
+        panic!("expected an expression");
+    };
+    let lexemes: Vec<_> = expr.iter().map(|t| &*t.lexeme).collect();
+    assert_eq!(lexemes, ["f", "(", "{", "x", ":", r#""b ${y}""#, "}", ")"]);
+    let inner = parts(&expr[5]);
+    let StringPart::Expr(y) = &inner[1] else {
+        panic!("expected an expression");
+    };
+    assert_eq!(&source[y[0].span.start..y[0].span.end], "y");
+}
+
+#[test]
+fn escaped_dollar_does_not_interpolate() {
+    let (tokens, _) = hm_lexer::tokenize(r#""\${x} costs $5""#).unwrap();
+    let TokenKind::Literal(Literals::StringLiteral(value)) = &tokens[0].kind else {
+        panic!("expected a plain string");
+    };
+    assert_eq!(value, "${x} costs $5");
+}
+
+#[test]
+fn unclosed_interpolation_is_an_unterminated_string() {
+    let err = hm_lexer::tokenize("x = \"a ${ b").unwrap_err();
+    let LexError::UnterminatedString { span, context } = err else {
+        panic!("expected an unterminated string");
+    };
+    assert_eq!((span.line_start, span.column_start), (1, 5));
+    assert_eq!((span.start, span.end), (4, 11));
+    assert_eq!(context.as_deref(), Some("unclosed `${` in string literal"));
+}
+
+#[test]
+fn deep_nesting_is_rejected() {
+    let source = "\"${".repeat(100);
+    let err = hm_lexer::tokenize(&source).unwrap_err();
+    let LexError::UnexpectedCharacter { ch, span, .. } = err else {
+        panic!("expected an unexpected character");
+    };
+    assert_eq!((ch, span.column_start), ('$', 64 * 3 + 2));
+    assert_eq!(span.end - span.start, 1);
+}
+
+#[test]
+fn interpolated_strings_round_trip() {
+    let source = "greet(\"hi ${name}, ${ \"n=${n + 1}\" }!\");";
+    let (tokens, names) = hm_lexer::tokenize(source).unwrap();
+    let debug = |tokens: &[Token]| format!("{tokens:?}");
+
+    let (decoded, _) = codec::decode(&codec::encode(&tokens, &names)).unwrap();
+    assert_eq!(debug(&decoded), debug(&tokens));
+
+    let text = snapshot::serialize(&tokens, &names);
+    assert_eq!(debug(&snapshot::parse(&text).unwrap().0), debug(&tokens));
+}
+
+#[test]
+fn unicode_escapes_decode_in_strings_and_chars() {
+    let (tokens, _) =
+        hm_lexer::tokenize(r#""\u{1F600}\u{e9}!" '\u{41}' """\u{10FFFF}""""#).unwrap();
+    let TokenKind::Literal(Literals::StringLiteral(value)) = &tokens[0].kind else {
+        panic!("expected a string literal");
+    };
+    assert_eq!(value, "\u{1F600}\u{e9}!");
+    assert!(matches!(
+        tokens[1].kind,
+        TokenKind::Literal(Literals::CharacterLiteral('A'))
+    ));
+    let TokenKind::Literal(Literals::MultilineStringLiteral { value, .. }) = &tokens[2].kind else {
+        panic!("expected a multiline string literal");
+    };
+    assert_eq!(value, "\u{10FFFF}");
+}
+
+#[test]
+fn invalid_unicode_escapes_report_their_digits() {
+    for (source, expected_sequence, expected_context) in [
+        (
+            r#""\u{D800}""#,
+            r"\u{D800}",
+            "`D800` is not a Unicode scalar value",
+        ),
+        (
+            r#""\u{110000}""#,
+            r"\u{110000}",
+            "`110000` is not a Unicode scalar value",
+        ),
+        (r#""\u{}""#, r"\u{}", "empty unicode escape"),
+        (
+            r#""\u{1234567}""#,
+            r"\u{1234567}",
+            "unicode escape must have at most 6 hex digits",
+        ),
+        (r#""\u{12g}""#, r"\u{12g", "`g` is not a hex digit"),
+        (
+            r#""\u{41""#,
+            r"\u{41",
+            "unterminated unicode escape; expected `}`",
+        ),
+        (r#""\u41""#, r"\u", "expected `{` after `\\u`"),
+    ] {
+        let err = hm_lexer::tokenize(source).unwrap_err();
+        let LexError::InvalidEscape {
+            sequence, context, ..
+        } = err
+        else {
+            panic!("expected an invalid escape for {source}");
+        };
+        assert_eq!(sequence, expected_sequence, "{source}");
+        assert_eq!(context.as_deref(), Some(expected_context), "{source}");
+    }
+}
+
+#[test]
+fn non_ascii_text_survives_in_literals() {
+    let (tokens, _) = hm_lexer::tokenize("s = \"caf\u{e9} \u{1F600}\"; c = '\u{e9}';").unwrap();
+    let TokenKind::Literal(Literals::StringLiteral(value)) = &tokens[2].kind else {
+        panic!("expected a string literal");
+    };
+    assert_eq!(value, "caf\u{e9} \u{1F600}");
+    assert!(matches!(
+        tokens[6].kind,
+        TokenKind::Literal(Literals::CharacterLiteral('\u{e9}'))
+    ));
+}
+
+#[test]
+fn invalid_utf8_in_literals_is_reported() {
+    for (source, literal) in [
+        (&b"x = \"ab\xffc\";"[..], "string"),
+        (b"x = \"\xe9t\xe9\";", "string"),
+        (b"x = '\xc3';", "character"),
+        (b"x = \"\"\"a\xf0\x9f\"\"\";", "string"),
+    ] {
+        let mut lexer = Lexer::new(CharStream::from_bytes(source).unwrap());
+        lexer.next_token().unwrap();
+        lexer.next_token().unwrap();
+        let LexError::InvalidUtf8 { span, context } = lexer.next_token().unwrap_err() else {
+            panic!("expected invalid UTF-8 in {source:?}");
+        };
+        assert_eq!((span.line_start, span.column_start), (1, 5));
+        let expected = format!("invalid UTF-8 in {literal} literal");
+        assert_eq!(context.as_deref(), Some(expected.as_str()));
+    }
+}
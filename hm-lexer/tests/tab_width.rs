@@ -0,0 +1,63 @@
+//! Column tracking with tab stops.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::LexerConfig;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::render;
+use hm_lexer::lineindex::LineIndex;
+use hm_lexer::tokenstream::TokenStream;
+
+const SOURCE: &str = "func f() {\n\tif x {\n\t\treturn a;\t// done\n  \t}\n}";
+
+fn lexer(source: &str, offsets_only: bool) -> Lexer {
+    let config = LexerConfig {
+        tab_width: 4,
+        offsets_only,
+        ..LexerConfig::default()
+    };
+    Lexer::with_config(CharStream::from_bytes(source.as_bytes()).unwrap(), config)
+}
+
+#[test]
+fn tabs_advance_to_the_next_tab_stop() {
+    let tokens = TokenStream::from_lexer(lexer(SOURCE, false)).unwrap();
+    tokens.validate(SOURCE).unwrap();
+
+    let columns: Vec<_> = (5..12)
+        .map(|i| {
+            (
+                tokens.peek(i).lexeme.to_string(),
+                tokens.peek(i).span.column_start,
+            )
+        })
+        .collect();
+    let expected = [
+        ("if", 5),
+        ("x", 8),
+        ("{", 10),
+        ("return", 9),
+        ("a", 16),
+        (";", 17),
+        ("}", 5),
+    ];
+    let expected: Vec<_> = expected.iter().map(|&(s, c)| (s.to_string(), c)).collect();
+    assert_eq!(columns, expected);
+}
+
+#[test]
+fn line_index_agrees_with_the_lexer() {
+    let tracked: Vec<_> = lexer(SOURCE, false).map(|t| t.unwrap().span).collect();
+    let index = LineIndex::new(SOURCE).with_tab_width(4);
+    let resolved: Vec<_> = lexer(SOURCE, true)
+        .map(|t| index.resolve(t.unwrap().span))
+        .collect();
+    assert_eq!(resolved, tracked);
+}
+
+#[test]
+fn rendered_carets_still_line_up_under_tabs() {
+    let source = "\tvar s = \"open";
+    let err = lexer(source, false).find_map(Result::err).unwrap();
+    assert_eq!(err.column(), Some(13));
+    assert!(render(&err, source).ends_with("1 | \tvar s = \"open\n  | \t        ^^^^^\n"));
+}
@@ -0,0 +1,58 @@
+//! The generated TextMate grammar highlights what the lexer lexes.
+
+use hm_lexer::grammar;
+use hm_lexer::textmate::{scope, tm_language_json};
+
+/// The spellings an alternation rule such as `(?:<<|\|\|)` matches, in
+/// the order the regex tries them.
+fn alternatives(pattern: &str) -> Vec<String> {
+    let body = pattern
+        .strip_prefix("(?:")
+        .and_then(|rest| rest.strip_suffix(')'))
+        .unwrap_or_else(|| panic!("not an alternation: {pattern}"));
+    let mut out = vec![String::new()];
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.last_mut().unwrap().push(chars.next().unwrap()),
+            '|' => out.push(String::new()),
+            _ => out.last_mut().unwrap().push(c),
+        }
+    }
+    out
+}
+
+#[test]
+fn every_multi_char_operator_is_matched_whole() {
+    let json: serde_json::Value = serde_json::from_str(&tm_language_json()).unwrap();
+    let rules: Vec<(String, Vec<String>)> = json["repository"]["punctuation"]["patterns"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|rule| {
+            let name = rule["name"].as_str().unwrap().to_string();
+            (name, alternatives(rule["match"].as_str().unwrap()))
+        })
+        .collect();
+
+    let multi_char: Vec<_> = grammar::punctuators()
+        .into_iter()
+        .filter(|(spelling, _)| spelling.len() > 1)
+        .collect();
+    assert!(multi_char.iter().any(|(spelling, _)| *spelling == "<<"));
+    for (spelling, kind) in multi_char {
+        // TextMate takes the first rule, and the first alternative within
+        // it, that matches at the cursor.
+        let (name, matched) = rules
+            .iter()
+            .find_map(|(name, alternatives)| {
+                let matched = alternatives
+                    .iter()
+                    .find(|alt| spelling.starts_with(alt.as_str()))?;
+                Some((name, matched))
+            })
+            .unwrap_or_else(|| panic!("no rule matches {spelling}"));
+        assert_eq!(matched, spelling);
+        assert_eq!(name, scope(&kind), "{spelling}");
+    }
+}
@@ -0,0 +1,140 @@
+//! The lazy [`Lexer`] and the eager [`TokenStream`] behave identically when
+//! driven through [`TokenSource`], and lexer output passes
+//! [`TokenStream::validate`].
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::{ColumnUnit, LexerConfig};
+use hm_lexer::intern::Interner;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::token::Token;
+use hm_lexer::tokensource::TokenSource;
+use hm_lexer::tokenstream::{StreamViolation, TokenStream};
+
+const SOURCE: &[u8] = b"func f(a: int32): int32 { return a * 2; }";
+
+/// Mix lookahead and bumps the way a parser would, recording each lexeme seen.
+fn drive(source: &mut impl TokenSource) -> Vec<String> {
+    let mut seen = Vec::new();
+    loop {
+        let ahead = source.lookahead(2).unwrap().lexeme.to_string();
+        let current = source.current().unwrap().lexeme.to_string();
+        let bumped = source.bump().unwrap();
+        assert_eq!(bumped.lexeme, current);
+        seen.push(format!("{current}|{ahead}"));
+        if bumped.is_eof() {
+            break;
+        }
+    }
+    seen
+}
+
+#[test]
+fn lexer_and_token_stream_agree() {
+    let mut lexer = Lexer::new(CharStream::from_bytes(SOURCE).unwrap());
+    let mut stream =
+        TokenStream::from_lexer(Lexer::new(CharStream::from_bytes(SOURCE).unwrap())).unwrap();
+    assert_eq!(drive(&mut lexer), drive(&mut stream));
+}
+
+#[test]
+fn lookahead_does_not_consume() {
+    let mut lexer = Lexer::new(CharStream::from_bytes(b"a b c").unwrap());
+    assert_eq!(lexer.lookahead(1).unwrap().lexeme, "b");
+    assert_eq!(lexer.next_token().unwrap().lexeme, "a");
+    assert_eq!(lexer.next_token().unwrap().lexeme, "b");
+    assert!(lexer.lookahead(5).unwrap().is_eof());
+    assert_eq!(lexer.bump().unwrap().lexeme, "c");
+    assert!(lexer.bump().unwrap().is_eof());
+    assert!(lexer.bump().unwrap().is_eof());
+}
+
+#[test]
+fn unexpected_end_of_input() {
+    let mut stream = TokenStream::from_source("x").unwrap();
+    stream.bump().unwrap();
+    let err = stream.unexpected(&["`;`", "`=`", "`(`"]).unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Expected `;`, `=` or `(`, found end of input at line 1, column 2"
+    );
+}
+
+#[test]
+fn lexer_output_validates_in_every_column_unit() {
+    let source = "var s = \"é🙂\"; // ünïcode\n/* two\nlines */ x\t+= 'q';";
+    for unit in [ColumnUnit::Bytes, ColumnUnit::Chars, ColumnUnit::Utf16] {
+        let config = LexerConfig {
+            column_unit: unit,
+            char_offsets: true,
+            ..LexerConfig::default()
+        };
+        let lexer = Lexer::with_config(CharStream::from_bytes(source.as_bytes()).unwrap(), config);
+        let stream = TokenStream::from_lexer(lexer).unwrap();
+        if let Err(violation) = stream.validate(source) {
+            panic!("{violation}");
+        }
+    }
+}
+
+#[test]
+fn validate_reports_wrong_columns() {
+    let source = "a\nb";
+    let mut names = Interner::new();
+    let a = Token::identifier_builder("a", &mut names).build();
+    let b = Token::identifier_builder("b", &mut names)
+        .at(2, 1, 3)
+        .build();
+    let err = TokenStream::new(vec![a, b]).validate(source).unwrap_err();
+    assert!(matches!(
+        err,
+        StreamViolation::Position {
+            index: 1,
+            offset: 2,
+            expected: (2, 1),
+            found: (1, 3)
+        }
+    ));
+}
+
+#[test]
+fn restore_replays_the_same_tokens() {
+    let config = LexerConfig {
+        char_offsets: true,
+        ..LexerConfig::default()
+    };
+    let source = "var s = \"é\"; // ü\nfunc f(a: int32) { return a; }";
+    let stream = CharStream::from_bytes(source.as_bytes()).unwrap();
+    let mut lexer = Lexer::with_config(stream, config);
+
+    lexer.next_token().unwrap();
+    lexer.lookahead(3).unwrap();
+    let early = lexer.save();
+    let first: Vec<Token> = (0..6).map(|_| lexer.next_token().unwrap()).collect();
+    let late = lexer.save();
+    let next = lexer.next_token().unwrap();
+
+    lexer.restore(early);
+    let again: Vec<Token> = (0..6).map(|_| lexer.next_token().unwrap()).collect();
+    assert_eq!(format!("{first:?}"), format!("{again:?}"));
+
+    // Restoring forward works as well as restoring backward.
+    lexer.restore(late);
+    assert_eq!(
+        format!("{next:?}"),
+        format!("{:?}", lexer.next_token().unwrap())
+    );
+}
+
+#[test]
+fn peeked_tokens_keep_their_spans() {
+    let mut lexer = Lexer::new(CharStream::from_bytes(b"a\n  bb @").unwrap());
+    let peeked = lexer.peek_nth(1).unwrap().span;
+    assert_eq!((peeked.start, peeked.end, peeked.line_start), (4, 6, 2));
+    assert!(lexer.peek_nth(2).is_err());
+
+    assert_eq!(lexer.bump().unwrap().lexeme, "a");
+    assert_eq!(lexer.peek_token().unwrap().span, peeked);
+    let bumped = lexer.bump().unwrap();
+    assert_eq!((bumped.lexeme.as_ref(), bumped.span), ("bb", peeked));
+    assert!(lexer.peek_token().is_err());
+}
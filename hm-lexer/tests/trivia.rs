@@ -0,0 +1,249 @@
+//! Lossless lexing: tokens with their leading and trailing trivia, nested
+//! and unterminated block comments, and shebang lines.
+
+use hm_lexer::charstream::CharStream;
+use hm_lexer::config::{ColumnUnit, LexerConfig, LineEndings};
+use hm_lexer::highlight::{self, HighlightClass};
+use hm_lexer::lex_with_trivia;
+use hm_lexer::lexer::Lexer;
+use hm_lexer::lexerror::{ErrorCode, LexError};
+use hm_lexer::token::tokenkind::TokenKind;
+use hm_lexer::token::trivia::{reconstruct, Trivia, TriviaKind, TriviaToken};
+use hm_lexer::tokensource::TokenSource;
+
+const SOURCES: &[&str] = &[
+    "var x = 1;",
+    "  \t\n// header\r\n\r\nfunc f() { /* body */ return 1; }  // end\n\n",
+    "a/* one */b /* two\nlines */ c\r\n",
+    "\"${ x /* in */ }\" // trailing without newline",
+    "x // only a comment before eof",
+    "\n\n\n",
+];
+
+fn kinds(pieces: &[Trivia]) -> Vec<TriviaKind> {
+    pieces.iter().map(|t| t.kind).collect()
+}
+
+#[test]
+fn trivia_reconstructs_the_source() {
+    for source in SOURCES {
+        let (tokens, _) = lex_with_trivia(source).unwrap();
+        assert_eq!(reconstruct(&tokens), *source);
+        assert!(tokens.last().unwrap().token.is_eof());
+    }
+}
+
+#[test]
+fn trivia_spans_cover_the_gaps_between_tokens() {
+    for source in SOURCES {
+        let (tokens, _) = lex_with_trivia(source).unwrap();
+        let mut offset = 0;
+        for TriviaToken {
+            leading,
+            token,
+            trailing,
+        } in &tokens
+        {
+            for trivia in leading {
+                assert_eq!(trivia.span.start, offset);
+                assert_eq!(&source[trivia.span.start..trivia.span.end], trivia.text);
+                offset = trivia.span.end;
+            }
+            assert_eq!(token.span.start, offset);
+            offset = token.span.end;
+            for trivia in trailing {
+                assert_eq!(trivia.span.start, offset);
+                assert_eq!(&source[trivia.span.start..trivia.span.end], trivia.text);
+                offset = trivia.span.end;
+            }
+        }
+        assert_eq!(offset, source.len());
+    }
+}
+
+#[test]
+fn trailing_trivia_stops_after_the_first_newline() {
+    let (tokens, _) = lex_with_trivia("a; // note\r\n\n  // next\nb").unwrap();
+    let semicolon = &tokens[1];
+    assert_eq!(
+        kinds(&semicolon.trailing),
+        [
+            TriviaKind::Whitespace,
+            TriviaKind::LineComment,
+            TriviaKind::Newline
+        ]
+    );
+    assert_eq!(semicolon.trailing[1].text, "// note");
+    assert_eq!(semicolon.trailing[2].text, "\r\n");
+
+    let b = &tokens[2];
+    assert_eq!(
+        kinds(&b.leading),
+        [
+            TriviaKind::Newline,
+            TriviaKind::Whitespace,
+            TriviaKind::LineComment,
+            TriviaKind::Newline
+        ]
+    );
+    let comment = &b.leading[2];
+    assert_eq!((comment.span.line_start, comment.span.column_start), (3, 3));
+    assert!(b.trailing.is_empty());
+}
+
+#[test]
+fn block_comments_and_eof_trivia() {
+    let (tokens, _) = lex_with_trivia("x /* a\nb */ y /* end */\n").unwrap();
+    assert_eq!(
+        kinds(&tokens[0].trailing),
+        [
+            TriviaKind::Whitespace,
+            TriviaKind::BlockComment,
+            TriviaKind::Whitespace
+        ]
+    );
+    assert_eq!(tokens[0].trailing[1].span.line_end, 2);
+    assert_eq!(
+        kinds(&tokens[1].trailing),
+        [
+            TriviaKind::Whitespace,
+            TriviaKind::BlockComment,
+            TriviaKind::Newline
+        ]
+    );
+    assert!(tokens[2].token.is_eof());
+    assert!(tokens[2].leading.is_empty());
+}
+
+#[test]
+fn lookahead_is_relexed_with_its_trivia() {
+    let mut lexer = Lexer::new(CharStream::from_bytes(b"a\n /* c */ b").unwrap());
+    assert_eq!(lexer.next_token_with_trivia().unwrap().token.lexeme, "a");
+    lexer.lookahead(1).unwrap();
+    let b = lexer.next_token_with_trivia().unwrap();
+    assert_eq!(b.token.lexeme, "b");
+    assert_eq!(
+        kinds(&b.leading),
+        [
+            TriviaKind::Whitespace,
+            TriviaKind::BlockComment,
+            TriviaKind::Whitespace
+        ]
+    );
+}
+
+#[test]
+fn block_comments_nest() {
+    let source = "a /* x /* y */ z */ b /* /* */ */";
+    let lexemes: Vec<_> = hm_lexer::tokenize(source)
+        .unwrap()
+        .0
+        .into_iter()
+        .map(|t| t.lexeme)
+        .collect();
+    assert_eq!(lexemes, ["a", "b", ""]);
+
+    let (tokens, _) = lex_with_trivia(source).unwrap();
+    assert_eq!(tokens[0].trailing[1].text, "/* x /* y */ z */");
+    assert_eq!(tokens[1].trailing[1].text, "/* /* */ */");
+
+    let comments: Vec<_> = highlight::spans(source)
+        .into_iter()
+        .filter(|(_, class)| *class == HighlightClass::Comment)
+        .map(|(range, _)| range)
+        .collect();
+    assert_eq!(comments, [2..19, 22..33]);
+}
+
+#[test]
+fn leading_shebang_is_trivia() {
+    let source = "#!/usr/bin/env hm\nvar x = 1;";
+    let lexemes: Vec<_> = hm_lexer::tokenize(source)
+        .unwrap()
+        .0
+        .into_iter()
+        .map(|t| t.lexeme)
+        .collect();
+    assert_eq!(lexemes, ["var", "x", "=", "1", ";", ""]);
+
+    let (tokens, _) = lex_with_trivia(source).unwrap();
+    assert_eq!(
+        kinds(&tokens[0].leading),
+        [TriviaKind::Shebang, TriviaKind::Newline]
+    );
+    assert_eq!(tokens[0].leading[0].text, "#!/usr/bin/env hm");
+    assert_eq!(reconstruct(&tokens), source);
+
+    let comment = highlight::spans(source)
+        .into_iter()
+        .find(|(_, class)| *class == HighlightClass::Comment);
+    assert!(comment == Some((0..17, HighlightClass::Comment)));
+}
+
+#[test]
+fn shebang_is_only_trivia_at_the_start() {
+    assert!(hm_lexer::tokenize(" #!/usr/bin/env hm").is_err());
+    assert!(hm_lexer::tokenize("x\n#!/usr/bin/env hm").is_err());
+}
+
+#[test]
+fn unterminated_block_comment_is_reported() {
+    let source = "x = 1;\n  /* a /* b */ never closed\n";
+    let err = hm_lexer::tokenize(source).unwrap_err();
+    let LexError::UnterminatedBlockComment { span, .. } = err else {
+        panic!("expected an unterminated block comment, got {err:?}");
+    };
+    assert_eq!((span.line_start, span.column_start), (2, 3));
+    assert_eq!((span.start, span.end), (9, source.len()));
+    assert_eq!(err.code().as_str(), "L0011");
+
+    let mut lexer = Lexer::new(CharStream::from_bytes(b"a /* open").unwrap());
+    let a = lexer.next_token_with_trivia().unwrap();
+    assert_eq!(kinds(&a.trailing), [TriviaKind::Whitespace]);
+    assert!(lexer.next_token_with_trivia().is_err());
+    assert!(lexer.next_token().unwrap().is_eof());
+
+    let config = LexerConfig {
+        error_recovery: true,
+        ..LexerConfig::default()
+    };
+    let mut lexer = Lexer::with_config(CharStream::from_bytes(b"a /* open").unwrap(), config);
+    lexer.next_token().unwrap();
+    let error = lexer.next_token().unwrap();
+    assert!(matches!(
+        error.kind,
+        TokenKind::Error {
+            message_code: ErrorCode::UnterminatedBlockComment
+        }
+    ));
+    assert_eq!(error.lexeme, "/* open");
+    assert!(lexer.next_token().unwrap().is_eof());
+}
+
+#[test]
+fn bulk_skipping_tracks_positions_like_stepping() {
+    let source = "\t/* é\r\n\t🙂 /* */ */\t x // ü\r\n  y\r\r\n/*\n*/";
+    let configs = [
+        (ColumnUnit::Bytes, LineEndings::Lf, 0),
+        (ColumnUnit::Chars, LineEndings::Crlf, 4),
+        (ColumnUnit::Utf16, LineEndings::Normalize, 8),
+    ];
+    for (unit, line_endings, tab_width) in configs {
+        let stream = || {
+            let mut stream = CharStream::from_bytes(source.as_bytes()).unwrap();
+            stream.set_column_unit(unit);
+            stream.set_line_endings(line_endings);
+            stream.set_tab_width(tab_width);
+            stream.set_char_tracking(true);
+            stream
+        };
+        for end in 0..=source.len() {
+            let mut bulk = stream();
+            bulk.advance_to(end);
+            let mut stepped = stream();
+            stepped.advance_n(end);
+            assert_eq!(bulk.current_position(), stepped.current_position());
+            assert_eq!(bulk.char_index(), stepped.char_index());
+        }
+    }
+}